@@ -1,5 +1,5 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use ion_binary_rs::{IonEncoder, IonParser};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ion_binary_rs::{IonEncoder, IonParser, IonSliceParser, IonValue, IonVersion};
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("ion decode simple", |b| {
@@ -10,6 +10,14 @@ fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("ion slice decode simple", |b| {
+        let ion_test = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+
+        b.iter(|| {
+            IonSliceParser::new(black_box(&ion_test[..])).consume_all()
+        })
+    });
+
     c.bench_function("bson decode simple", |b| {
 
         let bson_test = b"\x71\x00\x00\x00\x02\x4d\x6f\x64\x65\x6c\x00\x08\x00\x00\x00\x43\x4c\x4b\x20\x33\x35\x30\x00\x02\x54\x79\x70\x65\x00\x06\x00\x00\x00\x53\x65\x64\x61\x6e\x00\x02\x43\x6f\x6c\x6f\x72\x00\x06\x00\x00\x00\x57\x68\x69\x74\x65\x00\x02\x56\x49\x4e\x00\x12\x00\x00\x00\x31\x43\x34\x52\x4a\x46\x41\x47\x30\x46\x43\x36\x32\x35\x37\x39\x37\x00\x02\x4d\x61\x6b\x65\x00\x09\x00\x00\x00\x4d\x65\x72\x63\x65\x64\x65\x73\x00\x10\x59\x65\x61\x72\x00\xe3\x07\x00\x00\x00";
@@ -30,6 +38,31 @@ fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("ion encode_into simple", |b| {
+        let ion_test = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+        let value = IonParser::new(black_box(&ion_test[..])).consume_value().unwrap();
+        let mut encoder = IonEncoder::new();
+        let mut buffer = Vec::new();
+
+        b.iter(|| {
+            buffer.clear();
+            encoder.add(value.0.clone());
+            encoder.encode_into(&mut buffer);
+        })
+    });
+
+    c.bench_function("ion encode simple ion 1.1", |b| {
+        let ion_test = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+        let value = IonParser::new(black_box(&ion_test[..])).consume_value().unwrap();
+
+        b.iter(|| {
+            let mut encoder = IonEncoder::new();
+            encoder.with_target_version(IonVersion::Ion1_1);
+            encoder.add(value.0.clone());
+            encoder.encode()
+        })
+    });
+
     c.bench_function("bson encode simple", |b| {
         let doc = bson::doc! {
            "Model": "CLK 350",
@@ -48,5 +81,34 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+// Every field name is a new symbol, so the time per field should stay the
+// same as the struct grows.
+fn symbols_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ion encode distinct field names");
+
+    for len in [1_000, 10_000, 100_000] {
+        let value = IonValue::OrderedStruct(
+            (0..len)
+                .map(|index| (format!("field_{}", index).into(), IonValue::Integer(index)))
+                .collect(),
+        );
+
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &value, |b, value| {
+            let mut buffer = Vec::new();
+
+            b.iter(|| {
+                buffer.clear();
+
+                let mut encoder = IonEncoder::new();
+                encoder.add(value.clone());
+                encoder.encode_into(&mut buffer);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, symbols_benchmark);
 criterion_main!(benches);
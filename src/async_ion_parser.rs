@@ -0,0 +1,207 @@
+use crate::catalog::Catalog;
+use crate::ion_parser::{IonParser, IonParserOptions};
+use crate::ion_parser_types::{IonParserError, IonValue, LocatedIonParserError};
+use crate::symbol_table::SymbolContextError;
+use crate::ParsingError;
+use std::io::Cursor;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Parses Ion binary from anything implementing tokio's `AsyncRead`, such as
+/// sockets or object-store streams, without buffering the whole payload.
+///
+/// Each call to `next_value` reads the bytes of the next top-level value,
+/// using the lengths in its headers, and decodes them with the same logic as
+/// `IonParser::consume_value`. Only one top-level value is kept in memory at
+/// a time, and the limits set with `with_options` are checked before its
+/// bytes are read.
+///
+/// Only Ion 1.0 is supported, as the value lengths are read from Ion 1.0
+/// headers. Ion 1.1 values return `Ion11NotSupported`.
+///
+/// ```rust
+/// use ion_binary_rs::{AsyncIonParser, IonEncoder, IonValue};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut encoder = IonEncoder::new();
+/// encoder.add(IonValue::Symbol("first".into()));
+/// encoder.add(IonValue::Integer(2));
+/// let bytes = encoder.encode();
+///
+/// let mut parser = AsyncIonParser::new(&bytes[..]);
+///
+/// assert_eq!(parser.next_value().await.unwrap(), Some(IonValue::Symbol("first".into())));
+/// assert_eq!(parser.next_value().await.unwrap(), Some(IonValue::Integer(2)));
+/// assert_eq!(parser.next_value().await.unwrap(), None);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncIonParser<R: AsyncRead + Unpin> {
+    reader: R,
+    // Decodes the bytes of each top-level value once they have been read,
+    // keeping the symbol tables and the position between values.
+    parser: IonParser<Cursor<Vec<u8>>>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncIonParser<R> {
+    pub fn new(reader: R) -> AsyncIonParser<R> {
+        AsyncIonParser {
+            reader,
+            parser: IonParser::new(Cursor::new(Vec::new())),
+        }
+    }
+
+    /// Same as `IonParser::with_shared_table`.
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        self.parser.with_shared_table(name, version, symbols)
+    }
+
+    /// Same as `IonParser::with_catalog`.
+    pub fn with_catalog<C: Catalog + 'static>(&mut self, catalog: C) {
+        self.parser.with_catalog(catalog);
+    }
+
+    /// Same as `IonParser::with_ordered_structs`.
+    pub fn with_ordered_structs(&mut self) {
+        self.parser.with_ordered_structs();
+    }
+
+    /// Sets the limits checked while parsing, see `IonParserOptions`.
+    pub fn with_options(&mut self, options: IonParserOptions) {
+        self.parser.with_options(options);
+    }
+
+    /// Returns the amount of bytes decoded so far.
+    pub fn position(&self) -> usize {
+        self.parser.position()
+    }
+
+    /// Reads and returns the next IonValue, processing the NOP Padding, Shared
+    /// Tables and Local Tables found before it. Returns None once the stream
+    /// ends. Errors tell where they were found, as the ones of
+    /// `IonParser::consume_value_located`.
+    pub async fn next_value(&mut self) -> Result<Option<IonValue>, LocatedIonParserError> {
+        loop {
+            let frame = self.parser.get_mut();
+            frame.get_mut().clear();
+            frame.set_position(0);
+
+            let result = match self.read_frame().await {
+                Ok(()) => self.parser.consume_next_item(),
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Ok(None) => continue,
+                Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => return Ok(None),
+                result => {
+                    return self
+                        .parser
+                        .locate(result)
+                        .map(|value| value.map(|value| value.0))
+                }
+            }
+        }
+    }
+
+    /// Reads the bytes of the next top-level value, including the version
+    /// markers before it. It stops early when the stream ends or a limit is
+    /// exceeded, leaving the decoder to report it.
+    async fn read_frame(&mut self) -> Result<(), IonParserError> {
+        loop {
+            let byte = match self.read_byte().await? {
+                Some(byte) => byte,
+                None => return Ok(()),
+            };
+
+            if byte == 0xE0 {
+                self.read_bytes(3).await?;
+                continue;
+            }
+
+            let value_type = byte >> 4;
+            let value_len = byte & 0b0000_1111;
+
+            // Nulls and bools don't have a body
+            if value_len == 15 || value_type == 1 {
+                return Ok(());
+            }
+
+            let length = if value_len == 14 || (value_type == 13 && value_len == 1) {
+                match self.read_varuint().await? {
+                    Some(length) => length,
+                    None => return Ok(()),
+                }
+            } else {
+                value_len.into()
+            };
+
+            if !self.exceeds_limits(length) {
+                self.read_bytes(length).await?;
+            }
+
+            return Ok(());
+        }
+    }
+
+    fn exceeds_limits(&self, length: usize) -> bool {
+        let options = self.parser.get_options();
+        let frame_end = self.parser.position() + self.parser.get_ref().get_ref().len();
+
+        let exceeds_value_size = matches!(options.max_value_size, Some(max) if length > max);
+        let exceeds_total_input =
+            matches!(options.max_total_input, Some(max) if frame_end.saturating_add(length) > max);
+
+        exceeds_value_size || exceeds_total_input
+    }
+
+    /// Reads a VarUInt, returning None if the stream ends before it does or
+    /// if it doesn't fit in an usize.
+    async fn read_varuint(&mut self) -> Result<Option<usize>, IonParserError> {
+        let mut number: usize = 0;
+
+        loop {
+            let byte = match self.read_byte().await? {
+                Some(byte) => byte,
+                None => return Ok(None),
+            };
+
+            number = match number
+                .checked_mul(128)
+                .and_then(|number| number.checked_add((byte & 0b0111_1111).into()))
+            {
+                Some(number) => number,
+                None => return Ok(None),
+            };
+
+            if byte & 0b1000_0000 != 0 {
+                return Ok(Some(number));
+            }
+        }
+    }
+
+    async fn read_byte(&mut self) -> Result<Option<u8>, IonParserError> {
+        match self.read_bytes(1).await? {
+            0 => Ok(None),
+            _ => Ok(self.parser.get_ref().get_ref().last().copied()),
+        }
+    }
+
+    /// Appends up to `length` bytes to the frame, returning how many were
+    /// read. Fewer bytes are only returned when the stream ends.
+    async fn read_bytes(&mut self, length: usize) -> Result<usize, IonParserError> {
+        let frame = self.parser.get_mut().get_mut();
+
+        let read_bytes = (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(frame)
+            .await
+            .map_err(ParsingError::ErrorReadingData)?;
+
+        Ok(read_bytes)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::ion_writer::{BufferedEncoder, DEFAULT_FLUSH_THRESHOLD};
+use crate::IonValue;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Same as the `IonWriter`, but writing into anything implementing tokio's
+/// `AsyncWrite`. Values are buffered and written with their symbol tables in
+/// the same way, so both produce the same bytes.
+///
+/// The `flush` or `finish` methods need to be called at the end in order to
+/// write the remaining values. Neither of them shuts down the underlying
+/// writer.
+///
+/// ```rust
+/// use ion_binary_rs::{AsyncIonParser, AsyncIonWriter, IonValue};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut writer = AsyncIonWriter::new(Vec::new());
+///
+/// for index in 0..1000 {
+///     writer.write(&IonValue::Integer(index)).await.unwrap();
+/// }
+///
+/// let bytes = writer.finish().await.unwrap();
+///
+/// let mut parser = AsyncIonParser::new(&bytes[..]);
+///
+/// assert_eq!(parser.next_value().await.unwrap(), Some(IonValue::Integer(0)));
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncIonWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    encoder: BufferedEncoder,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncIonWriter<W> {
+    pub fn new(writer: W) -> AsyncIonWriter<W> {
+        AsyncIonWriter::with_flush_threshold(writer, DEFAULT_FLUSH_THRESHOLD)
+    }
+
+    /// Same as `IonWriter::with_flush_threshold`.
+    pub fn with_flush_threshold(writer: W, flush_threshold: usize) -> AsyncIonWriter<W> {
+        AsyncIonWriter {
+            writer,
+            encoder: BufferedEncoder::new(flush_threshold),
+        }
+    }
+
+    /// Writes floats in 4 bytes when no precision is lost, see
+    /// `IonEncoder::with_compact_floats`.
+    pub fn with_compact_floats(&mut self) {
+        self.encoder.with_compact_floats();
+    }
+
+    pub async fn write(&mut self, value: &IonValue) -> std::io::Result<()> {
+        self.encoder.add(value);
+        self.write_output().await
+    }
+
+    /// Writes all the buffered values and flushes the underlying writer.
+    pub async fn flush(&mut self) -> std::io::Result<()> {
+        self.encoder.flush_values();
+        self.write_output().await?;
+        self.writer.flush().await
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Flushes the writer and returns the underlying one.
+    pub async fn finish(mut self) -> std::io::Result<W> {
+        self.flush().await?;
+        Ok(self.writer)
+    }
+
+    async fn write_output(&mut self) -> std::io::Result<()> {
+        if !self.encoder.output().is_empty() {
+            self.writer.write_all(self.encoder.output()).await?;
+            self.encoder.clear_output();
+        }
+
+        Ok(())
+    }
+}
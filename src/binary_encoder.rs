@@ -1,7 +1,7 @@
+use crate::ion_timestamp::{IonTimestamp, TimestampPrecision};
 use crate::NullIonValue;
-use bigdecimal::{BigDecimal, Zero};
-use chrono::{DateTime, Datelike, FixedOffset, Timelike};
-use num_bigint::{BigInt, BigUint, Sign};
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use num_bigint::{BigInt, Sign};
 use std::convert::TryFrom;
 
 pub const ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED: u8 = 14;
@@ -27,6 +27,7 @@ pub fn encode_ion_value(value: &IonValue) -> Vec<u8> {
     }
 }
 
+#[cfg(any(test, feature = "serde"))]
 pub fn encode_bool(value: &bool) -> Vec<u8> {
     let mut buffer = vec![];
 
@@ -43,6 +44,7 @@ pub fn encode_bool_buffer<'a>(buffer: &'a mut Vec<u8>, value: &bool) -> &'a mut
     buffer
 }
 
+#[cfg(any(test, feature = "serde"))]
 pub fn encode_null(value: &NullIonValue) -> Vec<u8> {
     let mut buffer = vec![];
 
@@ -70,7 +72,7 @@ pub fn encode_null_buffer<'a>(buffer: &'a mut Vec<u8>, value: &NullIonValue) ->
     buffer
 }
 
-pub fn encode_datetime_representation(value: &DateTime<FixedOffset>) -> Vec<u8> {
+pub fn encode_datetime_representation(value: &IonTimestamp) -> Vec<u8> {
     let mut buffer = vec![];
 
     encode_datetime_representation_buffer(&mut buffer, value).to_vec()
@@ -78,75 +80,63 @@ pub fn encode_datetime_representation(value: &DateTime<FixedOffset>) -> Vec<u8>
 
 pub fn encode_datetime_representation_buffer<'a>(
     buffer: &'a mut Vec<u8>,
-    value: &DateTime<FixedOffset>,
+    value: &IonTimestamp,
 ) -> &'a mut Vec<u8> {
-    let datetime = value.naive_utc();
-
-    let year = datetime.year();
-    let month = datetime.month();
-    let day = datetime.day();
-    let hour = datetime.hour();
-    let minute = datetime.minute();
-    let second = datetime.second();
-    let mut nanosecond = datetime.nanosecond();
-
-    // Accounting for the case of a leap second, which shouldn't ever happen.
-    // https://docs.rs/chrono/0.4.19/chrono/naive/struct.NaiveTime.html#leap-second-handling
-    if nanosecond > 1_000_000_000 {
-        nanosecond -= 1_000_000_000;
-    }
-
-    // This gives us a maximum decimal precision of 9 places.
-    // It will use less bytes if the number needs less. 23.100 seconds will become 23.1.
-    //
-    // This means that this implementation is not fully following the Ion Spec.
-    // In an Ion Timestamp 23.100 seconds are not the same as 23.1 seconds. An Ion
-    // Timestamp comparison between two dates representing the same moment but with
-    // different number of zeros in the seconds value results in "not equal". Given
-    // that we use DateTime type for the decoded value we loose the original stored
-    // precision. We assume that the precision is the lowest one that doesn't
-    // loose data. So equality comparisons in this library are less strict than in
-    // the Ion standard.
-    //
-    // Additionally, the ISO standard doesn't caps the maximum quantity of decimals
-    // in a seconds, but many implementations do. For example, nodejs rounds to 3
-    // decimals, so 23.999 seconds are 23.999 but 23.9999 are 24 seconds.
-    //
-    // If you are comparing Ion Timestamps and expect the equality to be an Ion
-    // equality operation or if you are comparing hashes hashed in Rust and other
-    // languages you may end with unexpected results.
-    let nanosecond: BigDecimal = BigDecimal::from(nanosecond) / BigDecimal::from(1_000_000_000);
-
-    let (coefficient, exponent) = nanosecond.as_bigint_and_exponent();
+    let [year, month, day, hour, minute, second] = value.utc_components();
+    let precision = value.precision();
 
-    let coefficient = BigInt::from_signed_bytes_le(&coefficient.to_signed_bytes_le());
+    match value.offset() {
+        Some(offset) => {
+            let unsigned_offset = offset.unsigned_abs().to_be_bytes();
+            buffer.append(&mut encode_varint(&unsigned_offset, offset.is_negative()));
+        }
+        // The unknown offset (-00:00) is a negative zero.
+        None => buffer.push(0xC0),
+    }
 
-    let exponent = -exponent;
+    buffer.append(&mut encode_varuint(&year.to_be_bytes()));
 
-    let exponent_bytes = exponent.abs().to_be_bytes();
-    let exponent_bytes = filter_significant_bytes_slice(&exponent_bytes);
+    // Timestamp precision in term of components (day, hour, seconds, etc)
+    // depends of the representation.
+    // 2011-01-01T00:00Z encodes to 80 0F DB 81 81 80 80
+    // 2011-01-01T00:00:00Z encodes to 80 0F DB 81 81 80 80 80 even
+    // if the seconds are 0.
+    if precision >= TimestampPrecision::Month {
+        buffer.append(&mut encode_varuint(&month.to_be_bytes()));
+    }
+
+    if precision >= TimestampPrecision::Day {
+        buffer.append(&mut encode_varuint(&day.to_be_bytes()));
+    }
 
-    let offset = value.offset().local_minus_utc() / 60;
+    if precision >= TimestampPrecision::Minute {
+        buffer.append(&mut encode_varuint(&hour.to_be_bytes()));
+        buffer.append(&mut encode_varuint(&minute.to_be_bytes()));
+    }
 
-    let unsigned_offset = offset.unsigned_abs().to_be_bytes();
+    if precision >= TimestampPrecision::Second {
+        buffer.append(&mut encode_varuint(&second.to_be_bytes()));
+    }
 
-    buffer.append(&mut encode_varint(&unsigned_offset, offset.is_negative()));
-    buffer.append(&mut encode_varuint(&year.to_be_bytes()));
-    buffer.append(&mut encode_varuint(&month.to_be_bytes()));
-    buffer.append(&mut encode_varuint(&day.to_be_bytes()));
-    buffer.append(&mut encode_varuint(&hour.to_be_bytes()));
-    buffer.append(&mut encode_varuint(&minute.to_be_bytes()));
-    buffer.append(&mut encode_varuint(&second.to_be_bytes()));
+    // The fraction keeps its scale, so 23.100 seconds are encoded with a
+    // -3 exponent and 23.1 seconds with a -1 one, as they are different
+    // Ion values.
+    if let Some(fraction) = value.fraction() {
+        let (coefficient, exponent) = fraction.as_bigint_and_exponent();
+
+        let coefficient = BigInt::from_signed_bytes_le(&coefficient.to_signed_bytes_le());
+
+        let exponent = -exponent;
+
+        let exponent_bytes = exponent.unsigned_abs().to_be_bytes();
+        let exponent_bytes = filter_significant_bytes_slice(&exponent_bytes);
+
+        if exponent_bytes.is_empty() {
+            buffer.push(0x80);
+        } else {
+            buffer.append(&mut encode_varint(exponent_bytes, exponent.is_negative()));
+        }
 
-    // Timestamp precision in term of components (day, hour, seconds, etc)
-    // depends of the representation.
-    // 2011-01-01T00 encodes to 80 0F DB 81 81 80
-    // 2011-01-01T00:00:00+00:00 encodes to 80 0F DB 81 81 80 80 80 even
-    // if the minutes and seconds are 0.
-    // We don't know the original represented precision, so we use seconds
-    // or fractional seconds.
-    if !exponent.is_zero() && !coefficient.is_zero() {
-        buffer.append(&mut encode_varint(exponent_bytes, exponent.is_negative()));
         if !coefficient.is_zero() {
             buffer.append(&mut encode_int(&coefficient));
         }
@@ -155,11 +145,11 @@ pub fn encode_datetime_representation_buffer<'a>(
     buffer
 }
 
-pub fn encode_datetime(value: &DateTime<FixedOffset>) -> Vec<u8> {
+pub fn encode_datetime(value: &IonTimestamp) -> Vec<u8> {
     let mut buffer = encode_datetime_representation(value);
 
     let len = buffer.len();
-    let mut len_bytes = filter_significant_bytes_slice(&len.to_be_bytes()).to_vec();
+    let mut len_bytes = encode_varuint(&len.to_be_bytes());
 
     let has_length_field = len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into();
 
@@ -174,6 +164,7 @@ pub fn encode_datetime(value: &DateTime<FixedOffset>) -> Vec<u8> {
     buffer
 }
 
+#[cfg(any(test, feature = "serde"))]
 pub fn encode_blob(header: u8, value: &[u8]) -> Vec<u8> {
     let mut buffer = vec![];
 
@@ -185,45 +176,51 @@ pub fn encode_blob_buffer<'a>(
     header: u8,
     value: &[u8],
 ) -> &'a mut Vec<u8> {
-    let len = value.len();
+    encode_header_buffer(buffer, header, value.len());
 
-    let header = header << 4;
+    buffer.extend_from_slice(value);
 
-    let has_len_field = len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into();
+    buffer
+}
+
+/// Bytes taken by the type descriptor and the length field of a value
+/// whose content takes `len` bytes.
+pub fn header_len(len: usize) -> usize {
+    if len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into() {
+        1 + varuint_len(len)
+    } else {
+        1
+    }
+}
 
-    let len_bytes = encode_varuint(&len.to_be_bytes());
+/// Writes the type descriptor, with the type in the high nibble, and the
+/// length field if needed, for a value whose content takes `len` bytes.
+pub fn encode_header_buffer(buffer: &mut Vec<u8>, ion_type: u8, len: usize) {
+    let header = ion_type << 4;
 
-    if has_len_field {
+    if len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into() {
         buffer.push(header + ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED);
+        encode_varuint_buffer(buffer, len);
     } else {
         // Impossible error due to the check of len with
         // ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED
         buffer.push(header + u8::try_from(len).expect("Impossible error"));
-    };
-
-    if has_len_field {
-        for value in len_bytes {
-            buffer.push(value);
-        }
-    }
-
-    for value in value {
-        buffer.push(*value);
     }
-
-    buffer
 }
 
 pub fn encode_decimal(value: &BigDecimal) -> Vec<u8> {
-    if *value == BigDecimal::from(0) {
+    // BigDecimal keeps the scale, which is the Ion exponent negated
+    let (coefficient, scale) = value.as_bigint_and_exponent();
+
+    // Zeros with an exponent, like 0.0 or 0e5, keep it
+    if coefficient.is_zero() && scale == 0 {
         return vec![0x50];
     }
 
-    let (coefficient, exponent) = value.as_bigint_and_exponent();
     let coefficient = BigInt::from_signed_bytes_le(&coefficient.to_signed_bytes_le());
-    let exponent_bytes = exponent.to_be_bytes();
+    let exponent_bytes = scale.unsigned_abs().to_be_bytes();
     let exponent_bytes = filter_significant_bytes_slice(&exponent_bytes);
-    let mut exponent_bytes = encode_varint(exponent_bytes, !exponent.is_negative());
+    let mut exponent_bytes = encode_varint(exponent_bytes, scale > 0);
     if exponent_bytes.is_empty() {
         // 0x80 = 0 positive in VarInt 0x_1_0_00_0000
         exponent_bytes = vec![0x80];
@@ -289,31 +286,40 @@ pub fn encode_int(value: &BigInt) -> Vec<u8> {
     value_bytes
 }
 
-pub fn encode_uint(value: &BigUint) -> Vec<u8> {
-    value.to_bytes_be()
-}
-
+#[cfg(any(test, feature = "serde"))]
 pub fn encode_float64(value: &f64) -> Vec<u8> {
     if *value == 0.0 && value.is_sign_positive() {
         return vec![0x40];
     }
 
-    let mut buffer: Vec<u8> = vec![0; 9];
+    let mut buffer = vec![0x48];
 
-    buffer[0] = 0x48;
+    buffer.extend_from_slice(&value.to_be_bytes());
 
-    let bytes = value.to_be_bytes();
+    buffer
+}
 
-    buffer[1] = bytes[0];
-    buffer[2] = bytes[1];
-    buffer[3] = bytes[2];
-    buffer[4] = bytes[3];
-    buffer[5] = bytes[4];
-    buffer[6] = bytes[5];
-    buffer[7] = bytes[6];
-    buffer[8] = bytes[7];
+/// Same as `encode_integer`, without going through a BigInt.
+pub fn encode_int64_buffer(buffer: &mut Vec<u8>, value: i64) {
+    if value == 0 {
+        buffer.push(0x20);
+        return;
+    }
 
-    buffer
+    let ion_type = if value < 0 { 3 } else { 2 };
+
+    let bytes = value.unsigned_abs().to_be_bytes();
+    let bytes = filter_significant_bytes_slice(&bytes);
+
+    encode_header_buffer(buffer, ion_type, bytes.len());
+    buffer.extend_from_slice(bytes);
+}
+
+pub fn int64_len(value: i64) -> usize {
+    let zero_bytes = value.unsigned_abs().leading_zeros() as usize / 8;
+
+    // Zero takes only the type descriptor
+    1 + 8 - zero_bytes.min(8)
 }
 
 pub fn encode_integer(value: &BigInt) -> Vec<u8> {
@@ -409,6 +415,28 @@ pub fn encode_varuint(value: &[u8]) -> Vec<u8> {
     encode_var(value)
 }
 
+/// Number of bytes of the VarUInt representation of the value.
+pub fn varuint_len(value: usize) -> usize {
+    let significant_bits = (usize::BITS - value.leading_zeros()) as usize;
+
+    div_ceil(significant_bits, 7).max(1)
+}
+
+/// Same as `encode_varuint`, writing at the end of the buffer.
+pub fn encode_varuint_buffer(buffer: &mut Vec<u8>, value: usize) {
+    let len = varuint_len(value);
+
+    for index in (0..len).rev() {
+        let byte = ((value >> (index * 7)) & 0b_0111_1111) as u8;
+
+        if index == 0 {
+            buffer.push(byte | 0b_1000_0000);
+        } else {
+            buffer.push(byte);
+        }
+    }
+}
+
 pub fn encode_varint(value: &[u8], is_negative: bool) -> Vec<u8> {
     if value.is_empty() {
         return vec![];
@@ -420,8 +448,10 @@ pub fn encode_varint(value: &[u8], is_negative: bool) -> Vec<u8> {
         if is_negative {
             buffer[0] |= 0b_0100_0000;
         }
-    } else {
+    } else if is_negative {
         buffer.insert(0, 0b_0100_0000)
+    } else {
+        buffer.insert(0, 0)
     }
 
     buffer
@@ -549,6 +579,296 @@ pub fn encode_var(input: &[u8]) -> Vec<u8> {
     buffer_output
 }
 
+/// Number of bytes of the FlexUInt representation of the value.
+pub fn flex_uint_len(value: usize) -> usize {
+    let significant_bits = (usize::BITS - value.leading_zeros()) as usize;
+
+    div_ceil(significant_bits, 7).max(1)
+}
+
+/// Writes the Ion 1.1 FlexUInt of the value. The length of the field is
+/// the amount of trailing zeros plus one, so the number goes after them.
+pub fn encode_flex_uint_buffer(buffer: &mut Vec<u8>, value: usize) {
+    let len = flex_uint_len(value);
+    let bits = (value as u128) << len | 1 << (len - 1);
+
+    buffer.extend_from_slice(&bits.to_le_bytes()[..len]);
+}
+
+/// Number of bytes of the FlexInt representation of the value.
+pub fn flex_int_len(value: i128) -> usize {
+    let magnitude = if value < 0 { !value } else { value };
+
+    // Significant bits plus the sign one
+    let significant_bits = (i128::BITS - magnitude.leading_zeros()) as usize + 1;
+
+    div_ceil(significant_bits, 7)
+}
+
+/// Same as `encode_flex_uint_buffer`, with the number in two's complement.
+pub fn encode_flex_int_buffer(buffer: &mut Vec<u8>, value: i128) {
+    let len = flex_int_len(value);
+    let bits = value << len | 1 << (len - 1);
+
+    buffer.extend_from_slice(&bits.to_le_bytes()[..len]);
+}
+
+/// Bytes taken by an Ion 1.1 opcode whose value takes `len` bytes. The
+/// length goes in the opcode when it is at most `max_short_len`, and in a
+/// FlexUInt after it otherwise.
+pub fn opcode_len_1_1(len: usize, max_short_len: usize) -> usize {
+    if len > max_short_len {
+        1 + flex_uint_len(len)
+    } else {
+        1
+    }
+}
+
+/// Writes the opcode returned by `opcode_len_1_1`, `short_opcode` plus the
+/// length or `long_opcode` followed by the length.
+pub fn encode_opcode_buffer_1_1(
+    buffer: &mut Vec<u8>,
+    short_opcode: u8,
+    long_opcode: u8,
+    max_short_len: usize,
+    len: usize,
+) {
+    if len > max_short_len {
+        buffer.push(long_opcode);
+        encode_flex_uint_buffer(buffer, len);
+    } else {
+        // Impossible error as max_short_len is never bigger than 15
+        buffer.push(short_opcode + u8::try_from(len).expect("Impossible error"));
+    }
+}
+
+pub fn encode_null_buffer_1_1(buffer: &mut Vec<u8>, value: &NullIonValue) {
+    let null_type = match value {
+        // Ion 1.1 doesn't have a null annotation wrapper
+        NullIonValue::Null | NullIonValue::Annotation => {
+            buffer.push(0xEA);
+            return;
+        }
+        NullIonValue::Bool => 0,
+        NullIonValue::Integer => 1,
+        NullIonValue::Float => 2,
+        NullIonValue::Decimal => 3,
+        NullIonValue::DateTime => 4,
+        NullIonValue::String => 5,
+        NullIonValue::Symbol => 6,
+        NullIonValue::Blob => 7,
+        NullIonValue::Clob => 8,
+        NullIonValue::List => 9,
+        NullIonValue::SExpr => 10,
+        NullIonValue::Struct => 11,
+    };
+
+    buffer.extend_from_slice(&[0xEB, null_type]);
+}
+
+/// Bytes of the Ion 1.1 FixedInt of the value, none for zero.
+pub fn fixed_int64_len(value: i64) -> usize {
+    if value == 0 {
+        return 0;
+    }
+
+    let magnitude = if value < 0 { !value } else { value };
+
+    // Significant bits plus the sign one
+    div_ceil((i64::BITS - magnitude.leading_zeros()) as usize + 1, 8)
+}
+
+/// Ion 1.1 integers are little-endian FixedInts of up to 8 bytes after
+/// the 0x60 to 0x68 opcodes.
+pub fn encode_int64_buffer_1_1(buffer: &mut Vec<u8>, value: i64) {
+    let len = fixed_int64_len(value);
+
+    // Impossible error as the length is at most 8
+    buffer.push(0x60 + u8::try_from(len).expect("Impossible error"));
+    buffer.extend_from_slice(&value.to_le_bytes()[..len]);
+}
+
+pub fn encode_integer_1_1(value: &BigInt) -> Vec<u8> {
+    if value.is_zero() {
+        return vec![0x60];
+    }
+
+    let bytes = value.to_signed_bytes_le();
+
+    let mut buffer = Vec::with_capacity(opcode_len_1_1(bytes.len(), 8) + bytes.len());
+
+    encode_opcode_buffer_1_1(&mut buffer, 0x60, 0xF6, 8, bytes.len());
+    buffer.extend_from_slice(&bytes);
+
+    buffer
+}
+
+//            +=========================+
+// Decimal    :   exponent (FlexInt)    :
+//            +-------------------------+
+//            : coefficient (FixedInt)  :
+//            +=========================+
+//
+// A zero coefficient takes no bytes, so zeros keep their exponent.
+pub fn encode_decimal_1_1(value: &BigDecimal) -> Vec<u8> {
+    let (coefficient, scale) = value.as_bigint_and_exponent();
+
+    if coefficient.is_zero() && scale == 0 {
+        return vec![0x70];
+    }
+
+    let mut content = vec![];
+
+    encode_flex_int_buffer(&mut content, -i128::from(scale));
+
+    if !coefficient.is_zero() {
+        content.extend_from_slice(&coefficient.to_signed_bytes_le());
+    }
+
+    let mut buffer = Vec::with_capacity(opcode_len_1_1(content.len(), 15) + content.len());
+
+    encode_opcode_buffer_1_1(&mut buffer, 0x70, 0xF7, 15, content.len());
+    buffer.append(&mut content);
+
+    buffer
+}
+
+/// Ion 1.1 timestamp, in the short form when the year is between 1970 and
+/// 2097, the offset is in quarters of an hour and the fraction has
+/// milliseconds, microseconds or nanoseconds. Otherwise it takes the long
+/// one. The components are written in UTC in both forms.
+pub fn encode_datetime_1_1(value: &IonTimestamp) -> Vec<u8> {
+    if let Some(buffer) = encode_short_datetime_1_1(value) {
+        return buffer;
+    }
+
+    let [year, month, day, hour, minute, second] = value.utc_components();
+    let precision = value.precision();
+
+    let mut bits = u64::from(year);
+
+    if precision >= TimestampPrecision::Month {
+        bits |= u64::from(month) << 14;
+    }
+
+    if precision >= TimestampPrecision::Day {
+        bits |= u64::from(day) << 18;
+    }
+
+    let len = match precision {
+        TimestampPrecision::Year => 2,
+        TimestampPrecision::Month | TimestampPrecision::Day => 3,
+        _ => {
+            let offset = match value.offset() {
+                // Impossible error as offsets are smaller than a day
+                Some(offset) => u64::try_from(offset + 1440).expect("Impossible error"),
+                None => 0b1111_1111_1111,
+            };
+
+            bits |= u64::from(hour) << 23 | u64::from(minute) << 28 | offset << 34;
+
+            if precision == TimestampPrecision::Minute {
+                6
+            } else {
+                bits |= u64::from(second) << 46;
+                7
+            }
+        }
+    };
+
+    let mut content = bits.to_le_bytes()[..len].to_vec();
+
+    if let Some(fraction) = value.fraction() {
+        let (coefficient, scale) = fraction.as_bigint_and_exponent();
+
+        // Only zero can have a negative scale, as fractions are below one
+        encode_flex_uint_buffer(&mut content, usize::try_from(scale).unwrap_or(0));
+
+        if !coefficient.is_zero() {
+            content.extend_from_slice(&coefficient.to_bytes_le().1);
+        }
+    }
+
+    let mut buffer = vec![0xF8];
+
+    encode_flex_uint_buffer(&mut buffer, content.len());
+    buffer.append(&mut content);
+
+    buffer
+}
+
+// See `consume_short_timestamp_1_1` for the layout of the bits.
+fn encode_short_datetime_1_1(value: &IonTimestamp) -> Option<Vec<u8>> {
+    let [year, month, day, hour, minute, second] = value.utc_components();
+
+    if !(1970..1970 + 128).contains(&year) {
+        return None;
+    }
+
+    let mut bits = u128::from(year - 1970);
+
+    let (opcode, bits_len) = match value.precision() {
+        TimestampPrecision::Year => (0x80, 7),
+        TimestampPrecision::Month => {
+            bits |= u128::from(month) << 7;
+            (0x81, 11)
+        }
+        TimestampPrecision::Day => {
+            bits |= u128::from(month) << 7 | u128::from(day) << 11;
+            (0x82, 16)
+        }
+        precision => {
+            bits |= u128::from(month) << 7
+                | u128::from(day) << 11
+                | u128::from(hour) << 16
+                | u128::from(minute) << 21;
+
+            let (opcode, second_start) = match value.offset() {
+                None => (0x83, 28),
+                Some(0) => {
+                    bits |= 1 << 27;
+                    (0x83, 28)
+                }
+                Some(offset) if offset % 15 == 0 && (-56..72).contains(&(offset / 15)) => {
+                    bits |= u128::try_from(offset / 15 + 56).ok()? << 27;
+                    (0x88, 34)
+                }
+                Some(_) => return None,
+            };
+
+            if precision == TimestampPrecision::Minute {
+                (opcode, second_start)
+            } else {
+                bits |= u128::from(second) << second_start;
+
+                match value.fraction() {
+                    None => (opcode + 1, second_start + 6),
+                    Some(fraction) => {
+                        let (coefficient, scale) = fraction.as_bigint_and_exponent();
+
+                        let (fraction_opcode, fraction_len) = match scale {
+                            3 => (opcode + 2, 10),
+                            6 => (opcode + 3, 20),
+                            9 => (opcode + 4, 30),
+                            _ => return None,
+                        };
+
+                        bits |= u128::from(coefficient.to_u64()?) << (second_start + 6);
+
+                        (fraction_opcode, second_start + 6 + fraction_len)
+                    }
+                }
+            }
+        }
+    };
+
+    let mut buffer = vec![opcode];
+
+    buffer.extend_from_slice(&bits.to_le_bytes()[..div_ceil(bits_len, 8)]);
+
+    Some(buffer)
+}
+
 pub const fn div_ceil(this: usize, rhs: usize) -> usize {
     let d = this / rhs;
     let r = this % rhs;
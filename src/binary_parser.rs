@@ -1,31 +1,92 @@
 use crate::binary_parser_types::*;
 use num_bigint::{BigInt, BigUint, Sign};
+use std::convert::TryFrom;
 use std::fmt::Debug;
-use std::io::Read;
+use std::io::{sink, Read, Seek, SeekFrom};
 
 pub struct IonBinaryParser<T: Read> {
-    reader: T,
+    reader: PositionReader<T>,
     current_ion_version: Option<(u8, u8)>,
     temp_buffer: Vec<u8>,
 }
 
+// Keeps track of how many bytes have been read from the stream so the
+// upper layers can know where a container ends without counting by hand.
+struct PositionReader<T: Read> {
+    reader: T,
+    position: usize,
+    // Only defined for readers implementing Seek, so skipped bytes can be
+    // seeked instead of read. See `IonBinaryParser::new_seekable`.
+    seek: Option<fn(&mut T, i64) -> std::io::Result<u64>>,
+}
+
+impl<T: Read> Read for PositionReader<T> {
+    #[inline]
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, std::io::Error> {
+        let read_bytes = self.reader.read(buffer)?;
+        self.position += read_bytes;
+        Ok(read_bytes)
+    }
+}
+
 impl<T: Read> IonBinaryParser<T> {
     #[inline]
     pub fn new(reader: T) -> IonBinaryParser<T> {
         IonBinaryParser {
-            reader,
+            reader: PositionReader {
+                reader,
+                position: 0,
+                seek: None,
+            },
             current_ion_version: None,
             temp_buffer: Vec::with_capacity(256),
         }
     }
 
+    /// Same as `new`, but skipped values are seeked instead of read.
+    #[inline]
+    pub fn new_seekable(reader: T) -> IonBinaryParser<T>
+    where
+        T: Seek,
+    {
+        let mut parser = IonBinaryParser::new(reader);
+
+        parser.reader.seek = Some(|reader, offset| reader.seek(SeekFrom::Current(offset)));
+
+        parser
+    }
+
+    /// Amount of bytes consumed from the underlying reader since the
+    /// parser was created.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.reader.position
+    }
+
+    /// Returns a reference to the underlying reader.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn get_ref(&self) -> &T {
+        &self.reader.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        &mut self.reader.reader
+    }
+
     #[inline]
     fn read(&mut self, buffer: &mut [u8]) -> Result<usize, std::io::Error> {
         IonBinaryParser::read_no_self(&mut self.reader, buffer)
     }
 
     #[inline]
-    fn read_no_self(reader: &mut T, buffer: &mut [u8]) -> Result<usize, std::io::Error> {
+    fn read_no_self(
+        reader: &mut PositionReader<T>,
+        buffer: &mut [u8],
+    ) -> Result<usize, std::io::Error> {
         reader.read(buffer)
     }
 
@@ -60,8 +121,39 @@ impl<T: Read> IonBinaryParser<T> {
         IonBinaryParser::read_bytes_no_self(&mut self.reader, buffer)
     }
 
+    /// Discards the given amount of bytes from the reader without
+    /// keeping them in memory. Seekable readers move forward without
+    /// reading, so a premature end of the stream is only detected on the
+    /// next read.
     #[inline]
-    pub fn read_bytes_no_self(reader: &mut T, buffer: &mut [u8]) -> Result<(), ParsingError> {
+    pub fn skip_bytes(&mut self, length: usize) -> Result<(), ParsingError> {
+        if length == 0 {
+            return Ok(());
+        }
+
+        if let Some(seek) = self.reader.seek {
+            let offset = i64::try_from(length).map_err(|_| ParsingError::InvalidHeaderLength)?;
+            seek(&mut self.reader.reader, offset)?;
+            self.reader.position += length;
+            return Ok(());
+        }
+
+        let skipped = std::io::copy(&mut (&mut self.reader).take(length as u64), &mut sink())?;
+
+        match skipped {
+            0 => Err(ParsingError::NoDataToRead),
+            skipped if skipped < length as u64 => {
+                Err(ParsingError::NotEnoughtDataToRead(skipped as usize))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn read_bytes_no_self(
+        reader: &mut PositionReader<T>,
+        buffer: &mut [u8],
+    ) -> Result<(), ParsingError> {
         let read_bytes = IonBinaryParser::<T>::read_no_self(reader, buffer);
 
         match read_bytes {
@@ -156,6 +248,14 @@ impl<T: Read> IonBinaryParser<T> {
     //                                 +--sign
     #[inline]
     pub fn consume_varint(&mut self) -> Result<(BigInt, usize), ParsingError> {
+        let (number, _, consumed) = self.consume_varint_with_sign()?;
+
+        Ok((number, consumed))
+    }
+
+    /// Same as `consume_varint` but it also returns the sign bit, so a
+    /// negative zero can be told apart from a zero.
+    pub fn consume_varint_with_sign(&mut self) -> Result<(BigInt, bool, usize), ParsingError> {
         let bytes = self.consume_var_number()?;
 
         for byte in bytes.iter_mut() {
@@ -175,7 +275,7 @@ impl<T: Read> IonBinaryParser<T> {
             number = -number;
         }
 
-        Ok((number, bytes.len()))
+        Ok((number, is_negative, bytes.len()))
     }
 
     // Note: Guarantees to return at least one byte if it succeed
@@ -205,12 +305,11 @@ impl<T: Read> IonBinaryParser<T> {
         Ok(&mut self.temp_buffer)
     }
 
-    //   7       4 3       0
-    //  +---------+---------+
-    //  |    T    |    L    |
-    //  +---------+---------+
+    /// Reads the first byte of a value, which is the type descriptor in Ion
+    /// 1.0 and the opcode in Ion 1.1. Version markers before it are consumed,
+    /// changing the current version.
     #[inline]
-    pub fn consume_value_header(&mut self, nested_level: u64) -> Result<ValueHeader, ParsingError> {
+    pub fn consume_opcode(&mut self, nested_level: u64) -> Result<u8, ParsingError> {
         let mut byte = [0u8; 1];
 
         let read_bytes = self.read(&mut byte);
@@ -230,10 +329,10 @@ impl<T: Read> IonBinaryParser<T> {
                     }
                     let version = self.consume_ion_version_once_identified()?;
                     self.set_current_ion_version(version);
-                    return self.consume_value_header(nested_level);
+                    return self.consume_opcode(nested_level);
                 }
 
-                ValueHeader::new(byte)
+                Ok(byte)
             }
         }
     }
@@ -267,6 +366,105 @@ impl<T: Read> IonBinaryParser<T> {
     fn set_current_ion_version(&mut self, version: (u8, u8)) {
         self.current_ion_version = Some(version);
     }
+
+    /// Whether the last version marker read was the Ion 1.1 one.
+    #[inline]
+    pub fn is_ion_1_1(&self) -> bool {
+        self.current_ion_version == Some((1, 1))
+    }
+
+    //              7                       0
+    //            +-------------------------+
+    // FixedUInt  |          bits           |
+    //            +-------------------------+
+    //            :          bits           :
+    //            +=========================+
+    //             n+7                     n
+    //
+    // Ion 1.1 fixed-width integers are little-endian, so the first byte
+    // holds the least significant bits.
+    #[inline]
+    pub fn consume_fixed_uint(&mut self, octets: usize) -> Result<BigUint, ParsingError> {
+        if octets == 0 {
+            return Ok(BigUint::from(0u8));
+        }
+
+        self.temp_buffer.resize(octets, 0);
+
+        IonBinaryParser::read_bytes_no_self(&mut self.reader, &mut self.temp_buffer)?;
+
+        Ok(BigUint::from_bytes_le(&self.temp_buffer))
+    }
+
+    // Same as the FixedUInt, but the number is in two's complement.
+    #[inline]
+    pub fn consume_fixed_int(&mut self, octets: usize) -> Result<BigInt, ParsingError> {
+        if octets == 0 {
+            return Ok(BigInt::from(0u8));
+        }
+
+        self.temp_buffer.resize(octets, 0);
+
+        IonBinaryParser::read_bytes_no_self(&mut self.reader, &mut self.temp_buffer)?;
+
+        Ok(BigInt::from_signed_bytes_le(&self.temp_buffer))
+    }
+
+    //                  7                     0       n+7                     n
+    //                +-----------------+-+---+     +-------------------------+
+    // FlexUInt field |  bits           |1|0 0| ... |          bits           |
+    //                +-----------------+-+---+     +-------------------------+
+    //
+    // The amount of trailing zeros in the first bytes, plus one, is the
+    // length of the field. The rest of the bits are the number, little-endian.
+    #[inline]
+    pub fn consume_flex_uint(&mut self) -> Result<(BigUint, usize), ParsingError> {
+        let length = self.consume_flex_bytes()?;
+        let number = BigUint::from_bytes_le(&self.temp_buffer) >> length;
+
+        Ok((number, length))
+    }
+
+    // Same as the FlexUInt, but the number is in two's complement.
+    #[inline]
+    pub fn consume_flex_int(&mut self) -> Result<(BigInt, usize), ParsingError> {
+        let length = self.consume_flex_bytes()?;
+        let number = BigInt::from_signed_bytes_le(&self.temp_buffer) >> length;
+
+        Ok((number, length))
+    }
+
+    // Reads the bytes of a FlexUInt or a FlexInt into the temp buffer and
+    // returns how many they are.
+    #[inline]
+    fn consume_flex_bytes(&mut self) -> Result<usize, ParsingError> {
+        let mut byte = [0u8; 1];
+
+        self.temp_buffer.clear();
+
+        let length = loop {
+            IonBinaryParser::read_bytes_no_self(&mut self.reader, &mut byte)?;
+            self.temp_buffer.push(byte[0]);
+
+            if byte[0] != 0 {
+                let zeros = (self.temp_buffer.len() - 1) * 8 + byte[0].trailing_zeros() as usize;
+                break zeros + 1;
+            }
+        };
+
+        let read_bytes = self.temp_buffer.len();
+
+        if length > read_bytes {
+            self.temp_buffer.resize(length, 0);
+
+            IonBinaryParser::read_bytes_no_self(
+                &mut self.reader,
+                &mut self.temp_buffer[read_bytes..],
+            )?;
+        }
+
+        Ok(length)
+    }
 }
 
 impl<T: Read> Debug for IonBinaryParser<T> {
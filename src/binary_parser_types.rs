@@ -13,6 +13,56 @@ pub const SYSTEM_SYMBOL_TABLE: &[&str; 10] = &[
     "$ion_shared_symbol_table",
 ];
 
+// Ion 1.1 system symbols, referenced with their address by the 0xEE opcode
+// and the FlexSym escapes. They are not part of the symbol tables.
+pub const SYSTEM_SYMBOL_TABLE_1_1: &[&str; 45] = &[
+    "$0",
+    "$ion",
+    "$ion_1_0",
+    "$ion_symbol_table",
+    "name",
+    "version",
+    "imports",
+    "symbols",
+    "max_id",
+    "$ion_shared_symbol_table",
+    "encoding",
+    "$ion_literal",
+    "$ion_shared_module",
+    "macro",
+    "macro_table",
+    "symbol_table",
+    "module",
+    "retain",
+    "export",
+    "catalog_key",
+    "import",
+    "",
+    "literal",
+    "if_none",
+    "if_some",
+    "if_single",
+    "if_multi",
+    "for",
+    "default",
+    "values",
+    "annotate",
+    "make_string",
+    "make_symbol",
+    "make_blob",
+    "make_decimal",
+    "make_timestamp",
+    "make_list",
+    "make_sexp",
+    "make_struct",
+    "parse_ion",
+    "repeat",
+    "delta",
+    "sum",
+    "meta",
+    "flatten",
+];
+
 #[allow(dead_code)]
 #[derive(Eq, PartialEq, Debug, Clone, Copy)]
 pub(crate) enum SystemSymbolIds {
@@ -147,7 +197,7 @@ impl PartialEq for ParsingError {
 //  +---------+---------+
 //  |    T    |    L    |
 //  +---------+---------+
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
 pub struct ValueHeader(u8);
 
 impl ValueHeader {
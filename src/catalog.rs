@@ -0,0 +1,161 @@
+use crate::symbol_table::{SharedSymbolTable, SymbolContextError};
+use crate::{IonParser, IonParserError, IonTextParser};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Source of shared symbol tables. Parsers and encoders ask the catalog for
+/// the tables imported by local symbol tables that they don't have yet, so
+/// they don't need to be added beforehand with `with_shared_table`.
+///
+/// When the requested version isn't available, implementations should
+/// return the highest version they have, as the Ion specification allows
+/// to use it when the import declares a `max_id`.
+pub trait Catalog: Debug + Send + Sync {
+    fn get_table(&self, name: &str, version: u32) -> Option<SharedSymbolTable>;
+}
+
+impl<C: Catalog + ?Sized> Catalog for Arc<C> {
+    fn get_table(&self, name: &str, version: u32) -> Option<SharedSymbolTable> {
+        (**self).get_table(name, version)
+    }
+}
+
+/// Errors that can happen while loading a catalog.
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    #[error("Error reading the catalog file")]
+    ErrorReadingFile(#[from] std::io::Error),
+    #[error("Error parsing the catalog file")]
+    ParserError(#[from] IonParserError),
+    #[error("Error adding the shared table into the catalog")]
+    ErrorAddingSharedTable(SymbolContextError),
+}
+
+/// Catalog keeping all its tables in memory.
+///
+/// ```rust
+/// use ion_binary_rs::{Catalog, InMemoryCatalog, SharedSymbolTable};
+///
+/// let mut catalog = InMemoryCatalog::new();
+///
+/// catalog
+///     .add_table(SharedSymbolTable::new("cars", 1, &["brand".to_string()]))
+///     .unwrap();
+///
+/// assert_eq!(catalog.get_table("cars", 1).unwrap().version(), 1);
+/// assert_eq!(catalog.get_table("cars", 2).unwrap().version(), 1);
+/// assert!(catalog.get_table("trucks", 1).is_none());
+/// ```
+#[derive(Debug, Default)]
+pub struct InMemoryCatalog {
+    tables: HashMap<String, BTreeMap<u32, SharedSymbolTable>>,
+}
+
+impl InMemoryCatalog {
+    pub fn new() -> InMemoryCatalog {
+        InMemoryCatalog::default()
+    }
+
+    pub fn add_table(&mut self, table: SharedSymbolTable) -> Result<(), SymbolContextError> {
+        let versions = self.tables.entry(table.name().to_string()).or_default();
+
+        if versions.contains_key(&table.version()) {
+            return Err(SymbolContextError::TableVersionAlreadyThere);
+        }
+
+        versions.insert(table.version(), table);
+
+        Ok(())
+    }
+}
+
+impl Catalog for InMemoryCatalog {
+    fn get_table(&self, name: &str, version: u32) -> Option<SharedSymbolTable> {
+        let versions = self.tables.get(name)?;
+
+        versions
+            .get(&version)
+            .or_else(|| versions.values().next_back())
+            .cloned()
+    }
+}
+
+/// Catalog with the shared tables declared (`$ion_shared_symbol_table::{...}`)
+/// in Ion files, either binary or text. The files are read when added, any
+/// other value in them is ignored.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{FileCatalog, IonParser};
+///
+/// let mut catalog = FileCatalog::new();
+///
+/// catalog.load_directory("./symbol_tables").unwrap();
+///
+/// let mut parser = IonParser::new(&[0xE0, 0x01, 0x00, 0xEA][..]);
+///
+/// parser.with_catalog(catalog);
+/// ```
+#[derive(Debug, Default)]
+pub struct FileCatalog {
+    catalog: InMemoryCatalog,
+}
+
+impl FileCatalog {
+    pub fn new() -> FileCatalog {
+        FileCatalog::default()
+    }
+
+    /// Adds the shared tables declared in the file.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CatalogError> {
+        let bytes = std::fs::read(path)?;
+
+        let tables = if bytes.starts_with(&[0xE0, 0x01, 0x00, 0xEA]) {
+            let mut parser = IonParser::new(&bytes[..]);
+            parser.consume_all()?;
+            parser.get_shared_tables()
+        } else {
+            let mut parser = IonTextParser::new(&bytes[..]);
+            parser.consume_all()?;
+            parser.get_shared_tables()
+        };
+
+        for table in tables {
+            self.catalog
+                .add_table(table)
+                .map_err(CatalogError::ErrorAddingSharedTable)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the shared tables declared in all the files of the directory.
+    /// Subdirectories are not read.
+    pub fn load_directory<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CatalogError> {
+        let mut paths = vec![];
+
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+
+        paths.sort();
+
+        for path in paths {
+            self.load_file(path)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Catalog for FileCatalog {
+    fn get_table(&self, name: &str, version: u32) -> Option<SharedSymbolTable> {
+        self.catalog.get_table(name, version)
+    }
+}
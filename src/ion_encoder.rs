@@ -1,13 +1,32 @@
 use crate::binary_encoder::{
-    encode_blob, encode_bool, encode_datetime, encode_decimal, encode_float64, encode_integer,
-    encode_null, encode_uint, encode_varuint, ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED,
+    encode_blob_buffer, encode_bool_buffer, encode_datetime, encode_decimal, encode_header_buffer,
+    encode_int64_buffer, encode_integer, encode_null_buffer, encode_varuint_buffer, header_len,
+    int64_len, varuint_len,
 };
+#[cfg(feature = "serde")]
+use crate::binary_encoder::{encode_varuint, ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED};
 use crate::binary_parser_types::{SystemSymbolIds, SYSTEM_SYMBOL_TABLE};
-use crate::symbol_table::SymbolContext;
-use crate::IonValue;
-use num_bigint::{BigInt, BigUint};
+use crate::catalog::Catalog;
+use crate::symbol_table::{Import, Symbol, SymbolContext, SymbolContextError};
+use crate::symbol_token::FieldName;
+#[cfg(feature = "serde")]
+use crate::SymbolToken;
+use crate::{ImportLocation, IonValue};
 use std::collections::HashMap;
+#[cfg(feature = "serde")]
 use std::convert::TryFrom;
+use std::sync::Arc;
+
+const ION_1_0_HEADER: [u8; 4] = [0xE0, 0x01, 0x00, 0xEA];
+const ION_1_1_HEADER: [u8; 4] = [0xE0, 0x01, 0x01, 0xEA];
+
+/// Version of the Ion binary format written by the `IonEncoder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IonVersion {
+    #[default]
+    Ion1_0,
+    Ion1_1,
+}
 
 /// Allows to binary encode one or multiple IonValue.
 ///
@@ -16,7 +35,34 @@ use std::convert::TryFrom;
 ///
 /// - `add` allows to add IonValue to the buffer to later encoding.
 /// - `encode` takes all biffered values and encodes them, generating
-/// the symbol's table and the ion header. It returns a Vec<u8>.
+///   the symbol's table and the ion header. It returns a Vec<u8>.
+///   `encode_into` does the same, appending them to a given buffer.
+///
+/// The symbol table is kept between calls to `encode`, so every output
+/// declares all the symbols used until then. Use `reset` in order to start
+/// again with an empty table, or `with_appended_symbol_tables` in order to
+/// write a single stream in chunks.
+///
+/// Values are encoded in two passes. The first one computes the length of
+/// every container, so the second one can write each value only once,
+/// directly in the output buffer.
+///
+/// `new_canonical` creates an encoder whose output only depends on the
+/// values, so equal values are always encoded to the same bytes, as needed
+/// for signatures or content addressing. In this mode:
+///
+/// - Struct fields are written sorted by name, OrderedStruct ones too.
+///   Fields with the same name keep their order.
+/// - Symbols are added to the symbol table in the order they are written,
+///   and every `encode` starts with an empty local table, so the table is
+///   the same for the same values. For the same reason
+///   `with_appended_symbol_tables` has no effect.
+/// - Floats that can be represented exactly in 32 bits take 4 bytes
+///   instead of 8.
+///
+/// Symbols without text are encoded as `$0`, unless they have an import
+/// location. In that case the symbol table imports the shared table they come
+/// from, so they keep the same meaning when parsed again.
 ///
 /// ```rust,no_run
 ///
@@ -49,7 +95,20 @@ use std::convert::TryFrom;
 #[derive(Debug)]
 pub struct IonEncoder {
     current_buffer: Vec<IonValue>,
-    symbol_table: SymbolContext,
+    pub(crate) symbol_table: SymbolContext,
+    pub(crate) plan: EncodingPlan,
+    // Imports added with `with_shared_table` and `with_import`, kept when
+    // the encoder is reset.
+    imports: Vec<Import>,
+    is_append_mode: bool,
+    // State of the stream being written, in order to declare only the new
+    // symbols when it continues.
+    declared_symbols: usize,
+    is_table_reset: bool,
+    is_header_written: bool,
+    // Times each symbol text is used by the values being encoded, only
+    // counted for Ion 1.1.
+    pub(crate) symbol_uses: HashMap<String, usize>,
 }
 
 impl Default for IonEncoder {
@@ -63,132 +122,466 @@ impl IonEncoder {
         IonEncoder {
             current_buffer: vec![],
             symbol_table: SymbolContext::new(),
+            plan: EncodingPlan::default(),
+            imports: vec![],
+            is_append_mode: false,
+            declared_symbols: 0,
+            is_table_reset: false,
+            is_header_written: false,
+            symbol_uses: HashMap::new(),
         }
     }
 
+    /// Creates an encoder in canonical mode, see the `IonEncoder` docs.
+    pub fn new_canonical() -> IonEncoder {
+        let mut encoder = IonEncoder::new();
+
+        encoder.plan.is_canonical = true;
+        encoder.plan.is_compact_floats = true;
+
+        encoder
+    }
+
+    /// Writes the floats that can be represented exactly in 32 bits, like
+    /// `0.5` or the ones read from 4 bytes floats, in 4 bytes instead of 8.
+    /// They are parsed to the same `f64`, so their value and their Ion hash
+    /// don't change.
+    pub fn with_compact_floats(&mut self) {
+        self.plan.is_compact_floats = true;
+    }
+
+    /// Sets the version of the Ion binary format written, Ion 1.0 by default.
+    /// The stream starts again, as the version marker resets the symbol
+    /// table.
+    ///
+    /// Ion 1.1 output is usually smaller. Symbols are only declared in the
+    /// symbol table when they are used enough times in the values encoded
+    /// together for their address to take less than their text, and written
+    /// inline otherwise. Containers whose length would take more bytes than
+    /// an end marker are delimited, and integers and timestamps take fewer
+    /// bytes. Macros are not used.
+    ///
+    /// ```rust
+    /// use ion_binary_rs::{IonEncoder, IonParser, IonValue, IonVersion};
+    ///
+    /// let value = IonValue::List(vec![IonValue::Symbol("sedan".into()), IonValue::Integer(2019)]);
+    ///
+    /// let mut encoder = IonEncoder::new();
+    /// encoder.with_target_version(IonVersion::Ion1_1);
+    ///
+    /// encoder.add(value.clone());
+    /// let bytes = encoder.encode();
+    ///
+    /// assert_eq!(bytes[..4], [0xE0, 0x01, 0x01, 0xEA]);
+    /// assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), vec![value]);
+    /// ```
+    pub fn with_target_version(&mut self, version: IonVersion) {
+        self.plan.version = version;
+        self.restart_stream();
+    }
+
+    /// Makes every `encode` continue the stream written by the previous
+    /// ones, so all the outputs can be concatenated into a single valid Ion
+    /// stream. Only the first one has the ion header, and the rest of them
+    /// start with a symbol table that appends the symbols not declared yet
+    /// to the previous one, or no table if all symbols were declared.
+    ///
+    /// ```rust
+    /// use ion_binary_rs::{IonEncoder, IonParser, IonValue};
+    ///
+    /// let mut encoder = IonEncoder::new();
+    /// encoder.with_appended_symbol_tables();
+    ///
+    /// encoder.add(IonValue::Symbol("sedan".into()));
+    /// let mut stream = encoder.encode();
+    ///
+    /// encoder.add(IonValue::Symbol("sedan".into()));
+    /// let chunk = encoder.encode();
+    ///
+    /// // Just the symbol, as it was already declared
+    /// assert_eq!(chunk, [0x71, 0x0A]);
+    ///
+    /// stream.extend_from_slice(&chunk);
+    ///
+    /// assert_eq!(IonParser::new(&stream[..]).consume_all().unwrap().len(), 2);
+    /// ```
+    ///
+    /// Canonical encoders ignore it, as their output can't depend on the
+    /// values encoded before.
+    pub fn with_appended_symbol_tables(&mut self) {
+        self.is_append_mode = true;
+    }
+
+    /// Starts a new stream. The symbols added by the encoded values are
+    /// removed from the symbol table, so the next output only declares the
+    /// symbols it uses. Shared tables and the imports added with
+    /// `with_shared_table` or `with_import` are kept, same as the values
+    /// added and not encoded yet.
+    pub fn reset(&mut self) {
+        // Imports always have max_len, so they can't fail.
+        let _ = self.symbol_table.set_new_table(&self.imports, &[]);
+
+        self.restart_stream();
+    }
+
+    // The next table written declares all the imports and symbols, after
+    // the ion header.
+    fn restart_stream(&mut self) {
+        self.declared_symbols = 0;
+        self.is_table_reset = true;
+        self.is_header_written = false;
+    }
+
+    /// Sets a catalog where the encoder looks for the shared tables it
+    /// imports. Symbols in those tables are encoded with their SIDs instead
+    /// of being added to the local symbol table.
+    pub fn with_catalog<C: Catalog + 'static>(&mut self, catalog: C) {
+        self.symbol_table.set_catalog(Arc::new(catalog));
+    }
+
+    /// Adds a shared table and imports it in the local symbol tables. Field
+    /// names, symbols and annotations in the table are encoded with their
+    /// SIDs, so only the symbols that are not there are declared in the
+    /// local symbol table. Parsers need the same table in order to read
+    /// the result, see `IonParser::with_shared_table`.
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        let symbols: Vec<Symbol> = symbols
+            .iter()
+            .map(|s| Symbol::Symbol(s.to_string()))
+            .collect();
+
+        self.symbol_table
+            .add_shared_table(name.clone(), version, &symbols)?;
+
+        self.with_import(name, version)
+    }
+
+    /// Same as `with_shared_table`, but for tables that have been already
+    /// added or that are in the catalog. If the catalog doesn't have the
+    /// version, the highest one available is imported.
+    pub fn with_import(&mut self, name: String, version: u32) -> Result<(), SymbolContextError> {
+        let mut imports = self.symbol_table.get_imports();
+
+        if imports.iter().any(|import| import.name == name) {
+            return Err(SymbolContextError::TableAlreadyImported);
+        }
+
+        let table = self
+            .symbol_table
+            .get_shared_table(&name, version)
+            .ok_or(SymbolContextError::SharedTableNotFound)?;
+
+        let import = Import {
+            name,
+            version: Some(table.version()),
+            max_len: Some(table.get_all_symbols().len()),
+        };
+
+        imports.push(import.clone());
+
+        self.symbol_table.set_imports(&imports)?;
+        self.imports.push(import);
+        self.is_table_reset = true;
+
+        Ok(())
+    }
+
     pub fn add(&mut self, value: IonValue) {
         self.current_buffer.push(value);
     }
 
     pub fn encode(&mut self) -> Vec<u8> {
-        let mut values = vec![];
+        let mut buffer = vec![];
 
-        values.append(&mut self.current_buffer);
+        self.encode_into(&mut buffer);
 
-        let mut values_buffer: Vec<u8> = values
-            .into_iter()
-            .flat_map(|value| self.encode_value(&value))
-            .collect();
+        buffer
+    }
 
-        let mut symbol_table = self.encode_current_symbol_table();
+    /// Same as `encode`, but appending the ion header, the symbol table
+    /// and the values to the buffer. Reusing the buffer between encodings
+    /// avoids allocating the output every time.
+    pub fn encode_into(&mut self, buffer: &mut Vec<u8>) {
+        let mut values = std::mem::take(&mut self.current_buffer);
 
-        let mut buffer = IonEncoder::get_ion_1_0_header();
+        if self.plan.is_canonical {
+            self.reset();
+        } else if !self.is_append_mode {
+            self.restart_stream();
+        }
 
-        buffer.append(&mut symbol_table);
-        buffer.append(&mut values_buffer);
+        for value in &values {
+            if let Some(imports) = self.get_imports_for(value) {
+                self.set_imports(&imports);
+            }
+        }
 
-        buffer
+        // Symbols are added to the table while planning, so the values
+        // need to be planned before the table.
+        self.plan.clear();
+        self.count_symbol_uses(&values);
+
+        let values_len: usize = values.iter().map(|value| self.plan_value(value)).sum();
+
+        let mut table_cursor = self.plan.end();
+        let symbol_table = self.get_stream_symbol_table();
+        let table_len = match &symbol_table {
+            Some(symbol_table) => self.plan_value(symbol_table),
+            None => 0,
+        };
+
+        buffer.reserve(ION_1_1_HEADER.len() + table_len + values_len);
+
+        self.encode_stream_header_into(buffer);
+
+        if let Some(symbol_table) = &symbol_table {
+            self.plan
+                .write_value(symbol_table, &mut table_cursor, buffer);
+        }
+
+        let mut cursor = PlanCursor::default();
+
+        for value in &values {
+            self.plan.write_value(value, &mut cursor, buffer);
+        }
+
+        // Keeps the allocated memory for the next values
+        values.clear();
+        self.current_buffer = values;
     }
 
-    fn get_ion_1_0_header() -> Vec<u8> {
-        vec![0xE0, 0x01, 0x00, 0xEA]
+    /// Prepends the ion header and the symbol table to already encoded
+    /// values. The values must have been encoded using this encoder's
+    /// symbol table.
+    #[cfg(feature = "serde")]
+    pub(crate) fn encode_with_values(&mut self, values_buffer: Vec<u8>) -> Vec<u8> {
+        let mut buffer = vec![];
+
+        self.restart_stream();
+        self.encode_stream_symbol_table_into(&mut buffer);
+
+        buffer.extend_from_slice(&values_buffer);
+
+        buffer
     }
 
+    #[cfg(test)]
     pub(crate) fn encode_value(&mut self, value: &IonValue) -> Vec<u8> {
+        let mut buffer = vec![];
+
+        self.encode_value_into(value, &mut buffer);
+
+        buffer
+    }
+
+    /// Appends the encoded value to the buffer, adding its symbols to the
+    /// symbol table. Neither the header nor the table are written.
+    pub(crate) fn encode_value_into(&mut self, value: &IonValue, buffer: &mut Vec<u8>) {
+        self.plan.clear();
+        self.count_symbol_uses(std::slice::from_ref(value));
+
+        let len = self.plan_value(value);
+
+        buffer.reserve(len);
+
+        self.plan
+            .write_value(value, &mut PlanCursor::default(), buffer);
+    }
+
+    /// First pass of the encoding. Returns the length of the encoded value
+    /// and records in the plan what the second pass needs to write it.
+    fn plan_value(&mut self, value: &IonValue) -> usize {
+        if self.plan.version == IonVersion::Ion1_1 {
+            return self.plan_value_1_1(value);
+        }
+
         match value {
-            IonValue::Null(value) => encode_null(value),
-            IonValue::Bool(value) => encode_bool(value),
-            IonValue::Integer(value) => encode_integer(&BigInt::from(*value)),
-            IonValue::BigInteger(value) => encode_integer(value),
-            IonValue::Float(value) => encode_float64(value),
-            IonValue::Decimal(value) => encode_decimal(value),
-            IonValue::String(value) => encode_blob(8, value.as_bytes()),
-            IonValue::Clob(value) => encode_blob(9, value),
-            IonValue::Blob(value) => encode_blob(10, value),
-            IonValue::DateTime(value) => encode_datetime(value),
-            IonValue::List(value) => self.encode_list(value, false),
-            IonValue::SExpr(value) => self.encode_list(value, true),
-            IonValue::Symbol(symbol) => self.encode_symbol(symbol),
-            IonValue::Struct(value) => self.encode_struct(value),
-            IonValue::Annotation(annotations, value) => self.encode_annotation(annotations, value),
+            IonValue::Null(_) | IonValue::Bool(_) => 1,
+            IonValue::Integer(value) => int64_len(*value),
+            IonValue::BigInteger(value) => self.plan.add_scalar(&encode_integer(value)),
+            IonValue::Float(value) => self.plan.float_len(value),
+            IonValue::Decimal(value) => self.plan.add_scalar(&encode_decimal(value)),
+            IonValue::String(value) => header_len(value.len()) + value.len(),
+            IonValue::Clob(value) | IonValue::Blob(value) => header_len(value.len()) + value.len(),
+            IonValue::DateTime(value) => self.plan.add_scalar(&encode_datetime(value)),
+            IonValue::Symbol(symbol) => {
+                let id = self.get_symbol_token_id(symbol);
+
+                self.plan.symbol_ids.push(id);
+
+                symbol_id_len(id)
+            }
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                let index = self.plan.add_length();
+
+                let len = values.iter().map(|value| self.plan_value(value)).sum();
+
+                self.plan.lengths[index] = len;
+
+                header_len(len) + len
+            }
+            IonValue::Struct(values) if self.plan.is_canonical => {
+                self.plan_struct(sorted_fields(values))
+            }
+            IonValue::Struct(values) => self.plan_struct(values),
+            IonValue::OrderedStruct(values) if self.plan.is_canonical => self.plan_struct(
+                sorted_fields(values.iter().map(|(name, value)| (name, value))),
+            ),
+            IonValue::OrderedStruct(values) => {
+                self.plan_struct(values.iter().map(|(name, value)| (name, value)))
+            }
+            IonValue::Annotation(annotations, value) => {
+                let index = self.plan.add_length();
+                let annotations_index = self.plan.add_length();
+
+                let mut annotations_len = 0;
+
+                for annotation in annotations {
+                    let id = self.get_symbol_token_id(annotation);
+
+                    self.plan.symbol_ids.push(id);
+
+                    annotations_len += varuint_len(id);
+                }
+
+                let len = varuint_len(annotations_len) + annotations_len + self.plan_value(value);
+
+                self.plan.lengths[index] = len;
+                self.plan.lengths[annotations_index] = annotations_len;
+
+                header_len(len) + len
+            }
         }
     }
 
-    pub(crate) fn encode_symbol(&mut self, symbol: &str) -> Vec<u8> {
-        let mut buffer: Vec<u8> = vec![];
+    fn plan_struct<'a, K, I>(&mut self, fields: I) -> usize
+    where
+        K: FieldName + 'a,
+        I: IntoIterator<Item = (&'a K, &'a IonValue)>,
+    {
+        let index = self.plan.add_length();
 
-        let mut header: u8 = 0x70;
+        let mut len = 0;
 
-        let id = self.symbol_table.insert_symbol(symbol);
+        for (name, value) in fields {
+            let id = self.get_symbol_token_id(name);
 
-        let mut id_bytes = encode_uint(&BigUint::from(id));
-        let id_bytes_len = id_bytes.len();
-        let has_len_field = id_bytes_len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into();
+            self.plan.symbol_ids.push(id);
 
-        if has_len_field {
-            header += ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED;
-            buffer.push(header);
-            let mut id_bytes_len_bytes = encode_varuint(&id_bytes_len.to_be_bytes());
-            buffer.append(&mut id_bytes_len_bytes);
-        } else {
-            header += u8::try_from(id_bytes_len).unwrap();
-            buffer.push(header);
-        };
+            len += varuint_len(id) + self.plan_value(value);
+        }
 
-        buffer.append(&mut id_bytes);
+        self.plan.lengths[index] = len;
 
-        buffer
+        header_len(len) + len
     }
 
-    pub(crate) fn encode_list(&mut self, values: &[IonValue], is_sexp: bool) -> Vec<u8> {
-        let mut buffer: Vec<u8> = vec![];
+    pub(crate) fn insert_symbol(&mut self, symbol: &str) -> usize {
+        self.symbol_table.insert_symbol(symbol)
+    }
 
-        for value in values {
-            let mut bytes = self.encode_value(value);
+    /// SID of the token in the current symbol table. Symbols without text
+    /// are `$0` unless their import location is imported by the table.
+    pub(crate) fn get_symbol_token_id<S: FieldName>(&mut self, symbol: &S) -> usize {
+        match (symbol.text(), symbol.import_location()) {
+            (Some(text), _) => self.insert_symbol(text),
+            (None, Some(location)) => self
+                .symbol_table
+                .get_id_by_import_location(location)
+                .unwrap_or(0),
+            (None, None) => 0,
+        }
+    }
 
-            buffer.append(&mut bytes);
+    /// Returns the imports the symbol table needs in order to encode the
+    /// symbols without text of the value, or None if it already has them.
+    pub(crate) fn get_imports_for(&self, value: &IonValue) -> Option<Vec<Import>> {
+        let mut imports = self.symbol_table.get_imports();
+        let imports_len = imports.len();
+        let mut is_changed = false;
+
+        for_each_import_location(value, &mut |location| {
+            let import = imports
+                .iter_mut()
+                .find(|import| import.name == location.import_name());
+
+            // Locations read from a stream declare the import they come
+            // from again, so the symbols keep their SIDs.
+            let max_len = location.import_max_id().unwrap_or(0).max(location.sid());
+
+            match import {
+                Some(import) if import.max_len.is_some_and(|len| len >= location.sid()) => {}
+                Some(import) => {
+                    import.max_len = Some(max_len);
+                    is_changed = true;
+                }
+                None => {
+                    imports.push(Import {
+                        name: location.import_name().to_string(),
+                        version: Some(location.import_version().unwrap_or(1)),
+                        max_len: Some(max_len),
+                    });
+                    is_changed = true;
+                }
+            }
+        });
+
+        // Struct fields are visited in no particular order, so new imports
+        // are sorted in order to get the same SIDs for the same values.
+        imports[imports_len..].sort_by(|a, b| a.name.cmp(&b.name));
+
+        if is_changed {
+            Some(imports)
+        } else {
+            None
         }
+    }
 
-        let buffer_len = buffer.len();
-        let has_len_field = buffer_len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into();
+    /// Changes the imports of the symbol table. Local symbols get new SIDs,
+    /// so values encoded before can't be written after the new table.
+    pub(crate) fn set_imports(&mut self, imports: &[Import]) {
+        // Imports always have max_len, so they can't fail.
+        let _ = self.symbol_table.set_imports(imports);
 
-        let mut header: u8 = if is_sexp { 0xC0 } else { 0xB0 };
+        self.declared_symbols = 0;
+        self.is_table_reset = true;
+    }
 
-        if has_len_field {
-            header += ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED;
-        } else {
-            header += u8::try_from(buffer_len).unwrap();
-        }
+    #[cfg(feature = "serde")]
+    pub(crate) fn encode_symbol(&mut self, symbol: &str) -> Vec<u8> {
+        let id = self.insert_symbol(symbol);
 
-        let mut buffer = if has_len_field {
-            let mut buffer_len_bytes = encode_varuint(&buffer_len.to_be_bytes());
-            buffer_len_bytes.append(&mut buffer);
-            buffer_len_bytes
-        } else {
-            buffer
-        };
+        let mut buffer = vec![];
 
-        buffer.insert(0, header);
+        encode_symbol_id_buffer(&mut buffer, id);
 
         buffer
     }
 
-    pub(crate) fn encode_annotation(
-        &mut self,
-        annotations: &[String],
-        value: &IonValue,
-    ) -> Vec<u8> {
+    #[cfg(feature = "serde")]
+    pub(crate) fn encode_annotation_symbols(&mut self, annotations: &[SymbolToken]) -> Vec<u8> {
         let mut annot_buffer: Vec<u8> = vec![];
 
         for annot in annotations {
-            let annot_symbol = self.symbol_table.insert_symbol(annot);
+            let annot_symbol = self.get_symbol_token_id(annot);
             let mut annot_symbol_bytes = encode_varuint(&annot_symbol.to_be_bytes());
             annot_buffer.append(&mut annot_symbol_bytes);
         }
 
-        let mut annot_len_bytes = encode_varuint(&annot_buffer.len().to_be_bytes());
+        annot_buffer
+    }
 
-        let mut value_bytes = self.encode_value(value);
+    #[cfg(feature = "serde")]
+    pub(crate) fn encode_annotation_wrapper(
+        mut annot_buffer: Vec<u8>,
+        mut value_bytes: Vec<u8>,
+    ) -> Vec<u8> {
+        let mut annot_len_bytes = encode_varuint(&annot_buffer.len().to_be_bytes());
 
         annot_len_bytes.append(&mut annot_buffer);
 
@@ -218,42 +611,61 @@ impl IonEncoder {
         final_buffer
     }
 
-    pub(crate) fn encode_struct(&mut self, value: &HashMap<String, IonValue>) -> Vec<u8> {
-        let mut content_buffer: Vec<u8> = vec![];
+    /// Writes the ion header if the stream hasn't started yet.
+    pub(crate) fn encode_stream_header_into(&mut self, buffer: &mut Vec<u8>) {
+        if !self.is_header_written {
+            match self.plan.version {
+                IonVersion::Ion1_0 => buffer.extend_from_slice(&ION_1_0_HEADER),
+                IonVersion::Ion1_1 => buffer.extend_from_slice(&ION_1_1_HEADER),
+            }
 
-        for (key, value) in value {
-            let symbol = self.symbol_table.insert_symbol(key);
-            let mut symbol_bytes = encode_varuint(&symbol.to_be_bytes());
-            let mut value_bytes = self.encode_value(value);
-            content_buffer.append(&mut symbol_bytes);
-            content_buffer.append(&mut value_bytes);
+            self.is_header_written = true;
         }
+    }
 
-        let content_len = content_buffer.len();
-        let has_len_field = content_len >= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into();
+    /// Writes what the stream needs before the values encoded since the
+    /// last call: the ion header when it starts, and a table with the
+    /// symbols not declared yet.
+    pub(crate) fn encode_stream_symbol_table_into(&mut self, buffer: &mut Vec<u8>) {
+        self.encode_stream_header_into(buffer);
 
-        let mut header = 0xD0;
+        if let Some(symbol_table) = self.get_stream_symbol_table() {
+            self.encode_value_into(&symbol_table, buffer);
+        }
+    }
 
-        let mut buffer: Vec<u8> = vec![];
+    /// Local symbol table declaring the symbols not declared yet in the
+    /// stream, or None if there are none. It appends them to the previous
+    /// table unless the stream restarted or the imports changed, as then
+    /// all of them need to be declared again.
+    fn get_stream_symbol_table(&mut self) -> Option<IonValue> {
+        let symbols = self
+            .symbol_table
+            .dump_local_symbols_from(self.declared_symbols);
+
+        if symbols.is_empty() && !self.is_table_reset {
+            return None;
+        }
 
-        if has_len_field {
-            header += ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED;
-            buffer.push(header);
-            let mut content_len_bytes = encode_varuint(&content_len.to_be_bytes());
-            buffer.append(&mut content_len_bytes);
-        } else {
-            header += u8::try_from(content_len).unwrap();
-            buffer.push(header);
+        // The Ion 1.1 writer only declares the symbols worth it, so it
+        // doesn't write tables without symbols nor imports.
+        if symbols.is_empty()
+            && self.declared_symbols == 0
+            && self.plan.version == IonVersion::Ion1_1
+            && self.symbol_table.get_imports().is_empty()
+        {
+            return None;
         }
 
-        buffer.append(&mut content_buffer);
+        let is_append = self.declared_symbols > 0;
 
-        buffer
-    }
+        self.declared_symbols += symbols.len();
+        self.is_table_reset = false;
 
-    pub(crate) fn encode_current_symbol_table(&mut self) -> Vec<u8> {
-        let symbols = self.symbol_table.dump_all_local_symbols();
+        Some(self.get_local_symbol_table(symbols, is_append))
+    }
 
+    fn get_local_symbol_table(&self, symbols: Vec<String>, append: bool) -> IonValue {
         let symbols = IonValue::List(symbols.into_iter().map(IonValue::String).collect());
 
         let mut annotation_struct = HashMap::new();
@@ -264,13 +676,319 @@ impl IonEncoder {
 
         annotation_struct.insert(symbols_symbol, symbols);
 
+        let imports_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Imports as usize].to_string();
+
+        if append {
+            annotation_struct.insert(
+                imports_symbol,
+                IonValue::Symbol(local_table_annotation_symbol.clone().into()),
+            );
+        } else {
+            let imports = self.symbol_table.get_imports();
+
+            if !imports.is_empty() {
+                let imports = imports.into_iter().map(encode_import).collect();
+
+                annotation_struct.insert(imports_symbol, IonValue::List(imports));
+            }
+        }
+
         let annotation_struct = IonValue::Struct(annotation_struct);
 
-        let annotation = IonValue::Annotation(
-            vec![local_table_annotation_symbol],
+        IonValue::Annotation(
+            vec![local_table_annotation_symbol.into()],
             Box::new(annotation_struct),
-        );
+        )
+    }
+}
+
+/// What the first pass of the encoding finds out about the values, in the
+/// same order the second pass needs it. The vectors are cleared and not
+/// dropped between values, so once they have grown encoding doesn't
+/// allocate.
+#[derive(Debug, Default)]
+pub(crate) struct EncodingPlan {
+    // Content length of every container and annotation wrapper. Annotation
+    // wrappers also have the length of their annotations list, and
+    // encoded scalars their encoded length.
+    pub(crate) lengths: Vec<usize>,
+    // SIDs of symbols, field names and annotations.
+    symbol_ids: Vec<usize>,
+    // Same as `symbol_ids` for Ion 1.1, where None is written inline.
+    pub(crate) symbol_addresses: Vec<Option<usize>>,
+    // Big integers, decimals and timestamps, already encoded as their
+    // length isn't known until they are.
+    scalars: Vec<u8>,
+    // Options of the encoder, they are kept when the plan is cleared.
+    pub(crate) is_canonical: bool,
+    is_compact_floats: bool,
+    pub(crate) version: IonVersion,
+}
+
+/// Position of the second pass in each of the vectors of the plan.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct PlanCursor {
+    length: usize,
+    symbol_id: usize,
+    pub(crate) symbol_address: usize,
+    scalar: usize,
+}
+
+impl EncodingPlan {
+    fn clear(&mut self) {
+        self.lengths.clear();
+        self.symbol_ids.clear();
+        self.symbol_addresses.clear();
+        self.scalars.clear();
+    }
+
+    fn end(&self) -> PlanCursor {
+        PlanCursor {
+            length: self.lengths.len(),
+            symbol_id: self.symbol_ids.len(),
+            symbol_address: self.symbol_addresses.len(),
+            scalar: self.scalars.len(),
+        }
+    }
+
+    /// Reserves the place of a length that is set once the content has
+    /// been planned.
+    pub(crate) fn add_length(&mut self) -> usize {
+        self.lengths.push(0);
+        self.lengths.len() - 1
+    }
+
+    pub(crate) fn add_scalar(&mut self, bytes: &[u8]) -> usize {
+        self.lengths.push(bytes.len());
+        self.scalars.extend_from_slice(bytes);
+        bytes.len()
+    }
+
+    pub(crate) fn next_length(&self, cursor: &mut PlanCursor) -> usize {
+        let len = self.lengths[cursor.length];
+        cursor.length += 1;
+        len
+    }
+
+    fn next_symbol_id(&self, cursor: &mut PlanCursor) -> usize {
+        let id = self.symbol_ids[cursor.symbol_id];
+        cursor.symbol_id += 1;
+        id
+    }
+
+    /// Second pass of the encoding. The value must be the planned one, as
+    /// struct fields are expected in the same order.
+    fn write_value(&self, value: &IonValue, cursor: &mut PlanCursor, buffer: &mut Vec<u8>) {
+        if self.version == IonVersion::Ion1_1 {
+            return self.write_value_1_1(value, cursor, buffer);
+        }
+
+        match value {
+            IonValue::Null(value) => {
+                encode_null_buffer(buffer, value);
+            }
+            IonValue::Bool(value) => {
+                encode_bool_buffer(buffer, value);
+            }
+            IonValue::Integer(value) => encode_int64_buffer(buffer, *value),
+            IonValue::Float(value) => self.write_float(value, buffer),
+            IonValue::String(value) => {
+                encode_blob_buffer(buffer, 8, value.as_bytes());
+            }
+            IonValue::Clob(value) => {
+                encode_blob_buffer(buffer, 9, value);
+            }
+            IonValue::Blob(value) => {
+                encode_blob_buffer(buffer, 10, value);
+            }
+            IonValue::BigInteger(_) | IonValue::Decimal(_) | IonValue::DateTime(_) => {
+                self.write_scalar(cursor, buffer)
+            }
+            IonValue::Symbol(_) => {
+                let id = self.next_symbol_id(cursor);
+
+                encode_symbol_id_buffer(buffer, id);
+            }
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                let ion_type = if let IonValue::SExpr(_) = value {
+                    0xC
+                } else {
+                    0xB
+                };
+
+                encode_header_buffer(buffer, ion_type, self.next_length(cursor));
+
+                for value in values {
+                    self.write_value(value, cursor, buffer);
+                }
+            }
+            IonValue::Struct(values) if self.is_canonical => {
+                self.write_struct(sorted_fields(values), cursor, buffer)
+            }
+            IonValue::Struct(values) => self.write_struct(values, cursor, buffer),
+            IonValue::OrderedStruct(values) if self.is_canonical => self.write_struct(
+                sorted_fields(values.iter().map(|(name, value)| (name, value))),
+                cursor,
+                buffer,
+            ),
+            IonValue::OrderedStruct(values) => self.write_struct(
+                values.iter().map(|(name, value)| (name, value)),
+                cursor,
+                buffer,
+            ),
+            IonValue::Annotation(annotations, value) => {
+                encode_header_buffer(buffer, 0xE, self.next_length(cursor));
+                encode_varuint_buffer(buffer, self.next_length(cursor));
+
+                for _ in annotations {
+                    encode_varuint_buffer(buffer, self.next_symbol_id(cursor));
+                }
+
+                self.write_value(value, cursor, buffer);
+            }
+        }
+    }
+
+    /// Copies the next already encoded scalar.
+    pub(crate) fn write_scalar(&self, cursor: &mut PlanCursor, buffer: &mut Vec<u8>) {
+        let len = self.next_length(cursor);
+
+        buffer.extend_from_slice(&self.scalars[cursor.scalar..cursor.scalar + len]);
+
+        cursor.scalar += len;
+    }
+
+    pub(crate) fn float_len(&self, value: &f64) -> usize {
+        1 + self.get_float_width(*value)
+    }
+
+    fn write_float(&self, value: &f64, buffer: &mut Vec<u8>) {
+        match self.get_float_width(*value) {
+            0 => buffer.push(0x40),
+            4 => {
+                buffer.push(0x44);
+                buffer.extend_from_slice(&(*value as f32).to_be_bytes());
+            }
+            _ => {
+                buffer.push(0x48);
+                buffer.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+
+    // Bytes after the type descriptor, none for 0e0
+    pub(crate) fn get_float_width(&self, value: f64) -> usize {
+        if value == 0.0 && value.is_sign_positive() {
+            0
+        } else if self.is_compact_floats && is_exact_float32(value) {
+            4
+        } else {
+            8
+        }
+    }
+
+    fn write_struct<'a, K, I>(&self, fields: I, cursor: &mut PlanCursor, buffer: &mut Vec<u8>)
+    where
+        K: 'a,
+        I: IntoIterator<Item = (&'a K, &'a IonValue)>,
+    {
+        encode_header_buffer(buffer, 0xD, self.next_length(cursor));
+
+        for (_, value) in fields {
+            encode_varuint_buffer(buffer, self.next_symbol_id(cursor));
+
+            self.write_value(value, cursor, buffer);
+        }
+    }
+}
+
+pub(crate) fn sorted_fields<'a, K, I>(fields: I) -> Vec<(&'a K, &'a IonValue)>
+where
+    K: FieldName + 'a,
+    I: IntoIterator<Item = (&'a K, &'a IonValue)>,
+{
+    let mut fields: Vec<(&K, &IonValue)> = fields.into_iter().collect();
+
+    // Stable, so repeated names keep their order
+    fields.sort_by_key(|(name, _)| *name);
 
-        self.encode_value(&annotation)
+    fields
+}
+
+// NaN is never exact, so its payload is kept.
+fn is_exact_float32(value: f64) -> bool {
+    (value as f32) as f64 == value
+}
+
+/// Symbols are encoded as the unsigned integer of their SID. `$0` takes
+/// a zero byte.
+fn symbol_id_len(id: usize) -> usize {
+    let zero_bytes = id.leading_zeros() as usize / 8;
+
+    1 + (std::mem::size_of::<usize>() - zero_bytes).max(1)
+}
+
+fn encode_symbol_id_buffer(buffer: &mut Vec<u8>, id: usize) {
+    let bytes = id.to_be_bytes();
+    let zero_bytes = (id.leading_zeros() as usize / 8).min(bytes.len() - 1);
+
+    encode_header_buffer(buffer, 0x7, bytes.len() - zero_bytes);
+    buffer.extend_from_slice(&bytes[zero_bytes..]);
+}
+
+fn encode_import(import: Import) -> IonValue {
+    let mut import_struct = HashMap::new();
+
+    let name_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Name as usize].to_string();
+    let version_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Version as usize].to_string();
+    let max_id_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::MaxId as usize].to_string();
+
+    import_struct.insert(name_symbol, IonValue::String(import.name));
+    import_struct.insert(
+        version_symbol,
+        IonValue::Integer(import.version.unwrap_or(1).into()),
+    );
+
+    if let Some(max_len) = import.max_len {
+        import_struct.insert(max_id_symbol, IonValue::Integer(max_len as i64));
+    }
+
+    IonValue::Struct(import_struct)
+}
+
+fn for_each_import_location<F: FnMut(&ImportLocation)>(value: &IonValue, callback: &mut F) {
+    match value {
+        IonValue::Symbol(symbol) => {
+            if let (None, Some(location)) = (symbol.text(), symbol.import_location()) {
+                callback(location);
+            }
+        }
+        IonValue::List(values) | IonValue::SExpr(values) => {
+            for value in values {
+                for_each_import_location(value, callback);
+            }
+        }
+        IonValue::Struct(values) => {
+            for value in values.values() {
+                for_each_import_location(value, callback);
+            }
+        }
+        IonValue::OrderedStruct(values) => {
+            for (name, value) in values {
+                if let (None, Some(location)) = (name.text(), name.import_location()) {
+                    callback(location);
+                }
+
+                for_each_import_location(value, callback);
+            }
+        }
+        IonValue::Annotation(annotations, value) => {
+            for annotation in annotations {
+                for_each_import_location(&IonValue::Symbol(annotation.clone()), callback);
+            }
+
+            for_each_import_location(value, callback);
+        }
+        _ => {}
     }
 }
@@ -0,0 +1,500 @@
+use crate::binary_encoder::{
+    encode_datetime_1_1, encode_decimal_1_1, encode_flex_int_buffer, encode_flex_uint_buffer,
+    encode_int64_buffer_1_1, encode_integer_1_1, encode_null_buffer_1_1, encode_opcode_buffer_1_1,
+    fixed_int64_len, flex_int_len, flex_uint_len, opcode_len_1_1,
+};
+use crate::binary_parser_types::SYSTEM_SYMBOL_TABLE;
+use crate::ion_encoder::{sorted_fields, EncodingPlan, IonEncoder, IonVersion, PlanCursor};
+use crate::symbol_token::FieldName;
+use crate::{IonValue, NullIonValue, SymbolToken};
+use std::collections::HashMap;
+
+const DELIMITED_END: u8 = 0xF0;
+
+// Ion 1.1 binary encoding, with the same two passes as the Ion 1.0 one.
+// Symbols are declared in `$ion_symbol_table` structs as in Ion 1.0, so
+// the Ion 1.1 reader resolves their addresses in the same symbol tables,
+// but only the ones worth it are added there. Macros are not used.
+impl IonEncoder {
+    /// Counts how many times each symbol text is used by the values, so
+    /// the first pass can decide which ones to add to the symbol table.
+    pub(crate) fn count_symbol_uses(&mut self, values: &[IonValue]) {
+        self.symbol_uses.clear();
+
+        if self.plan.version == IonVersion::Ion1_1 {
+            for value in values {
+                count_symbol_uses(value, &mut self.symbol_uses);
+            }
+        }
+    }
+
+    /// First pass of the Ion 1.1 encoding, see `plan_value`.
+    pub(crate) fn plan_value_1_1(&mut self, value: &IonValue) -> usize {
+        match value {
+            IonValue::Null(NullIonValue::Null | NullIonValue::Annotation) => 1,
+            IonValue::Null(_) => 2,
+            IonValue::Bool(_) => 1,
+            IonValue::Integer(value) => 1 + fixed_int64_len(*value),
+            IonValue::BigInteger(value) => self.plan.add_scalar(&encode_integer_1_1(value)),
+            IonValue::Float(value) => self.plan.float_len(value),
+            IonValue::Decimal(value) => self.plan.add_scalar(&encode_decimal_1_1(value)),
+            IonValue::String(value) => text_len(value.len()),
+            IonValue::Clob(value) | IonValue::Blob(value) => {
+                1 + flex_uint_len(value.len()) + value.len()
+            }
+            IonValue::DateTime(value) => self.plan.add_scalar(&encode_datetime_1_1(value)),
+            IonValue::Symbol(symbol) => {
+                let address = self.get_symbol_address_1_1(symbol);
+
+                self.plan.symbol_addresses.push(address);
+
+                symbol_len(symbol, address)
+            }
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                let index = self.plan.add_length();
+
+                let len = values.iter().map(|value| self.plan_value_1_1(value)).sum();
+
+                self.plan.lengths[index] = len;
+
+                sequence_header_len(len) + len
+            }
+            IonValue::Struct(values) if self.plan.is_canonical => {
+                self.plan_struct_1_1(sorted_fields(values))
+            }
+            IonValue::Struct(values) => self.plan_struct_1_1(values),
+            IonValue::OrderedStruct(values) if self.plan.is_canonical => self.plan_struct_1_1(
+                sorted_fields(values.iter().map(|(name, value)| (name, value))),
+            ),
+            IonValue::OrderedStruct(values) => {
+                self.plan_struct_1_1(values.iter().map(|(name, value)| (name, value)))
+            }
+            IonValue::Annotation(annotations, value) => {
+                let index = self.plan.add_length();
+                let start = self.plan.symbol_addresses.len();
+
+                for annotation in annotations {
+                    let address = self.get_symbol_address_1_1(annotation);
+
+                    self.plan.symbol_addresses.push(address);
+                }
+
+                let addresses = &self.plan.symbol_addresses[start..];
+                let with_flex_syms = addresses.iter().any(Option::is_none);
+
+                let annotations_len = annotations
+                    .iter()
+                    .zip(addresses)
+                    .map(|(annotation, address)| {
+                        symbol_ref_len(annotation.text(), *address, with_flex_syms)
+                    })
+                    .sum();
+
+                self.plan.lengths[index] = annotations_len;
+
+                annotations_header_len(annotations.len(), annotations_len)
+                    + annotations_len
+                    + self.plan_value_1_1(value)
+            }
+        }
+    }
+
+    fn plan_struct_1_1<'a, K, I>(&mut self, fields: I) -> usize
+    where
+        K: FieldName + 'a,
+        I: IntoIterator<Item = (&'a K, &'a IonValue)>,
+        I::IntoIter: Clone,
+    {
+        let fields = fields.into_iter();
+
+        let index = self.plan.add_length();
+        let start = self.plan.symbol_addresses.len();
+
+        // Field names are planned before the values, as whether any of
+        // them is written inline changes how all of them are written.
+        for (name, _) in fields.clone() {
+            let address = self.get_symbol_address_1_1(name);
+
+            self.plan.symbol_addresses.push(address);
+        }
+
+        let addresses = &self.plan.symbol_addresses[start..];
+        let with_flex_syms = addresses.iter().any(Option::is_none);
+
+        let names_len: usize = fields
+            .clone()
+            .zip(addresses)
+            .map(|((name, _), address)| symbol_ref_len(name.text(), *address, with_flex_syms))
+            .sum();
+
+        let values_len: usize = fields.map(|(_, value)| self.plan_value_1_1(value)).sum();
+
+        let len = names_len + values_len;
+
+        self.plan.lengths[index] = len;
+
+        struct_header_len(len, with_flex_syms) + len
+    }
+
+    /// Address of the token in the current symbol table, or None if it is
+    /// written inline.
+    fn get_symbol_address_1_1<S: FieldName>(&mut self, symbol: &S) -> Option<usize> {
+        match (symbol.text(), symbol.import_location()) {
+            (Some(text), _) => self.get_text_address_1_1(text),
+            (None, Some(location)) => self.symbol_table.get_id_by_import_location(location),
+            (None, None) => None,
+        }
+    }
+
+    /// Symbols already in the symbol table are written with their address.
+    /// New ones are only added to it when they are used enough times for
+    /// their addresses and their declaration in the table to take less than
+    /// their text every time.
+    fn get_text_address_1_1(&mut self, text: &str) -> Option<usize> {
+        // `$0` can't be written as an address everywhere
+        if let Some(id) = self
+            .symbol_table
+            .get_id_by_symbol(text)
+            .filter(|id| *id > 0)
+        {
+            return Some(id);
+        }
+
+        let uses = self.symbol_uses.get(text).copied().unwrap_or(1);
+
+        let next_address = SYSTEM_SYMBOL_TABLE.len() + self.symbol_table.get_symbols_len();
+
+        // The declaration is the text as a string in the symbols list
+        let inline_len = text_len(text.len());
+        let table_len = uses * symbol_address_len(next_address) + inline_len;
+
+        if uses * inline_len > table_len {
+            Some(self.insert_symbol(text))
+        } else {
+            None
+        }
+    }
+}
+
+impl EncodingPlan {
+    /// Second pass of the Ion 1.1 encoding, see `write_value`.
+    pub(crate) fn write_value_1_1(
+        &self,
+        value: &IonValue,
+        cursor: &mut PlanCursor,
+        buffer: &mut Vec<u8>,
+    ) {
+        match value {
+            IonValue::Null(value) => encode_null_buffer_1_1(buffer, value),
+            IonValue::Bool(true) => buffer.push(0x6E),
+            IonValue::Bool(false) => buffer.push(0x6F),
+            IonValue::Integer(value) => encode_int64_buffer_1_1(buffer, *value),
+            IonValue::Float(value) => self.write_float_1_1(value, buffer),
+            IonValue::String(value) => write_text(buffer, 0x90, 0xF9, value),
+            IonValue::Clob(value) => write_bytes(buffer, 0xFF, value),
+            IonValue::Blob(value) => write_bytes(buffer, 0xFE, value),
+            IonValue::BigInteger(_) | IonValue::Decimal(_) | IonValue::DateTime(_) => {
+                self.write_scalar(cursor, buffer)
+            }
+            IonValue::Symbol(symbol) => {
+                let address = self.next_symbol_addresses(cursor, 1)[0];
+
+                write_symbol(buffer, symbol, address);
+            }
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                let (opcode, long_opcode, delimited_opcode) = if let IonValue::SExpr(_) = value {
+                    (0xC0, 0xFC, 0xF2)
+                } else {
+                    (0xB0, 0xFB, 0xF1)
+                };
+
+                let len = self.next_length(cursor);
+                let is_delimited = is_delimited_sequence(len);
+
+                if is_delimited {
+                    buffer.push(delimited_opcode);
+                } else {
+                    encode_opcode_buffer_1_1(buffer, opcode, long_opcode, 15, len);
+                }
+
+                for value in values {
+                    self.write_value_1_1(value, cursor, buffer);
+                }
+
+                if is_delimited {
+                    buffer.push(DELIMITED_END);
+                }
+            }
+            IonValue::Struct(values) if self.is_canonical => {
+                self.write_struct_1_1(sorted_fields(values), cursor, buffer)
+            }
+            IonValue::Struct(values) => self.write_struct_1_1(values, cursor, buffer),
+            IonValue::OrderedStruct(values) if self.is_canonical => self.write_struct_1_1(
+                sorted_fields(values.iter().map(|(name, value)| (name, value))),
+                cursor,
+                buffer,
+            ),
+            IonValue::OrderedStruct(values) => self.write_struct_1_1(
+                values.iter().map(|(name, value)| (name, value)),
+                cursor,
+                buffer,
+            ),
+            IonValue::Annotation(annotations, value) => {
+                let len = self.next_length(cursor);
+                let addresses = self.next_symbol_addresses(cursor, annotations.len());
+                let with_flex_syms = addresses.iter().any(Option::is_none);
+
+                let opcode = if with_flex_syms { 0xE7 } else { 0xE4 };
+
+                match annotations.len() {
+                    // There are no annotations to write, only the value
+                    0 => {}
+                    1 => buffer.push(opcode),
+                    2 => buffer.push(opcode + 1),
+                    _ => {
+                        buffer.push(opcode + 2);
+                        encode_flex_uint_buffer(buffer, len);
+                    }
+                }
+
+                for (annotation, address) in annotations.iter().zip(addresses) {
+                    write_symbol_ref(buffer, annotation.text(), *address, with_flex_syms);
+                }
+
+                self.write_value_1_1(value, cursor, buffer);
+            }
+        }
+    }
+
+    fn write_float_1_1(&self, value: &f64, buffer: &mut Vec<u8>) {
+        match self.get_float_width(*value) {
+            0 => buffer.push(0x6A),
+            4 => {
+                buffer.push(0x6C);
+                buffer.extend_from_slice(&(*value as f32).to_le_bytes());
+            }
+            _ => {
+                buffer.push(0x6D);
+                buffer.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+
+    fn write_struct_1_1<'a, K, I>(&self, fields: I, cursor: &mut PlanCursor, buffer: &mut Vec<u8>)
+    where
+        K: FieldName + 'a,
+        I: IntoIterator<Item = (&'a K, &'a IonValue)>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let fields = fields.into_iter();
+
+        let len = self.next_length(cursor);
+        let names = self.next_symbol_addresses(cursor, fields.len());
+        let with_flex_syms = names.iter().any(Option::is_none);
+        let is_delimited = is_delimited_struct(len, with_flex_syms);
+
+        if is_delimited {
+            buffer.push(0xF3);
+        } else {
+            // 0xD1 is not a valid opcode, but fields take at least two bytes
+            encode_opcode_buffer_1_1(buffer, 0xD0, 0xFD, 15, len + usize::from(with_flex_syms));
+
+            // A zero address switches the field names to FlexSyms
+            if with_flex_syms {
+                encode_flex_uint_buffer(buffer, 0);
+            }
+        }
+
+        for ((name, value), address) in fields.zip(names) {
+            write_symbol_ref(buffer, name.text(), *address, with_flex_syms);
+
+            self.write_value_1_1(value, cursor, buffer);
+        }
+
+        // The FlexSym escape followed by the end opcode
+        if is_delimited {
+            buffer.extend_from_slice(&[0x01, DELIMITED_END]);
+        }
+    }
+
+    fn next_symbol_addresses(&self, cursor: &mut PlanCursor, len: usize) -> &[Option<usize>] {
+        let addresses = &self.symbol_addresses[cursor.symbol_address..cursor.symbol_address + len];
+        cursor.symbol_address += len;
+        addresses
+    }
+}
+
+fn count_symbol_uses(value: &IonValue, uses: &mut HashMap<String, usize>) {
+    match value {
+        IonValue::Symbol(symbol) => {
+            if let Some(text) = symbol.text() {
+                count_symbol_use(text, uses);
+            }
+        }
+        IonValue::List(values) | IonValue::SExpr(values) => {
+            for value in values {
+                count_symbol_uses(value, uses);
+            }
+        }
+        IonValue::Struct(values) => {
+            for (name, value) in values {
+                count_symbol_use(name, uses);
+                count_symbol_uses(value, uses);
+            }
+        }
+        IonValue::OrderedStruct(values) => {
+            for (name, value) in values {
+                if let Some(text) = name.text() {
+                    count_symbol_use(text, uses);
+                }
+
+                count_symbol_uses(value, uses);
+            }
+        }
+        IonValue::Annotation(annotations, value) => {
+            for text in annotations.iter().filter_map(SymbolToken::text) {
+                count_symbol_use(text, uses);
+            }
+
+            count_symbol_uses(value, uses);
+        }
+        _ => {}
+    }
+}
+
+fn count_symbol_use(text: &str, uses: &mut HashMap<String, usize>) {
+    match uses.get_mut(text) {
+        Some(count) => *count += 1,
+        None => {
+            uses.insert(text.to_string(), 1);
+        }
+    }
+}
+
+/// Strings and inline symbols take the opcode and the text.
+fn text_len(len: usize) -> usize {
+    opcode_len_1_1(len, 15) + len
+}
+
+fn write_text(buffer: &mut Vec<u8>, opcode: u8, long_opcode: u8, text: &str) {
+    encode_opcode_buffer_1_1(buffer, opcode, long_opcode, 15, text.len());
+    buffer.extend_from_slice(text.as_bytes());
+}
+
+fn write_bytes(buffer: &mut Vec<u8>, opcode: u8, bytes: &[u8]) {
+    buffer.push(opcode);
+    encode_flex_uint_buffer(buffer, bytes.len());
+    buffer.extend_from_slice(bytes);
+}
+
+fn symbol_len(symbol: &SymbolToken, address: Option<usize>) -> usize {
+    match (address, symbol.text()) {
+        (Some(address), _) => symbol_address_len(address),
+        (None, Some(text)) => text_len(text.len()),
+        // The system symbol `$0`
+        (None, None) => 2,
+    }
+}
+
+fn write_symbol(buffer: &mut Vec<u8>, symbol: &SymbolToken, address: Option<usize>) {
+    match (address, symbol.text()) {
+        (Some(address), _) => write_symbol_address(buffer, address),
+        (None, Some(text)) => write_text(buffer, 0xA0, 0xFA, text),
+        (None, None) => buffer.extend_from_slice(&[0xEE, 0x00]),
+    }
+}
+
+// 0xE1 and 0xE2 are followed by a FixedUInt of 1 and 2 bytes, 0xE3 by a
+// FlexUInt. Each of them starts where the previous one ends.
+fn symbol_address_len(address: usize) -> usize {
+    match address {
+        0..=255 => 2,
+        256..=65791 => 3,
+        _ => 1 + flex_uint_len(address - 65792),
+    }
+}
+
+fn write_symbol_address(buffer: &mut Vec<u8>, address: usize) {
+    match address {
+        0..=255 => buffer.extend_from_slice(&[0xE1, address as u8]),
+        256..=65791 => {
+            buffer.push(0xE2);
+            buffer.extend_from_slice(&((address - 256) as u16).to_le_bytes());
+        }
+        _ => {
+            buffer.push(0xE3);
+            encode_flex_uint_buffer(buffer, address - 65792);
+        }
+    }
+}
+
+/// Field names and annotations are FlexUInt addresses, or FlexSyms when
+/// any of them is written inline.
+fn symbol_ref_len(text: Option<&str>, address: Option<usize>, with_flex_syms: bool) -> usize {
+    match (address, text) {
+        (Some(address), _) if with_flex_syms => flex_int_len(address as i128),
+        (Some(address), _) => flex_uint_len(address),
+        (None, Some(text)) if !text.is_empty() => flex_int_len(-(text.len() as i128)) + text.len(),
+        // A zero followed by the escape of the empty text or `$0`
+        (None, _) => 2,
+    }
+}
+
+fn write_symbol_ref(
+    buffer: &mut Vec<u8>,
+    text: Option<&str>,
+    address: Option<usize>,
+    with_flex_syms: bool,
+) {
+    match (address, text) {
+        (Some(address), _) if with_flex_syms => encode_flex_int_buffer(buffer, address as i128),
+        (Some(address), _) => encode_flex_uint_buffer(buffer, address),
+        (None, Some("")) => buffer.extend_from_slice(&[0x01, 0xA0]),
+        (None, Some(text)) => {
+            encode_flex_int_buffer(buffer, -(text.len() as i128));
+            buffer.extend_from_slice(text.as_bytes());
+        }
+        (None, None) => buffer.extend_from_slice(&[0x01, 0x60]),
+    }
+}
+
+fn annotations_header_len(count: usize, len: usize) -> usize {
+    match count {
+        0 => 0,
+        1 | 2 => 1,
+        _ => 1 + flex_uint_len(len),
+    }
+}
+
+// Lists and sexps are delimited when their length would take more bytes
+// than the end opcode.
+fn is_delimited_sequence(len: usize) -> bool {
+    flex_uint_len(len) > 1
+}
+
+fn sequence_header_len(len: usize) -> usize {
+    if is_delimited_sequence(len) {
+        // The opcode and the end one
+        2
+    } else {
+        opcode_len_1_1(len, 15)
+    }
+}
+
+// Delimited structs always have FlexSym field names, so only the ones
+// having them anyway are delimited. Otherwise they need a byte switching to
+// FlexSyms after the length.
+fn is_delimited_struct(len: usize, with_flex_syms: bool) -> bool {
+    with_flex_syms && flex_uint_len(len + 1) > 1
+}
+
+fn struct_header_len(len: usize, with_flex_syms: bool) -> usize {
+    if is_delimited_struct(len, with_flex_syms) {
+        // The opcode, and the escape and the end opcode
+        3
+    } else {
+        let switch_len = usize::from(with_flex_syms);
+
+        opcode_len_1_1(len + switch_len, 15) + switch_len
+    }
+}
@@ -1,12 +1,11 @@
 use crate::binary_encoder::{encode_datetime_representation, encode_int, encode_varint};
-use crate::binary_parser_types::{SystemSymbolIds, SYSTEM_SYMBOL_TABLE};
-use crate::{IonValue, NullIonValue};
+use crate::ion_timestamp::IonTimestamp;
+use crate::symbol_token::FieldName;
+use crate::{IonValue, NullIonValue, SymbolToken};
 use bigdecimal::BigDecimal;
 use bigdecimal::Zero;
-use chrono::{DateTime, FixedOffset};
 use digest::Digest;
 use num_bigint::{BigInt, Sign};
-use std::collections::HashMap;
 
 pub fn encode_value<D: Digest>(value: &IonValue) -> Vec<u8> {
     let encoded_value = match value {
@@ -18,12 +17,15 @@ pub fn encode_value<D: Digest>(value: &IonValue) -> Vec<u8> {
         IonValue::Decimal(value) => encode_decimal_value(value),
         IonValue::DateTime(value) => encode_datetime_value(value),
         IonValue::String(value) => encode_string(value, 0x80),
-        IonValue::Symbol(value) => encode_symbol(value),
+        IonValue::Symbol(value) => encode_symbol_token(value),
         IonValue::Clob(value) => encode_blob(value, 0x90),
         IonValue::Blob(value) => encode_blob(value, 0xA0),
         IonValue::List(value) => encode_list::<D>(value, 0xB0),
         IonValue::SExpr(value) => encode_list::<D>(value, 0xC0),
-        IonValue::Struct(value) => encode_struct::<D>(value),
+        IonValue::Struct(value) => encode_struct::<D, _, _>(value),
+        IonValue::OrderedStruct(value) => {
+            encode_struct::<D, _, _>(value.iter().map(|(name, value)| (name, value)))
+        }
         IonValue::Annotation(annotations, value) => encode_annotation::<D>(annotations, value),
     };
 
@@ -38,11 +40,11 @@ fn add_markers(mut encoded_value: Vec<u8>) -> Vec<u8> {
     buffer
 }
 
-fn encode_annotation<D: Digest>(annotations: &[String], value: &IonValue) -> Vec<u8> {
+fn encode_annotation<D: Digest>(annotations: &[SymbolToken], value: &IonValue) -> Vec<u8> {
     let mut buffer = vec![0xE0];
 
     for annotation in annotations {
-        buffer.append(&mut encode_value::<D>(&IonValue::Symbol(annotation.into())));
+        buffer.append(&mut add_markers(encode_symbol_token(annotation)));
     }
 
     buffer.append(&mut encode_value::<D>(value));
@@ -50,11 +52,17 @@ fn encode_annotation<D: Digest>(annotations: &[String], value: &IonValue) -> Vec
     buffer
 }
 
-fn encode_struct<D: Digest>(values: &HashMap<String, IonValue>) -> Vec<u8> {
+// Fields are hashed one by one and the hashes are sorted, so the field order
+// doesn't change the result while repeated fields are all taken into account.
+fn encode_struct<'a, D: Digest, K, I>(fields: I) -> Vec<u8>
+where
+    K: FieldName + 'a,
+    I: IntoIterator<Item = (&'a K, &'a IonValue)>,
+{
     let mut hashes: Vec<Vec<u8>> = vec![];
 
-    for (name, value) in values {
-        let mut buffer = add_markers(encode_symbol(name));
+    for (name, value) in fields {
+        let mut buffer = add_markers(encode_symbol_text(name.text()));
         buffer.append(&mut encode_value::<D>(value));
         let hash = D::digest(&buffer).to_vec();
         hashes.push(hash);
@@ -91,13 +99,22 @@ fn encode_blob(value: &[u8], header: u8) -> Vec<u8> {
 }
 
 fn encode_symbol(value: &str) -> Vec<u8> {
-    if value == SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Zero as usize] {
-        return encode_string(value, 0x71);
-    }
-
     encode_string(value, 0x70)
 }
 
+// Symbols with unknown text are hashed as $0 no matter where they come from,
+// with TQ 0x71 and an empty representation.
+fn encode_symbol_token(value: &SymbolToken) -> Vec<u8> {
+    encode_symbol_text(value.text())
+}
+
+fn encode_symbol_text(value: Option<&str>) -> Vec<u8> {
+    match value {
+        Some(text) => encode_symbol(text),
+        None => vec![0x71],
+    }
+}
+
 fn encode_string(value: &str, header: u8) -> Vec<u8> {
     let mut buffer = vec![header];
 
@@ -106,7 +123,7 @@ fn encode_string(value: &str, header: u8) -> Vec<u8> {
     buffer
 }
 
-fn encode_datetime_value(value: &DateTime<FixedOffset>) -> Vec<u8> {
+fn encode_datetime_value(value: &IonTimestamp) -> Vec<u8> {
     let mut buffer = vec![0x60];
 
     buffer.append(&mut escape_buffer(&encode_datetime_representation(value)));
@@ -0,0 +1,418 @@
+use crate::ion_parser_types::IonValue;
+use crate::ion_text_parser::IonTextParser;
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+/// Builds an `IonValue` from Ion-like syntax.
+///
+/// - `null`, `null.int`, `null.struct`... are nulls, typed or not.
+/// - `true`, `false`, integers and floats are Rust literals, so `1.5` is a
+///   float. `nan`, `+inf` and `-inf` are special floats.
+/// - `"text"` is a string and identifiers like `sedan` are symbols. Symbols
+///   that aren't identifiers are written as `symbol("a symbol")`.
+/// - `decimal("1.50")` and `timestamp("2024-03-15T10:30Z")` take their
+///   Ion text and panic if it is not valid.
+/// - `blob(bytes)` and `clob(bytes)` take anything that converts into a
+///   `Vec<u8>`, like `b"bytes"`.
+/// - `[a, b]` are lists, `(a b)` sexps and `{ name: a, "other name": b }`
+///   structs. Repeated fields keep the last value.
+/// - `name::value` and `"other name"::value` add annotations.
+/// - `{ expr }` is any Rust expression that converts into an `IonValue`,
+///   such as variables or other `IonValue`s. Field names can be Rust
+///   expressions in the same way.
+///
+/// In sexps, values are separated by whitespace. Operators like `+` or `<`
+/// are symbols, but a `-` or `+` followed by a number is read as a signed
+/// number.
+///
+/// ```rust
+/// use ion_binary_rs::{ion, IonValue, NullIonValue};
+/// use std::collections::HashMap;
+///
+/// let year = 2019;
+///
+/// let car = ion!({
+///     model: "CLK 350",
+///     kind: sedan,
+///     year: { year },
+///     price: usd::decimal("48500.00"),
+///     owners: [],
+///     inspection: null.timestamp,
+///     filter: (> mileage 10000),
+/// });
+///
+/// let mut fields = HashMap::new();
+/// fields.insert("model".to_string(), IonValue::String("CLK 350".to_string()));
+/// fields.insert("kind".to_string(), IonValue::Symbol("sedan".into()));
+/// fields.insert("year".to_string(), IonValue::Integer(2019));
+/// fields.insert(
+///     "price".to_string(),
+///     IonValue::Annotation(
+///         vec!["usd".into()],
+///         Box::new(IonValue::Decimal("48500.00".parse().unwrap())),
+///     ),
+/// );
+/// fields.insert("owners".to_string(), IonValue::List(vec![]));
+/// fields.insert("inspection".to_string(), IonValue::Null(NullIonValue::DateTime));
+/// fields.insert(
+///     "filter".to_string(),
+///     IonValue::SExpr(vec![
+///         IonValue::Symbol(">".into()),
+///         IonValue::Symbol("mileage".into()),
+///         IonValue::Integer(10000),
+///     ]),
+/// );
+///
+/// assert_eq!(car, IonValue::Struct(fields));
+/// ```
+#[macro_export]
+macro_rules! ion {
+    // Lists, split by commas. Used when some element takes more than one
+    // token tree, as it recurses once per token.
+
+    (@list [$($elements:expr,)*]) => {
+        ::std::vec![$($elements,)*]
+    };
+
+    (@list [$($elements:expr,)*] $($rest:tt)+) => {
+        $crate::ion!(@list_element [$($elements,)*] [] $($rest)+)
+    };
+
+    (@list_element [$($elements:expr,)*] [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::ion!(@list [$($elements,)* $crate::ion!($($value)+),] $($rest)*)
+    };
+
+    (@list_element [$($elements:expr,)*] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::ion!(@list_element [$($elements,)*] [$($value)* $next] $($rest)*)
+    };
+
+    (@list_element [$($elements:expr,)*] [$($value:tt)+]) => {
+        $crate::ion!(@list [$($elements,)* $crate::ion!($($value)+),])
+    };
+
+    // Struct fields, inserted one by one into `$fields`. As with lists, only
+    // used when some value takes more than one token tree.
+
+    (@struct $fields:ident) => {};
+
+    (@struct $fields:ident $name:ident : $($rest:tt)+) => {
+        $crate::ion!(@field $fields [stringify!($name)] [] $($rest)+)
+    };
+
+    (@struct $fields:ident $name:literal : $($rest:tt)+) => {
+        $crate::ion!(@field $fields [$name] [] $($rest)+)
+    };
+
+    (@struct $fields:ident { $name:expr } : $($rest:tt)+) => {
+        $crate::ion!(@field $fields [$name] [] $($rest)+)
+    };
+
+    (@field_name $name:ident) => {
+        stringify!($name)
+    };
+
+    (@field_name $name:literal) => {
+        $name
+    };
+
+    (@field_name { $name:expr }) => {
+        $name
+    };
+
+    (@field $fields:ident [$name:expr] [$($value:tt)+] , $($rest:tt)*) => {
+        $fields.insert(::std::convert::Into::<::std::string::String>::into($name), $crate::ion!($($value)+));
+        $crate::ion!(@struct $fields $($rest)*)
+    };
+
+    (@field $fields:ident [$name:expr] [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::ion!(@field $fields [$name] [$($value)* $next] $($rest)*)
+    };
+
+    (@field $fields:ident [$name:expr] [$($value:tt)+]) => {
+        $fields.insert(::std::convert::Into::<::std::string::String>::into($name), $crate::ion!($($value)+));
+    };
+
+    // Sexp values, split by whitespace. Annotations are kept in `$value`
+    // until the annotated value is found.
+
+    (@sexp [$($elements:expr,)*] []) => {
+        ::std::vec![$($elements,)*]
+    };
+
+    // Checked before anything else, as the literal matchers would fail on
+    // a `-` that is followed by a symbol
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] - $($rest:tt)*) => {
+        $crate::ion!(@sexp_minus [$($elements,)*] [$($value)*] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] $annotation:ident :: $($rest:tt)+) => {
+        $crate::ion!(@sexp [$($elements,)*] [$($value)* $annotation ::] $($rest)+)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] $annotation:literal :: $($rest:tt)+) => {
+        $crate::ion!(@sexp [$($elements,)*] [$($value)* $annotation ::] $($rest)+)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] null . $type:ident $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* null . $type),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] + inf $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* +inf),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] + $number:literal $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* $number),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] decimal ($($text:tt)+) $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* decimal($($text)+)),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] timestamp ($($text:tt)+) $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* timestamp($($text)+)),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] symbol ($($text:tt)+) $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* symbol($($text)+)),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] blob ($($bytes:tt)+) $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* blob($($bytes)+)),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] clob ($($bytes:tt)+) $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* clob($($bytes)+)),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] $next:ident $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* $next),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] $next:literal $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* $next),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] [$($next:tt)*] $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* [$($next)*]),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] ($($next:tt)*) $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* ($($next)*)),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] {$($next:tt)*} $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* {$($next)*}),] [] $($rest)*)
+    };
+
+    (@sexp [$($elements:expr,)*] [$($value:tt)*] $operator:tt $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* @operator $operator),] [] $($rest)*)
+    };
+
+    (@sexp_minus [$($elements:expr,)*] [$($value:tt)*] inf $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* -inf),] [] $($rest)*)
+    };
+
+    (@sexp_minus [$($elements:expr,)*] [$($value:tt)*] $number:literal $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* - $number),] [] $($rest)*)
+    };
+
+    (@sexp_minus [$($elements:expr,)*] [$($value:tt)*] $($rest:tt)*) => {
+        $crate::ion!(@sexp [$($elements,)* $crate::ion!($($value)* @operator -),] [] $($rest)*)
+    };
+
+    (@operator $operator:tt) => {
+        $crate::IonValue::Symbol($crate::SymbolToken::new(stringify!($operator)))
+    };
+
+    // Nulls
+
+    (null) => {
+        $crate::IonValue::Null($crate::NullIonValue::Null)
+    };
+
+    (null.null) => {
+        $crate::IonValue::Null($crate::NullIonValue::Null)
+    };
+
+    (null.bool) => {
+        $crate::IonValue::Null($crate::NullIonValue::Bool)
+    };
+
+    (null.int) => {
+        $crate::IonValue::Null($crate::NullIonValue::Integer)
+    };
+
+    (null.float) => {
+        $crate::IonValue::Null($crate::NullIonValue::Float)
+    };
+
+    (null.decimal) => {
+        $crate::IonValue::Null($crate::NullIonValue::Decimal)
+    };
+
+    (null.timestamp) => {
+        $crate::IonValue::Null($crate::NullIonValue::DateTime)
+    };
+
+    (null.string) => {
+        $crate::IonValue::Null($crate::NullIonValue::String)
+    };
+
+    (null.symbol) => {
+        $crate::IonValue::Null($crate::NullIonValue::Symbol)
+    };
+
+    (null.blob) => {
+        $crate::IonValue::Null($crate::NullIonValue::Blob)
+    };
+
+    (null.clob) => {
+        $crate::IonValue::Null($crate::NullIonValue::Clob)
+    };
+
+    (null.list) => {
+        $crate::IonValue::Null($crate::NullIonValue::List)
+    };
+
+    (null.sexp) => {
+        $crate::IonValue::Null($crate::NullIonValue::SExpr)
+    };
+
+    (null.struct) => {
+        $crate::IonValue::Null($crate::NullIonValue::Struct)
+    };
+
+    // Scalars without a Rust literal
+
+    (true) => {
+        $crate::IonValue::Bool(true)
+    };
+
+    (false) => {
+        $crate::IonValue::Bool(false)
+    };
+
+    (nan) => {
+        $crate::IonValue::Float(f64::NAN)
+    };
+
+    (+inf) => {
+        $crate::IonValue::Float(f64::INFINITY)
+    };
+
+    (-inf) => {
+        $crate::IonValue::Float(f64::NEG_INFINITY)
+    };
+
+    (decimal($text:expr)) => {
+        $crate::__ion_decimal($text)
+    };
+
+    (timestamp($text:expr)) => {
+        $crate::__ion_timestamp($text)
+    };
+
+    (symbol($text:expr)) => {
+        $crate::IonValue::Symbol($crate::SymbolToken::from($text))
+    };
+
+    (blob($bytes:expr)) => {
+        $crate::IonValue::Blob(::std::convert::Into::<::std::vec::Vec<u8>>::into($bytes))
+    };
+
+    (clob($bytes:expr)) => {
+        $crate::IonValue::Clob(::std::convert::Into::<::std::vec::Vec<u8>>::into($bytes))
+    };
+
+    // Annotations
+
+    ($annotation:ident :: $($value:tt)+) => {
+        $crate::__ion_annotate(stringify!($annotation), $crate::ion!($($value)+))
+    };
+
+    ($annotation:literal :: $($value:tt)+) => {
+        $crate::__ion_annotate($annotation, $crate::ion!($($value)+))
+    };
+
+    // Containers
+
+    ([]) => {
+        $crate::IonValue::List(::std::vec![])
+    };
+
+    // Without recursion, so long lists don't reach the recursion limit
+    ([ $($element:tt),+ $(,)? ]) => {
+        $crate::IonValue::List(::std::vec![$($crate::ion!($element)),+])
+    };
+
+    ([ $($elements:tt)+ ]) => {
+        $crate::IonValue::List($crate::ion!(@list [] $($elements)+))
+    };
+
+    (( $($elements:tt)* )) => {
+        $crate::IonValue::SExpr($crate::ion!(@sexp [] [] $($elements)*))
+    };
+
+    ({}) => {
+        $crate::IonValue::Struct(::std::collections::HashMap::new())
+    };
+
+    ({ $value:expr }) => {
+        $crate::IonValue::from($value)
+    };
+
+    ({ $($name:tt : $value:tt),+ $(,)? }) => {{
+        let mut fields = ::std::collections::HashMap::new();
+        $(
+            fields.insert(
+                ::std::convert::Into::<::std::string::String>::into($crate::ion!(@field_name $name)),
+                $crate::ion!($value),
+            );
+        )+
+        $crate::IonValue::Struct(fields)
+    }};
+
+    ({ $($fields:tt)+ }) => {{
+        let mut fields = ::std::collections::HashMap::new();
+        $crate::ion!(@struct fields $($fields)+);
+        $crate::IonValue::Struct(fields)
+    }};
+
+    // Symbols and Rust literals
+
+    ($symbol:ident) => {
+        $crate::IonValue::Symbol($crate::SymbolToken::new(stringify!($symbol)))
+    };
+
+    ($value:expr) => {
+        $crate::IonValue::from($value)
+    };
+}
+
+#[doc(hidden)]
+pub fn __ion_decimal(text: &str) -> IonValue {
+    match BigDecimal::from_str(text) {
+        Ok(decimal) => IonValue::Decimal(decimal),
+        Err(_) => panic!("Invalid decimal in ion!: {:?}", text),
+    }
+}
+
+#[doc(hidden)]
+pub fn __ion_timestamp(text: &str) -> IonValue {
+    match IonTextParser::new(text.as_bytes()).consume_all().as_deref() {
+        Ok([value @ IonValue::DateTime(_)]) => value.clone(),
+        _ => panic!("Invalid timestamp in ion!: {:?}", text),
+    }
+}
+
+#[doc(hidden)]
+pub fn __ion_annotate(annotation: &str, value: IonValue) -> IonValue {
+    match value {
+        IonValue::Annotation(mut annotations, value) => {
+            annotations.insert(0, annotation.into());
+            IonValue::Annotation(annotations, value)
+        }
+        value => IonValue::Annotation(vec![annotation.into()], Box::new(value)),
+    }
+}
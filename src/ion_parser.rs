@@ -1,17 +1,26 @@
 use crate::binary_parser::IonBinaryParser;
 use crate::binary_parser_types::*;
+use crate::catalog::Catalog;
 use crate::ion_parser_types::*;
+use crate::ion_timestamp::{IonTimestamp, TimestampPrecision};
 use crate::symbol_table::*;
+use crate::symbol_token::SymbolToken;
 use bigdecimal::BigDecimal;
-use chrono::{naive::NaiveDate, DateTime, FixedOffset, Utc};
 use log::trace;
 use num_bigint::{BigInt, BigUint};
 use num_traits::ops::checked::CheckedSub;
+use num_traits::Zero;
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
-use std::{collections::HashMap, io::Read};
+use std::io::{Read, Seek};
+use std::sync::Arc;
 
 /// In order to use it call the new method and then the "consume_all" method.
 ///
+/// `consume_value_located` and `consume_all_located` return the errors as a
+/// `LocatedIonParserError`, with the byte offset, the index of the top-level
+/// value and the path inside it where they were found.
+///
 /// ### Example
 ///
 /// ```rust,no_run
@@ -29,13 +38,90 @@ use std::{collections::HashMap, io::Read};
 /// ```
 #[derive(Debug)]
 pub struct IonParser<T: Read> {
-    parser: IonBinaryParser<T>,
-    context: SymbolContext,
+    pub(crate) parser: IonBinaryParser<T>,
+    pub(crate) context: SymbolContext,
     temp_buffer: Vec<u8>,
+    pub(crate) ordered_structs: bool,
+    options: IonParserOptions,
+    value_index: usize,
+    // Filled while an error goes up through the containers, so it holds the
+    // innermost component first.
+    error_path: Vec<PathComponent>,
 }
 
 pub type ConsumerResult = Result<(IonValue, usize), IonParserError>;
 
+/// Annotation wrapper data read before the wrapped value.
+#[derive(Debug)]
+pub(crate) struct AnnotationHeader {
+    pub(crate) symbols: Vec<usize>,
+    pub(crate) length: usize,
+    pub(crate) consumed_bytes: usize,
+    pub(crate) total_consumed_bytes: usize,
+}
+
+/// A value header already read from the stream, together with its
+/// annotations and, if annotated, the position where the annotation
+/// wrapper ends.
+#[derive(Debug)]
+pub(crate) struct PeekedValue {
+    pub(crate) header: ValueHeader,
+    pub(crate) annotations: Vec<SymbolToken>,
+    pub(crate) annotation_end: Option<usize>,
+}
+
+/// Limits checked by the `IonParser`, useful when parsing untrusted input.
+/// Every limit is disabled when set to None, which is the default, and
+/// returns its own `IonParserError` when it is exceeded. The parser recurses
+/// into every nested container, so without `max_depth` the nesting is only
+/// limited by the stack.
+///
+/// ```rust
+/// use ion_binary_rs::{IonEncoder, IonParser, IonParserError, IonParserOptions, IonValue};
+///
+/// let mut encoder = IonEncoder::new();
+/// encoder.add(IonValue::List(vec![IonValue::List(vec![IonValue::Integer(1)])]));
+/// let bytes = encoder.encode();
+///
+/// let mut parser = IonParser::new(&bytes[..]);
+/// parser.with_options(IonParserOptions {
+///     max_depth: Some(1),
+///     ..IonParserOptions::default()
+/// });
+///
+/// assert_eq!(
+///     parser.consume_value().unwrap_err(),
+///     IonParserError::MaxDepthExceeded
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IonParserOptions {
+    /// Maximum amount of nested containers. A top-level list has a depth of 1.
+    /// Annotations don't count, but symbol tables do, and they need a depth
+    /// of 3 when they import shared tables.
+    pub max_depth: Option<usize>,
+    /// Maximum length in bytes of a value, as declared in its header. It
+    /// applies to containers and annotation wrappers too, so it also limits
+    /// the size of top-level values.
+    pub max_value_size: Option<usize>,
+    /// Maximum amount of symbols a symbol table can declare, including the
+    /// imported ones and the ones appended to the previous table.
+    pub max_symbols: Option<usize>,
+    /// Maximum amount of imports in a local symbol table.
+    pub max_imports: Option<usize>,
+    /// Maximum amount of bytes to read from the reader. It is checked after
+    /// every value header and before every value body is read.
+    pub max_total_input: Option<usize>,
+}
+
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub(crate) enum AnnotationKind {
+    SharedTable,
+    LocalTable,
+    User,
+}
+
 impl<T: Read> IonParser<T> {
     /// Creates a new parser. It accepts anything that implements the trait
     /// [Read Trait](https://doc.rust-lang.org/stable/std/io/trait.Read.html)
@@ -45,6 +131,29 @@ impl<T: Read> IonParser<T> {
             parser: IonBinaryParser::new(reader),
             context: SymbolContext::new(),
             temp_buffer: Vec::with_capacity(256),
+            ordered_structs: false,
+            options: IonParserOptions::default(),
+            value_index: 0,
+            error_path: vec![],
+        }
+    }
+
+    /// Same as `new`, but for readers that also implement the trait
+    /// [Seek Trait](https://doc.rust-lang.org/stable/std/io/trait.Seek.html).
+    /// Values skipped with `skip_value` are seeked over instead of read.
+    #[inline]
+    pub fn new_seekable(reader: T) -> IonParser<T>
+    where
+        T: Seek,
+    {
+        IonParser {
+            parser: IonBinaryParser::new_seekable(reader),
+            context: SymbolContext::new(),
+            temp_buffer: Vec::with_capacity(256),
+            ordered_structs: false,
+            options: IonParserOptions::default(),
+            value_index: 0,
+            error_path: vec![],
         }
     }
 
@@ -66,14 +175,69 @@ impl<T: Read> IonParser<T> {
         self.context.add_shared_table(name, version, &symbols)
     }
 
+    /// Sets a catalog where the parser looks for the shared tables imported
+    /// by local symbol tables that have not been added with
+    /// `with_shared_table`.
+    #[inline]
+    pub fn with_catalog<C: Catalog + 'static>(&mut self, catalog: C) {
+        self.context.set_catalog(Arc::new(catalog));
+    }
+
+    /// Shared tables declared in the stream read so far, plus the ones
+    /// added with `with_shared_table` or taken from the catalog.
+    pub(crate) fn get_shared_tables(&self) -> Vec<SharedSymbolTable> {
+        self.context.get_shared_tables()
+    }
+
+    /// Makes the parser return structs as IonValue::OrderedStruct, keeping
+    /// the order of the fields and repeated field names.
+    #[inline]
+    pub fn with_ordered_structs(&mut self) {
+        self.ordered_structs = true;
+    }
+
+    /// Sets the limits checked while parsing, see `IonParserOptions`.
+    #[inline]
+    pub fn with_options(&mut self, options: IonParserOptions) {
+        self.options = options;
+    }
+
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn get_options(&self) -> &IonParserOptions {
+        &self.options
+    }
+
+    /// Returns a reference to the underlying reader.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn get_ref(&self) -> &T {
+        self.parser.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        self.parser.get_mut()
+    }
+
     /// Consumes all the IonValues in the binary blob and returns an array with them.
     #[inline]
     pub fn consume_all(&mut self) -> Result<Vec<IonValue>, IonParserError> {
+        self.consume_all_located().map_err(IonParserError::from)
+    }
+
+    /// Same as `consume_all`, but the error tells where it was found.
+    #[inline]
+    pub fn consume_all_located(&mut self) -> Result<Vec<IonValue>, LocatedIonParserError> {
         let mut values = Vec::with_capacity(1);
 
         loop {
-            match self.consume_value() {
-                Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => break,
+            match self.consume_value_located() {
+                Err(e) if *e.kind() == IonParserError::BinaryError(ParsingError::NoDataToRead) => {
+                    break
+                }
                 Ok((value, _)) => values.push(value),
                 Err(e) => return Err(e),
             }
@@ -86,10 +250,32 @@ impl<T: Read> IonParser<T> {
     /// NOP Padding, Shared Tables and Local Tables, automatically continuing in case
     /// that any of them are found.
     #[inline]
-    pub fn consume_value(&mut self) -> ConsumerResult {
-        let value_header = self.parser.consume_value_header(0)?;
+    pub fn consume_value(&mut self) -> Result<(IonValue, usize), IonParserError> {
+        self.consume_value_located().map_err(IonParserError::from)
+    }
+
+    /// Same as `consume_value`, but the error tells where it was found.
+    #[inline]
+    pub fn consume_value_located(
+        &mut self,
+    ) -> Result<(IonValue, usize), LocatedIonParserError> {
+        let result = self.consume_next_value(0);
+        self.locate(result)
+    }
+
+    /// Reads the next value, `nested_level` containers deep. Annotated
+    /// values and values after NOP Padding are read at the same level.
+    #[inline]
+    pub(crate) fn consume_next_value(&mut self, nested_level: u64) -> ConsumerResult {
+        let opcode = self.consume_opcode(nested_level)?;
+
+        if self.parser.is_ion_1_1() {
+            return self.consume_top_level_value_1_1(opcode);
+        }
+
+        let value_header = ValueHeader::new(opcode)?;
 
-        let mut value = self.consume_value_body(&value_header, 0)?;
+        let mut value = self.consume_value_body(&value_header, nested_level)?;
 
         let already_consumed_value_header = 1;
         value.1 += already_consumed_value_header;
@@ -97,46 +283,341 @@ impl<T: Read> IonParser<T> {
         Ok(value)
     }
 
+    /// Same as `consume_next_value`, but it stops after processing NOP
+    /// Padding or a symbol table, returning None instead of reading the next
+    /// value.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn consume_next_item(
+        &mut self,
+    ) -> Result<Option<(IonValue, usize)>, IonParserError> {
+        let value_header = self.consume_value_header(0)?;
+
+        if value_header.is_nop() {
+            self.consume_nop(&value_header)?;
+            return Ok(None);
+        }
+
+        let (value, consumed_bytes) = match value_header.get_type() {
+            ValueType::Annotation => match self.consume_annotation(&value_header, 0)? {
+                (Some(annotation), consumed_bytes) => (annotation, consumed_bytes),
+                (None, _) => return Ok(None),
+            },
+            _ => self.consume_value_body(&value_header, 0)?,
+        };
+
+        let already_consumed_value_header = 1;
+
+        Ok(Some((
+            value,
+            consumed_bytes + already_consumed_value_header,
+        )))
+    }
+
+    /// Skips **one** IonValue without decoding it, using the lengths in the
+    /// value headers to move past it. As with `consume_value`, NOP Padding,
+    /// Shared Tables and Local Tables are processed. Returns the amount of
+    /// bytes skipped.
+    #[inline]
+    pub fn skip_value(&mut self) -> Result<usize, IonParserError> {
+        let result = self.skip_next_value();
+        self.locate(result).map_err(IonParserError::from)
+    }
+
+    #[inline]
+    fn skip_next_value(&mut self) -> Result<usize, IonParserError> {
+        let start = self.position();
+
+        loop {
+            let header = self.consume_value_header(0)?;
+
+            if header.is_nop() {
+                self.consume_nop(&header)?;
+                continue;
+            }
+
+            if header.get_type() != ValueType::Annotation {
+                self.skip_value_body(&header)?;
+                break;
+            }
+
+            let annotation = self.consume_annotation_header(&header)?;
+
+            match self.get_annotation_kind(&annotation.symbols)? {
+                AnnotationKind::SharedTable => {
+                    let table = self.consume_annotated_value(&annotation, 0)?;
+                    self.load_shared_table(table)?;
+                }
+                AnnotationKind::LocalTable => {
+                    let table = self.consume_annotated_value(&annotation, 0)?;
+                    self.load_local_table(table)?;
+                }
+                AnnotationKind::User => {
+                    let value_len = annotation
+                        .length
+                        .checked_sub(annotation.consumed_bytes)
+                        .ok_or(IonParserError::BadAnnotationLength)?;
+
+                    self.skip_bytes(value_len)?;
+                    break;
+                }
+            }
+        }
+
+        Ok(self.position() - start)
+    }
+
+    /// Returns the amount of bytes consumed from the reader so far.
     #[inline]
-    fn consume_value_body(
+    pub fn position(&self) -> usize {
+        self.parser.position()
+    }
+
+    #[inline]
+    pub(crate) fn is_ion_1_1(&self) -> bool {
+        self.parser.is_ion_1_1()
+    }
+
+    /// Adds the location to the error of reading a top-level value, or
+    /// moves to the next top-level value index if it was read.
+    pub(crate) fn locate<V>(
+        &mut self,
+        result: Result<V, IonParserError>,
+    ) -> Result<V, LocatedIonParserError> {
+        match result {
+            Ok(value) => {
+                self.value_index += 1;
+                Ok(value)
+            }
+            Err(error) => {
+                let mut path = Vec::with_capacity(self.error_path.len() + 1);
+                path.push(PathComponent::Index(self.value_index));
+                path.extend(self.error_path.drain(..).rev());
+
+                Err(LocatedIonParserError::new(
+                    error,
+                    self.position(),
+                    self.value_index,
+                    IonValuePath::new(path),
+                ))
+            }
+        }
+    }
+
+    /// Records that the error being returned was found inside the given
+    /// struct field or list element.
+    #[inline]
+    pub(crate) fn push_error_path(&mut self, component: PathComponent) {
+        self.error_path.push(component);
+    }
+
+    #[inline]
+    pub(crate) fn consume_value_header(
+        &mut self,
+        nested_level: u64,
+    ) -> Result<ValueHeader, IonParserError> {
+        let opcode = self.consume_opcode(nested_level)?;
+
+        // Only `consume_next_value` can read Ion 1.1
+        if self.parser.is_ion_1_1() {
+            return Err(IonParserError::Ion11NotSupported);
+        }
+
+        Ok(ValueHeader::new(opcode)?)
+    }
+
+    #[inline]
+    pub(crate) fn consume_opcode(&mut self, nested_level: u64) -> Result<u8, IonParserError> {
+        let opcode = self.parser.consume_opcode(nested_level)?;
+
+        // Checked once read, so a stream ending at the limit is not an error.
+        self.check_total_input(0)?;
+
+        Ok(opcode)
+    }
+
+    #[inline]
+    fn check_total_input(&self, length: usize) -> Result<(), IonParserError> {
+        match self.options.max_total_input {
+            Some(max) if self.position().saturating_add(length) > max => {
+                Err(IonParserError::MaxTotalInputExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the length of a value body before it is read.
+    #[inline]
+    pub(crate) fn check_value_len(&self, length: usize) -> Result<(), IonParserError> {
+        if matches!(self.options.max_value_size, Some(max) if length > max) {
+            return Err(IonParserError::MaxValueSizeExceeded);
+        }
+
+        self.check_total_input(length)
+    }
+
+    /// Checks that a container at the given level can be read.
+    #[inline]
+    pub(crate) fn check_depth(&self, nested_level: u64) -> Result<(), IonParserError> {
+        match self.options.max_depth {
+            Some(max) if nested_level.saturating_add(1) > max as u64 => {
+                Err(IonParserError::MaxDepthExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn consume_value_body(
         &mut self,
         value_header: &ValueHeader,
         nested_level: u64,
     ) -> ConsumerResult {
         if value_header.is_nop() {
             let consumed_bytes = self.consume_nop(value_header)?;
-            let value = self.consume_value()?;
+            let value = self.consume_next_value(nested_level)?;
             return Ok((value.0, value.1 + consumed_bytes));
         }
 
         match value_header.get_type() {
-            ValueType::Bool => Ok(self.consume_bool(value_header)?),
-            ValueType::Annotation => match self.consume_annotation(value_header)? {
+            ValueType::Annotation => match self.consume_annotation(value_header, nested_level)? {
                 (Some(annotation), consumed_bytes) => Ok((annotation, consumed_bytes)),
                 (None, consumed_bytes) => {
-                    let value = self.consume_value()?;
+                    let value = self.consume_next_value(nested_level)?;
                     Ok((value.0, value.1 + consumed_bytes))
                 }
             },
-            ValueType::Struct => Ok(self.consume_struct(value_header, nested_level)?),
-            ValueType::List => Ok(self.consume_list(value_header, nested_level)?),
-            ValueType::Symbol => Ok(self.consume_symbol(value_header)?),
-            ValueType::PositiveInt => Ok(self.consume_int(value_header, false)?),
-            ValueType::NegativeInt => Ok(self.consume_int(value_header, true)?),
-            ValueType::String => Ok(self.consume_string(value_header)?),
-            ValueType::Timestamp => Ok(self.consume_timestamp(value_header)?),
+            ValueType::Struct => self.consume_struct(value_header, nested_level),
+            ValueType::List => self.consume_list(value_header, nested_level),
+            ValueType::SExpr => self.consume_sexpr(value_header, nested_level),
+            _ => self.consume_scalar(value_header),
+        }
+    }
+
+    // Scalars are read apart from containers, so the stack frames of the
+    // recursion through nested containers stay small.
+    #[inline(never)]
+    fn consume_scalar(&mut self, value_header: &ValueHeader) -> ConsumerResult {
+        match value_header.get_type() {
+            ValueType::Bool => self.consume_bool(value_header),
+            ValueType::Symbol => self.consume_symbol(value_header),
+            ValueType::PositiveInt => self.consume_int(value_header, false),
+            ValueType::NegativeInt => self.consume_int(value_header, true),
+            ValueType::String => self.consume_string(value_header),
+            ValueType::Timestamp => self.consume_timestamp(value_header),
             ValueType::Null => Ok((IonValue::Null(NullIonValue::Null), 0)),
-            ValueType::Float => Ok(self.consume_float(value_header)?),
-            ValueType::Decimal => Ok(self.consume_decimal(value_header)?),
-            ValueType::Clob => Ok(self.consume_clob(value_header)?),
-            ValueType::Blob => Ok(self.consume_blob(value_header)?),
-            ValueType::SExpr => Ok(self.consume_sexpr(value_header, nested_level)?),
-            ValueType::Reserved => Err(IonParserError::InvalidReservedTypeDescriptor),
+            ValueType::Float => self.consume_float(value_header),
+            ValueType::Decimal => self.consume_decimal(value_header),
+            ValueType::Clob => self.consume_clob(value_header),
+            ValueType::Blob => self.consume_blob(value_header),
+            ValueType::Reserved
+            | ValueType::Annotation
+            | ValueType::Struct
+            | ValueType::List
+            | ValueType::SExpr => Err(IonParserError::InvalidReservedTypeDescriptor),
         }
     }
 
+    /// Resolves a header read from the stream into the header of a user
+    /// value. NOP padding and symbol tables are consumed and processed here,
+    /// returning None. For annotations it reads the annotation symbols and
+    /// the header of the wrapped value.
+    pub(crate) fn resolve_value_header(
+        &mut self,
+        header: ValueHeader,
+        nested_level: u64,
+    ) -> Result<Option<PeekedValue>, IonParserError> {
+        if header.is_nop() {
+            self.consume_nop(&header)?;
+            return Ok(None);
+        }
+
+        if header.get_type() != ValueType::Annotation {
+            return Ok(Some(PeekedValue {
+                header,
+                annotations: vec![],
+                annotation_end: None,
+            }));
+        }
+
+        let annotation = self.consume_annotation_header(&header)?;
+
+        match self.get_annotation_kind(&annotation.symbols)? {
+            AnnotationKind::SharedTable => {
+                let table = self.consume_annotated_value(&annotation, nested_level)?;
+                self.load_shared_table(table)?;
+                Ok(None)
+            }
+            AnnotationKind::LocalTable => {
+                let table = self.consume_annotated_value(&annotation, nested_level)?;
+                self.load_local_table(table)?;
+                Ok(None)
+            }
+            AnnotationKind::User => {
+                let value_len = annotation
+                    .length
+                    .checked_sub(annotation.consumed_bytes)
+                    .ok_or(IonParserError::BadAnnotationLength)?;
+
+                let annotation_end = self.position() + value_len;
+
+                let mut annotations = Vec::with_capacity(annotation.symbols.len());
+
+                for symbol in annotation.symbols {
+                    annotations.push(self.get_symbol_name(symbol)?);
+                }
+
+                let header = loop {
+                    let header = self.consume_value_header(nested_level)?;
+
+                    if header.is_nop() {
+                        self.consume_nop(&header)?;
+                        continue;
+                    }
+
+                    if header.get_type() == ValueType::Annotation {
+                        return Err(IonParserError::NestedAnnotations);
+                    }
+
+                    break header;
+                };
+
+                Ok(Some(PeekedValue {
+                    header,
+                    annotations,
+                    annotation_end: Some(annotation_end),
+                }))
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn skip_bytes(&mut self, length: usize) -> Result<(), IonParserError> {
+        Ok(self.parser.skip_bytes(length)?)
+    }
+
+    /// Skips the body of a value whose header has already been read,
+    /// without decoding it. Returns the amount of bytes skipped.
+    pub(crate) fn skip_value_body(
+        &mut self,
+        header: &ValueHeader,
+    ) -> Result<usize, IonParserError> {
+        if header.is_len_null_value() || header.get_type() == ValueType::Bool {
+            return Ok(0);
+        }
+
+        let (length, _, total) = match header.get_type() {
+            ValueType::Struct => self.consume_value_len_for_struct(header)?,
+            ValueType::Reserved => return Err(IonParserError::InvalidReservedTypeDescriptor),
+            _ => self.consume_value_len(header)?,
+        };
+
+        self.skip_bytes(length)?;
+
+        Ok(total)
+    }
+
     #[inline]
-    fn consume_nop(&mut self, header: &ValueHeader) -> Result<usize, IonParserError> {
+    pub(crate) fn consume_nop(&mut self, header: &ValueHeader) -> Result<usize, IonParserError> {
         trace!("Consuming Nop Padding");
         let (length, _, total) = self.consume_value_len(header)?;
 
@@ -257,45 +738,33 @@ impl<T: Read> IonParser<T> {
             return Ok((IonValue::Null(NullIonValue::Struct), 0));
         }
 
+        self.check_depth(nested_level)?;
+
         let (length, _, total) = self.consume_value_len_for_struct(header)?;
         let mut consumed_bytes = 0;
-        let mut values: HashMap<String, IonValue> = HashMap::new();
+        let mut values: Vec<(SymbolToken, IonValue)> = Vec::new();
 
         while length - consumed_bytes > 0 {
-            let key = self.parser.consume_varuint()?;
-            consumed_bytes += key.1;
-
-            let key = match self.context.get_symbol_by_id(
-                key.0
-                    .try_into()
-                    .map_err(|_| IonParserError::SymbolIdTooBig)?,
-            ) {
-                Some(Symbol::Symbol(text)) => text.clone(),
-                _ => return Err(IonParserError::SymbolNotFoundInTable),
-            };
+            let (key, consumed) = self.consume_field_name()?;
+            consumed_bytes += consumed;
 
             trace!("Struct key field: {:?}", key);
 
-            let value_header = self
-                .parser
-                .consume_value_header(nested_level.saturating_add(1))?;
-
-            consumed_bytes += 1;
-
-            if value_header.is_nop() {
-                let consumed = self.consume_nop(&value_header)?;
-                trace!("Found NOP Padding in Struct of {:} bytes", consumed + 1);
-                consumed_bytes += consumed;
-                continue;
-            }
-
-            let value = self.consume_value_body(&value_header, nested_level.saturating_add(1))?;
+            let (value, consumed) = match self.consume_element(nested_level) {
+                Ok(element) => element,
+                Err(error) => {
+                    self.push_error_path(PathComponent::field(&key));
+                    return Err(error);
+                }
+            };
 
-            consumed_bytes += value.1;
+            consumed_bytes += consumed;
 
-            trace!("Struct field -> Key: {:?}, Value: {:?}", key, value.0);
+            if let Some(value) = value {
+                trace!("Struct field -> Key: {:?}, Value: {:?}", key, value);
 
-            values.insert(key, value.0);
+                values.push((key, value));
+            }
         }
 
         if length.checked_sub(consumed_bytes).is_none() {
@@ -304,7 +773,19 @@ impl<T: Read> IonParser<T> {
 
         trace!("End consuming struct");
 
-        Ok((IonValue::Struct(values), total))
+        Ok((new_struct_value(values, self.ordered_structs), total))
+    }
+
+    #[inline]
+    pub(crate) fn consume_field_name(&mut self) -> Result<(SymbolToken, usize), IonParserError> {
+        let (key, consumed_bytes) = self.parser.consume_varuint()?;
+
+        let key = self
+            .context
+            .get_symbol_token(key.try_into().map_err(|_| IonParserError::SymbolIdTooBig)?)
+            .ok_or(IonParserError::SymbolNotFoundInTable)?;
+
+        Ok((key, consumed_bytes))
     }
 
     #[inline]
@@ -315,29 +796,27 @@ impl<T: Read> IonParser<T> {
             return Ok((IonValue::Null(NullIonValue::List), 0));
         }
 
+        self.check_depth(nested_level)?;
+
         let (length, _, total) = self.consume_value_len(header)?;
         let mut consumed_bytes = 0;
         let mut values = vec![];
 
         while length - consumed_bytes > 0 {
-            let value_header = self
-                .parser
-                .consume_value_header(nested_level.saturating_add(1))?;
+            let (value, consumed) = match self.consume_element(nested_level) {
+                Ok(element) => element,
+                Err(error) => {
+                    self.push_error_path(PathComponent::Index(values.len()));
+                    return Err(error);
+                }
+            };
 
-            consumed_bytes += 1;
+            consumed_bytes += consumed;
 
-            if value_header.is_nop() {
-                let consumed = self.consume_nop(&value_header)?;
-                trace!("Found NOP Padding in List of {:} bytes", consumed + 1);
-                consumed_bytes += consumed;
-                continue;
+            if let Some(value) = value {
+                values.push(value);
             }
 
-            let value = self.consume_value_body(&value_header, nested_level.saturating_add(1))?;
-
-            consumed_bytes += value.1;
-            values.push(value.0);
-
             if consumed_bytes > length {
                 return Err(IonParserError::ListLengthWasTooShort);
             }
@@ -348,6 +827,26 @@ impl<T: Read> IonParser<T> {
         Ok((IonValue::List(values), total))
     }
 
+    /// Consumes a value inside a container, including its header. Returns
+    /// None for NOP padding.
+    #[inline]
+    fn consume_element(
+        &mut self,
+        nested_level: u64,
+    ) -> Result<(Option<IonValue>, usize), IonParserError> {
+        let value_header = self.consume_value_header(nested_level.saturating_add(1))?;
+
+        if value_header.is_nop() {
+            let consumed = self.consume_nop(&value_header)?;
+            trace!("Found NOP Padding of {:} bytes", consumed + 1);
+            return Ok((None, consumed + 1));
+        }
+
+        let value = self.consume_value_body(&value_header, nested_level.saturating_add(1))?;
+
+        Ok((Some(value.0), value.1 + 1))
+    }
+
     #[inline]
     fn consume_sexpr(&mut self, header: &ValueHeader, nested_level: u64) -> ConsumerResult {
         trace!("Consuming SExpr");
@@ -356,7 +855,7 @@ impl<T: Read> IonParser<T> {
             return Ok((IonValue::Null(NullIonValue::SExpr), 0));
         }
 
-        let list_value = self.consume_list(header, nested_level.saturating_add(1))?;
+        let list_value = self.consume_list(header, nested_level)?;
 
         if let (IonValue::List(list), len) = list_value {
             Ok((IonValue::SExpr(list), len))
@@ -381,18 +880,16 @@ impl<T: Read> IonParser<T> {
             self.parser.consume_uint(length)?
         };
 
-        let symbol = self.context.get_symbol_by_id(
-            symbol_id
-                .try_into()
-                .map_err(|_| IonParserError::SymbolIdTooBig)?,
-        );
-
-        let text = match symbol {
-            Some(Symbol::Symbol(text)) => text.clone(),
-            _ => return Err(IonParserError::SymbolNotFoundInTable),
-        };
+        let symbol = self
+            .context
+            .get_symbol_token(
+                symbol_id
+                    .try_into()
+                    .map_err(|_| IonParserError::SymbolIdTooBig)?,
+            )
+            .ok_or(IonParserError::SymbolNotFoundInTable)?;
 
-        Ok((IonValue::Symbol(text), total_consumed_bytes))
+        Ok((IonValue::Symbol(symbol), total_consumed_bytes))
     }
 
     #[inline]
@@ -403,21 +900,22 @@ impl<T: Read> IonParser<T> {
             return Ok((IonValue::Null(NullIonValue::DateTime), 0));
         }
 
-        let (length, mut consumed_bytes, _) = self.consume_value_len(header)?;
+        let (length, length_bytes, _) = self.consume_value_len(header)?;
+        let mut consumed_bytes = 0;
 
-        let (offset, consumed) = self.parser.consume_varint()?;
+        let (offset, is_offset_negative, consumed) = self.parser.consume_varint_with_sign()?;
         consumed_bytes += consumed;
         let (year, consumed) = self.parser.consume_varuint()?;
         consumed_bytes += consumed;
 
-        let year: i32 = year
+        let year: u32 = year
             .try_into()
             .map_err(|_| IonParserError::DateValueTooBig)?;
 
-        let mut components = [1u32, 1, 0, 0, 0];
+        let mut components = [year, 1, 1, 0, 0, 0];
         let mut component_counter = 0;
 
-        for component in &mut components {
+        for component in &mut components[1..] {
             if consumed_bytes >= length {
                 break;
             }
@@ -430,83 +928,54 @@ impl<T: Read> IonParser<T> {
             component_counter += 1;
         }
 
-        if component_counter == 3 {
-            return Err(IonParserError::DateWithHourButNoMinutes);
-        }
-
-        let [month, day, hour, minute, second] = components;
+        let precision = match component_counter {
+            0 => TimestampPrecision::Year,
+            1 => TimestampPrecision::Month,
+            2 => TimestampPrecision::Day,
+            3 => return Err(IonParserError::DateWithHourButNoMinutes),
+            4 => TimestampPrecision::Minute,
+            _ => TimestampPrecision::Second,
+        };
 
-        let fraction_exponent: i32 = if consumed_bytes < length {
+        let fraction_exponent: Option<i64> = if consumed_bytes < length {
             let value = self.parser.consume_varint()?;
             consumed_bytes += value.1;
-            value
-                .0
-                .try_into()
-                .map_err(|_| IonParserError::DateValueTooBig)?
+            Some(
+                value
+                    .0
+                    .try_into()
+                    .map_err(|_| IonParserError::DateValueTooBig)?,
+            )
         } else {
-            0
+            None
         };
 
-        let fraction_coefficient: i64 = if (consumed_bytes) < length {
+        let fraction_coefficient = if consumed_bytes < length {
             let remaining_bytes = length - consumed_bytes;
             let value = self.parser.consume_int(remaining_bytes)?;
             consumed_bytes += remaining_bytes;
             value
-                .try_into()
-                .map_err(|_| IonParserError::DateValueTooBig)?
         } else {
-            0
+            BigInt::zero()
         };
 
-        let second_fraction: f64 =
-            ((10f64.powi(fraction_exponent)) * fraction_coefficient as f64) * 1e9;
-
-        if second_fraction < 0.0 {
-            return Err(IonParserError::DateNegativeSecondFraction);
-        }
-
-        let second_fraction =
-            u32::try_from(second_fraction as i64).map_err(|_| IonParserError::DateValueTooBig)?;
-
-        let upper_fraction_limit = 1_000_000_000;
-        if second_fraction >= upper_fraction_limit {
-            return Err(IonParserError::DateSecondFractionOverflow);
-        }
-
-        let datetime = NaiveDate::from_ymd_opt(year, month, day)
-            .ok_or(IonParserError::InvalidDate(
-                year,
-                month,
-                day,
-                hour,
-                minute,
-                second,
-                second_fraction,
-            ))?
-            .and_hms_nano_opt(hour, minute, second, second_fraction)
-            .ok_or(IonParserError::InvalidDate(
-                year,
-                month,
-                day,
-                hour,
-                minute,
-                second,
-                second_fraction,
-            ))?;
-
-        let offset: i32 = offset
-            .try_into()
-            .map_err(|_| IonParserError::DateValueTooBig)?;
-
-        let offset = FixedOffset::east_opt(offset * 60).ok_or({
-            IonParserError::InvalidDate(year, month, day, hour, minute, second, second_fraction)
-        })?;
+        let fraction =
+            fraction_exponent.map(|exponent| BigDecimal::new(fraction_coefficient, -exponent));
 
-        let datetime = DateTime::<Utc>::from_utc(datetime, Utc);
+        // A negative zero offset is the unknown offset (-00:00).
+        let offset: Option<i32> = if offset.is_zero() && is_offset_negative {
+            None
+        } else {
+            Some(
+                offset
+                    .try_into()
+                    .map_err(|_| IonParserError::DateValueTooBig)?,
+            )
+        };
 
-        let datetime = datetime.with_timezone(&offset);
+        let timestamp = IonTimestamp::from_utc_components(precision, components, fraction, offset)?;
 
-        Ok((IonValue::DateTime(datetime), consumed_bytes))
+        Ok((IonValue::DateTime(timestamp), length_bytes + consumed_bytes))
     }
 
     #[inline]
@@ -628,9 +1097,37 @@ impl<T: Read> IonParser<T> {
     fn consume_annotation(
         &mut self,
         header: &ValueHeader,
+        nested_level: u64,
     ) -> Result<(Option<IonValue>, usize), IonParserError> {
         trace!("Consuming Annotation");
 
+        let annotation = self.consume_annotation_header(header)?;
+
+        let value = self.consume_annotated_value(&annotation, nested_level)?;
+
+        match self.get_annotation_kind(&annotation.symbols)? {
+            AnnotationKind::SharedTable => {
+                self.load_shared_table(value)?;
+                Ok((None, annotation.total_consumed_bytes))
+            }
+            AnnotationKind::LocalTable => {
+                self.load_local_table(value)?;
+                Ok((None, annotation.total_consumed_bytes))
+            }
+            AnnotationKind::User => Ok((
+                Some(self.construct_raw_annotation(&annotation.symbols, value)?),
+                annotation.total_consumed_bytes,
+            )),
+        }
+    }
+
+    /// Reads the annotation wrapper length and the annotation symbols,
+    /// leaving the reader at the start of the wrapped value.
+    #[inline]
+    pub(crate) fn consume_annotation_header(
+        &mut self,
+        header: &ValueHeader,
+    ) -> Result<AnnotationHeader, IonParserError> {
         if self.is_value_null(header) {
             return Err(IonParserError::NullAnnotationFound);
         }
@@ -665,38 +1162,52 @@ impl<T: Read> IonParser<T> {
 
         trace!("Annotations found: {:?}", symbols);
 
-        let is_shared_table_declaration =
-            self.contains_system_symbol(&symbols, SystemSymbolIds::IonSharedSymbolTable);
-
-        let is_local_table_declaration =
-            self.contains_system_symbol(&symbols, SystemSymbolIds::IonSymbolTable);
+        Ok(AnnotationHeader {
+            symbols,
+            length,
+            consumed_bytes,
+            total_consumed_bytes,
+        })
+    }
 
-        let value = self.consume_value()?;
+    /// Reads the value wrapped by the annotation, at the same depth as the
+    /// annotation wrapper.
+    #[inline]
+    pub(crate) fn consume_annotated_value(
+        &mut self,
+        annotation: &AnnotationHeader,
+        nested_level: u64,
+    ) -> Result<IonValue, IonParserError> {
+        let value = self.consume_next_value(nested_level)?;
         if let IonValue::Annotation(_, _) = value.0 {
             return Err(IonParserError::NestedAnnotations);
         }
 
-        consumed_bytes += value.1;
-        if consumed_bytes != length {
+        if annotation.consumed_bytes + value.1 != annotation.length {
             return Err(IonParserError::BadAnnotationLength);
         }
 
+        Ok(value.0)
+    }
+
+    #[inline]
+    pub(crate) fn get_annotation_kind(
+        &self,
+        symbols: &[usize],
+    ) -> Result<AnnotationKind, IonParserError> {
+        let is_shared_table_declaration =
+            self.contains_system_symbol(symbols, SystemSymbolIds::IonSharedSymbolTable);
+
+        let is_local_table_declaration =
+            self.contains_system_symbol(symbols, SystemSymbolIds::IonSymbolTable);
+
         match (is_shared_table_declaration, is_local_table_declaration) {
             (true, true) => {
                 Err(IonParserError::SharedTableAndLocalTableDeclarationIntTheSameAnnotation)
             }
-            (true, false) => {
-                self.load_shared_table(value.0)?;
-                Ok((None, total_consumed_bytes))
-            }
-            (false, true) => {
-                self.load_local_table(value.0)?;
-                Ok((None, total_consumed_bytes))
-            }
-            (false, false) => Ok((
-                Some(self.construct_raw_annotation(&symbols, value.0)?),
-                total_consumed_bytes,
-            )),
+            (true, false) => Ok(AnnotationKind::SharedTable),
+            (false, true) => Ok(AnnotationKind::LocalTable),
+            (false, false) => Ok(AnnotationKind::User),
         }
     }
 
@@ -706,7 +1217,7 @@ impl<T: Read> IonParser<T> {
     }
 
     #[inline]
-    fn consume_value_len(
+    pub(crate) fn consume_value_len(
         &mut self,
         header: &ValueHeader,
     ) -> Result<(usize, usize, usize), IonParserError> {
@@ -718,18 +1229,20 @@ impl<T: Read> IonParser<T> {
             consumed_bytes += len.1;
             usize::try_from(len.0).map_err(|_| IonParserError::ValueLenTooBig)?
         } else if header.is_len_null_value() {
-            null_length
+            return Ok((null_length, 0, null_length));
         } else {
             header.get_len().into()
         };
 
+        self.check_value_len(length)?;
+
         let total = consumed_bytes + length;
 
         Ok((length, consumed_bytes, total))
     }
 
     #[inline]
-    fn consume_value_len_for_struct(
+    pub(crate) fn consume_value_len_for_struct(
         &mut self,
         header: &ValueHeader,
     ) -> Result<(usize, usize, usize), IonParserError> {
@@ -746,187 +1259,264 @@ impl<T: Read> IonParser<T> {
             consumed_bytes += len.1;
             usize::try_from(len.0).map_err(|_| IonParserError::ValueLenTooBig)?
         } else if header.is_len_null_value() {
-            null_length
+            return Ok((null_length, 0, null_length));
         } else {
             header.get_len().into()
         };
 
+        self.check_value_len(length)?;
+
         let total = consumed_bytes + length;
 
         Ok((length, consumed_bytes, total))
     }
 
     #[inline]
-    fn get_parsed_struct_hashmap<'a>(
+    pub(crate) fn load_local_table(&mut self, table: IonValue) -> Result<(), IonParserError> {
+        load_local_table(&mut self.context, table, &self.options)
+    }
+
+    #[inline]
+    pub(crate) fn load_shared_table(&mut self, table: IonValue) -> Result<(), IonParserError> {
+        load_shared_table(&mut self.context, table, &self.options)
+    }
+
+    #[inline]
+    fn construct_raw_annotation(
         &self,
-        table: &'a IonValue,
-    ) -> Result<&'a HashMap<String, IonValue>, IonParserError> {
-        if let IonValue::Struct(table) = table {
-            Ok(table)
-        } else {
-            Err(IonParserError::LocalTableWithoutInternalStruct)
+        symbols: &[usize],
+        value: IonValue,
+    ) -> Result<IonValue, IonParserError> {
+        let mut symbols_names = Vec::new();
+
+        for symbol in symbols.iter() {
+            let name = self.get_symbol_name(*symbol)?;
+            symbols_names.push(name);
         }
+
+        Ok(IonValue::Annotation(symbols_names, Box::new(value)))
     }
 
     #[inline]
-    fn get_symbols_string(&self, table: &HashMap<String, IonValue>) -> Vec<Symbol> {
-        let symbols = table.get(self.get_symbol_name_by_type(SystemSymbolIds::Symbols));
+    fn contains_system_symbol(&self, symbols: &[usize], symbol: SystemSymbolIds) -> bool {
+        symbols.contains(&(symbol as usize))
+    }
 
-        if let Some(IonValue::List(symbols)) = symbols {
-            let mut symbols_string = Vec::new();
+    #[inline]
+    pub(crate) fn get_symbol_name(&self, symbol_id: usize) -> Result<SymbolToken, IonParserError> {
+        self.context
+            .get_symbol_token(symbol_id)
+            .ok_or(IonParserError::SymbolIdNotDefined)
+    }
+}
 
-            for symbol in symbols {
-                if let IonValue::String(text) = symbol {
-                    symbols_string.push(Symbol::Symbol(text.clone()));
-                } else {
-                    symbols_string.push(Symbol::Dummy);
-                }
-            }
+// Field names without text can't be keys of a Struct, so those structs are
+// returned as OrderedStruct even if the parser doesn't keep the order.
+pub(crate) fn new_struct_value(fields: Vec<(SymbolToken, IonValue)>, ordered: bool) -> IonValue {
+    if ordered || fields.iter().any(|(name, _)| name.is_unknown()) {
+        IonValue::OrderedStruct(fields)
+    } else {
+        IonValue::Struct(
+            fields
+                .into_iter()
+                .filter_map(|(name, value)| Some((name.into_text()?, value)))
+                .collect(),
+        )
+    }
+}
 
-            symbols_string
-        } else {
-            Vec::new()
+// Symbol tables are read the same way no matter if the parser keeps the
+// order of the struct fields or not. Fields without text are ignored.
+fn into_unordered_struct(value: IonValue) -> IonValue {
+    match value {
+        IonValue::OrderedStruct(fields) => IonValue::Struct(
+            fields
+                .into_iter()
+                .filter_map(|(name, value)| Some((name.into_text()?, into_unordered_struct(value))))
+                .collect(),
+        ),
+        IonValue::List(values) => {
+            IonValue::List(values.into_iter().map(into_unordered_struct).collect())
         }
+        value => value,
     }
+}
 
-    #[inline]
-    fn load_local_table(&mut self, table: IonValue) -> Result<(), IonParserError> {
-        trace!("Loading Local Table");
-
-        let table = self.get_parsed_struct_hashmap(&table)?;
+#[inline]
+fn get_parsed_struct_hashmap(
+    table: &IonValue,
+) -> Result<&HashMap<String, IonValue>, IonParserError> {
+    if let IonValue::Struct(table) = table {
+        Ok(table)
+    } else {
+        Err(IonParserError::LocalTableWithoutInternalStruct)
+    }
+}
 
-        let symbols = self.get_symbols_string(table);
+#[inline]
+fn get_symbols_string(table: &HashMap<String, IonValue>) -> Vec<Symbol> {
+    let symbols = table.get(get_symbol_name_by_type(SystemSymbolIds::Symbols));
 
-        let imports = table.get(self.get_symbol_name_by_type(SystemSymbolIds::Imports));
+    if let Some(IonValue::List(symbols)) = symbols {
+        let mut symbols_string = Vec::new();
 
-        let imports = match imports {
-            None => Vec::new(),
-            Some(IonValue::Symbol(symbol))
-                if symbol == self.get_symbol_name_by_type(SystemSymbolIds::IonSymbolTable) =>
-            {
-                self.context.set_new_table_from_current(symbols);
-                return Ok(());
+        for symbol in symbols {
+            if let IonValue::String(text) = symbol {
+                symbols_string.push(Symbol::Symbol(text.clone()));
+            } else {
+                symbols_string.push(Symbol::Dummy);
             }
-            Some(IonValue::List(list)) => self.decode_imports(list)?,
-            _ => return Err(IonParserError::LocalSymbolTableWithoutValidImport),
-        };
-
-        self.context
-            .set_new_table(&imports, &symbols)
-            .map_err(IonParserError::ErrorAddingCreatingLocal)?;
+        }
 
-        Ok(())
+        symbols_string
+    } else {
+        Vec::new()
     }
+}
 
-    #[inline]
-    fn decode_imports(&self, values: &[IonValue]) -> Result<Vec<Import>, IonParserError> {
-        let mut imports = Vec::new();
+#[inline]
+pub(crate) fn load_local_table(
+    context: &mut SymbolContext,
+    table: IonValue,
+    options: &IonParserOptions,
+) -> Result<(), IonParserError> {
+    trace!("Loading Local Table");
+
+    let table = into_unordered_struct(table);
+    let table = get_parsed_struct_hashmap(&table)?;
+
+    let symbols = get_symbols_string(table);
+
+    let imports = table.get(get_symbol_name_by_type(SystemSymbolIds::Imports));
+
+    let imports = match imports {
+        None => Vec::new(),
+        Some(IonValue::Symbol(symbol))
+            if symbol == get_symbol_name_by_type(SystemSymbolIds::IonSymbolTable) =>
+        {
+            check_symbols_len(context.get_symbols_len() + symbols.len(), options)?;
+            context.set_new_table_from_current(symbols);
+            return Ok(());
+        }
+        Some(IonValue::List(list)) => decode_imports(list)?,
+        _ => return Err(IonParserError::LocalSymbolTableWithoutValidImport),
+    };
 
-        for value in values {
-            let value = match value {
-                IonValue::Struct(value) => value,
-                _ => continue,
-            };
+    if matches!(options.max_imports, Some(max) if imports.len() > max) {
+        return Err(IonParserError::MaxImportsExceeded);
+    }
 
-            let name = match value.get(self.get_symbol_name_by_type(SystemSymbolIds::Name)) {
-                Some(IonValue::String(name)) => name.clone(),
-                _ => continue,
-            };
+    // Imports of tables that are not available are filled with symbols
+    // without text up to their max_id, so they are checked before creating
+    // the table.
+    let declared_len = imports
+        .iter()
+        .filter_map(|import| import.max_len)
+        .fold(symbols.len(), usize::saturating_add);
 
-            let version: u32 =
-                match value.get(self.get_symbol_name_by_type(SystemSymbolIds::Version)) {
-                    Some(IonValue::Integer(version)) => (*version)
-                        .try_into()
-                        .map_err(|_| IonParserError::TableVersionTooBig)?,
-                    _ => 1,
-                };
+    check_symbols_len(declared_len, options)?;
 
-            let max_len: Option<usize> =
-                match value.get(self.get_symbol_name_by_type(SystemSymbolIds::MaxId)) {
-                    Some(IonValue::Integer(version)) => Some(
-                        (*version)
-                            .try_into()
-                            .map_err(|_| IonParserError::TableVersionTooBig)?,
-                    ),
-                    _ => None,
-                };
+    context
+        .set_new_table(&imports, &symbols)
+        .map_err(IonParserError::ErrorAddingCreatingLocal)?;
 
-            imports.push(Import {
-                name,
-                version: Some(version),
-                max_len,
-            })
-        }
+    check_symbols_len(context.get_symbols_len(), options)
+}
 
-        Ok(imports)
+#[inline]
+fn check_symbols_len(len: usize, options: &IonParserOptions) -> Result<(), IonParserError> {
+    if matches!(options.max_symbols, Some(max) if len > max) {
+        return Err(IonParserError::MaxSymbolsExceeded);
     }
 
-    #[inline]
-    fn load_shared_table(&mut self, table: IonValue) -> Result<(), IonParserError> {
-        trace!("Loading Shared Table");
+    Ok(())
+}
 
-        let table = self.get_parsed_struct_hashmap(&table)?;
+#[inline]
+fn decode_imports(values: &[IonValue]) -> Result<Vec<Import>, IonParserError> {
+    let mut imports = Vec::new();
 
-        let name = table
-            .get(self.get_symbol_name_by_type(SystemSymbolIds::Name))
-            .ok_or(IonParserError::SharedTableDefinitionWithoutName)?;
+    for value in values {
+        let value = match value {
+            IonValue::Struct(value) => value,
+            _ => continue,
+        };
 
-        let version = table.get(self.get_symbol_name_by_type(SystemSymbolIds::Version));
+        let name = match value.get(get_symbol_name_by_type(SystemSymbolIds::Name)) {
+            Some(IonValue::String(name)) => name.clone(),
+            _ => continue,
+        };
 
-        let version: u32 = if let Some(IonValue::Integer(version)) = version {
-            (*version)
+        let version: u32 = match value.get(get_symbol_name_by_type(SystemSymbolIds::Version)) {
+            Some(IonValue::Integer(version)) => (*version)
                 .try_into()
-                .map_err(|_| IonParserError::TableVersionTooBig)?
-        } else {
-            1
+                .map_err(|_| IonParserError::TableVersionTooBig)?,
+            _ => 1,
         };
 
-        let name: String = if let IonValue::String(name) = name {
-            name.clone()
-        } else {
-            return Err(IonParserError::SharedTableDefinitionWithoutName);
-        };
+        let max_len: Option<usize> =
+            match value.get(get_symbol_name_by_type(SystemSymbolIds::MaxId)) {
+                Some(IonValue::Integer(version)) => Some(
+                    (*version)
+                        .try_into()
+                        .map_err(|_| IonParserError::TableVersionTooBig)?,
+                ),
+                _ => None,
+            };
 
-        let symbols = self.get_symbols_string(table);
+        imports.push(Import {
+            name,
+            version: Some(version),
+            max_len,
+        })
+    }
 
-        self.context
-            .add_shared_table(name, version, &symbols)
-            .map_err(IonParserError::ErrorAddingSharedTableToContext)?;
+    Ok(imports)
+}
 
-        Ok(())
-    }
+#[inline]
+pub(crate) fn load_shared_table(
+    context: &mut SymbolContext,
+    table: IonValue,
+    options: &IonParserOptions,
+) -> Result<(), IonParserError> {
+    trace!("Loading Shared Table");
 
-    #[inline]
-    fn get_symbol_name_by_type(&self, symbol: SystemSymbolIds) -> &'static str {
-        SYSTEM_SYMBOL_TABLE[symbol as usize]
-    }
+    let table = into_unordered_struct(table);
+    let table = get_parsed_struct_hashmap(&table)?;
 
-    #[inline]
-    fn construct_raw_annotation(
-        &self,
-        symbols: &[usize],
-        value: IonValue,
-    ) -> Result<IonValue, IonParserError> {
-        let mut symbols_names = Vec::new();
+    let name = table
+        .get(get_symbol_name_by_type(SystemSymbolIds::Name))
+        .ok_or(IonParserError::SharedTableDefinitionWithoutName)?;
 
-        for symbol in symbols.iter() {
-            let name = self.get_symbol_name(*symbol)?;
-            symbols_names.push(name);
-        }
+    let version = table.get(get_symbol_name_by_type(SystemSymbolIds::Version));
 
-        Ok(IonValue::Annotation(symbols_names, Box::new(value)))
-    }
+    let version: u32 = if let Some(IonValue::Integer(version)) = version {
+        (*version)
+            .try_into()
+            .map_err(|_| IonParserError::TableVersionTooBig)?
+    } else {
+        1
+    };
 
-    #[inline]
-    fn contains_system_symbol(&self, symbols: &[usize], symbol: SystemSymbolIds) -> bool {
-        symbols.iter().any(|&s| s == symbol as usize)
-    }
+    let name: String = if let IonValue::String(name) = name {
+        name.clone()
+    } else {
+        return Err(IonParserError::SharedTableDefinitionWithoutName);
+    };
 
-    #[inline]
-    fn get_symbol_name(&self, symbol_id: usize) -> Result<String, IonParserError> {
-        match self.context.get_symbol_by_id(symbol_id) {
-            Some(Symbol::Symbol(name)) => Ok(name.clone()),
-            Some(Symbol::Dummy) | None => Err(IonParserError::SymbolIdNotDefined),
-        }
-    }
+    let symbols = get_symbols_string(table);
+
+    check_symbols_len(symbols.len(), options)?;
+
+    context
+        .add_shared_table(name, version, &symbols)
+        .map_err(IonParserError::ErrorAddingSharedTableToContext)?;
+
+    Ok(())
+}
+
+#[inline]
+fn get_symbol_name_by_type(symbol: SystemSymbolIds) -> &'static str {
+    SYSTEM_SYMBOL_TABLE[symbol as usize]
 }
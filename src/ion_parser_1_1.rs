@@ -0,0 +1,781 @@
+use crate::binary_parser_types::SYSTEM_SYMBOL_TABLE_1_1;
+use crate::ion_parser::{new_struct_value, ConsumerResult, IonParser};
+use crate::ion_parser_types::{IonParserError, IonValue, NullIonValue, PathComponent};
+use crate::ion_timestamp::{IonTimestamp, TimestampPrecision};
+use crate::symbol_token::SymbolToken;
+use bigdecimal::BigDecimal;
+use log::trace;
+use num_bigint::{BigInt, BigUint};
+use std::convert::{TryFrom, TryInto};
+use std::io::Read;
+
+// Types of the typed nulls (0xEB), in the order of the byte following the
+// opcode.
+const NULL_TYPES: [NullIonValue; 12] = [
+    NullIonValue::Bool,
+    NullIonValue::Integer,
+    NullIonValue::Float,
+    NullIonValue::Decimal,
+    NullIonValue::DateTime,
+    NullIonValue::String,
+    NullIonValue::Symbol,
+    NullIonValue::Blob,
+    NullIonValue::Clob,
+    NullIonValue::List,
+    NullIonValue::SExpr,
+    NullIonValue::Struct,
+];
+
+// Lengths of the short timestamps, from 0x80 to 0x8C.
+const SHORT_TIMESTAMP_LENGTHS: [usize; 13] = [1, 2, 2, 4, 5, 6, 7, 8, 5, 5, 7, 8, 9];
+
+const DELIMITED_END: u8 = 0xF0;
+
+// A FlexSym is either a symbol or, after a zero, the end of a delimited
+// struct.
+enum FlexSym {
+    Symbol(SymbolToken),
+    End,
+}
+
+// Ion 1.1 binary decoding. Values are decoded into the same `IonValue` model
+// as Ion 1.0 ones, and symbol addresses are resolved in the same symbol
+// tables, so a stream can declare them with `$ion_symbol_table` structs as
+// in Ion 1.0. Macros are not supported, so e-expressions and encoding
+// directives return an error.
+impl<T: Read> IonParser<T> {
+    /// Consumes a top-level Ion 1.1 value whose opcode has been read. As
+    /// with Ion 1.0 values, NOP Padding, Shared Tables and Local Tables are
+    /// processed, continuing with the next value.
+    pub(crate) fn consume_top_level_value_1_1(&mut self, opcode: u8) -> ConsumerResult {
+        let already_consumed_opcode = 1;
+        let start = self.position() - already_consumed_opcode;
+
+        let value =
+            match self.consume_element_1_1(opcode, 0)? {
+                None => None,
+                Some(IonValue::Annotation(annotations, value)) => {
+                    let has_annotation = |text| {
+                        annotations
+                            .iter()
+                            .any(|annotation| annotation.text() == Some(text))
+                    };
+
+                    match (
+                        has_annotation("$ion_shared_symbol_table"),
+                        has_annotation("$ion_symbol_table"),
+                    ) {
+                        (true, true) => return Err(
+                            IonParserError::SharedTableAndLocalTableDeclarationIntTheSameAnnotation,
+                        ),
+                        (true, false) => {
+                            self.load_shared_table(*value)?;
+                            None
+                        }
+                        (false, true) => {
+                            self.load_local_table(*value)?;
+                            None
+                        }
+                        (false, false) if has_annotation("$ion_encoding") => {
+                            return Err(IonParserError::UnsupportedEncodingDirective)
+                        }
+                        (false, false) => Some(IonValue::Annotation(annotations, value)),
+                    }
+                }
+                value => value,
+            };
+
+        let value = match value {
+            Some(value) => value,
+            None => self.consume_next_value(0)?.0,
+        };
+
+        Ok((value, self.position() - start))
+    }
+
+    /// Consumes the value starting with the given opcode. Returns None for
+    /// NOP Padding.
+    fn consume_element_1_1(
+        &mut self,
+        opcode: u8,
+        nested_level: u64,
+    ) -> Result<Option<IonValue>, IonParserError> {
+        trace!("Consuming Ion 1.1 opcode {:#04x}", opcode);
+
+        let value = match opcode {
+            0x00..=0x5F | 0xEF | 0xF4 | 0xF5 => return Err(self.consume_macro_invocation(opcode)?),
+            0x60..=0x68 => self.consume_int_1_1(usize::from(opcode - 0x60))?,
+            0x6A => IonValue::Float(0f64),
+            0x6B..=0x6D => self.consume_float_1_1(opcode)?,
+            0x6E => IonValue::Bool(true),
+            0x6F => IonValue::Bool(false),
+            0x70..=0x7F => self.consume_decimal_1_1(usize::from(opcode - 0x70))?,
+            0x80..=0x8C => self.consume_short_timestamp_1_1(opcode)?,
+            0x90..=0x9F => self.consume_string_1_1(usize::from(opcode - 0x90))?,
+            0xA0..=0xAF => self.consume_inline_symbol_1_1(usize::from(opcode - 0xA0))?,
+            0xB0..=0xBF => {
+                let length = usize::from(opcode - 0xB0);
+                IonValue::List(self.consume_sequence_1_1(Some(length), nested_level)?)
+            }
+            0xC0..=0xCF => {
+                let length = usize::from(opcode - 0xC0);
+                IonValue::SExpr(self.consume_sequence_1_1(Some(length), nested_level)?)
+            }
+            0xD0 | 0xD2..=0xDF => {
+                let length = usize::from(opcode - 0xD0);
+                self.consume_struct_1_1(Some(length), nested_level)?
+            }
+            0xE1..=0xE3 => self.consume_symbol_address_1_1(opcode)?,
+            0xE4..=0xE9 => self.consume_annotation_1_1(opcode, nested_level)?,
+            0xEA => IonValue::Null(NullIonValue::Null),
+            0xEB => self.consume_typed_null_1_1()?,
+            0xEC => return Ok(None),
+            0xED => {
+                let length = self.consume_len_1_1()?;
+                trace!("Nop Padding with length {}", length);
+                self.skip_bytes(length)?;
+                return Ok(None);
+            }
+            0xEE => {
+                let address = self.consume_fixed_len_1_1(1)?;
+                IonValue::Symbol(get_system_symbol(address)?)
+            }
+            0xF1 => IonValue::List(self.consume_sequence_1_1(None, nested_level)?),
+            0xF2 => IonValue::SExpr(self.consume_sequence_1_1(None, nested_level)?),
+            0xF3 => self.consume_struct_1_1(None, nested_level)?,
+            0xF6 => {
+                let length = self.consume_len_1_1()?;
+                self.consume_int_1_1(length)?
+            }
+            0xF7 => {
+                let length = self.consume_len_1_1()?;
+                self.consume_decimal_1_1(length)?
+            }
+            0xF8 => {
+                let length = self.consume_len_1_1()?;
+                self.consume_long_timestamp_1_1(length)?
+            }
+            0xF9 => {
+                let length = self.consume_len_1_1()?;
+                self.consume_string_1_1(length)?
+            }
+            0xFA => {
+                let length = self.consume_len_1_1()?;
+                self.consume_inline_symbol_1_1(length)?
+            }
+            0xFB => {
+                let length = self.consume_len_1_1()?;
+                IonValue::List(self.consume_sequence_1_1(Some(length), nested_level)?)
+            }
+            0xFC => {
+                let length = self.consume_len_1_1()?;
+                IonValue::SExpr(self.consume_sequence_1_1(Some(length), nested_level)?)
+            }
+            0xFD => {
+                let length = self.consume_len_1_1()?;
+                self.consume_struct_1_1(Some(length), nested_level)?
+            }
+            0xFE => {
+                let length = self.consume_len_1_1()?;
+                IonValue::Blob(self.consume_bytes_1_1(length)?)
+            }
+            0xFF => {
+                let length = self.consume_len_1_1()?;
+                IonValue::Clob(self.consume_bytes_1_1(length)?)
+            }
+            _ => return Err(IonParserError::InvalidOpcode(opcode)),
+        };
+
+        Ok(Some(value))
+    }
+
+    // Reads the address of the macro invoked by an e-expression in order to
+    // return it in the error.
+    fn consume_macro_invocation(&mut self, opcode: u8) -> Result<IonParserError, IonParserError> {
+        let low_bits = usize::from(opcode & 0b0000_1111);
+
+        let address = match opcode {
+            0x00..=0x3F => usize::from(opcode),
+            0x40..=0x4F => 64 + (self.consume_fixed_len_1_1(1)? << 4 | low_bits),
+            0x50..=0x5F => 4160 + (self.consume_fixed_len_1_1(2)? << 4 | low_bits),
+            0xEF => {
+                let address = self.consume_fixed_len_1_1(1)?;
+                return Ok(IonParserError::UnsupportedSystemMacro(address));
+            }
+            _ => self.consume_flex_len_1_1()?,
+        };
+
+        Ok(IonParserError::UnsupportedMacro(address))
+    }
+
+    fn consume_int_1_1(&mut self, length: usize) -> Result<IonValue, IonParserError> {
+        self.check_value_len(length)?;
+
+        let value = self.parser.consume_fixed_int(length)?;
+
+        Ok(match i64::try_from(&value) {
+            Ok(value) => IonValue::Integer(value),
+            Err(_) => IonValue::BigInteger(value),
+        })
+    }
+
+    fn consume_float_1_1(&mut self, opcode: u8) -> Result<IonValue, IonParserError> {
+        let value = match opcode {
+            0x6B => {
+                let mut buffer = [0u8; 2];
+                self.parser.read_bytes(&mut buffer)?;
+                half_to_f64(u16::from_le_bytes(buffer))
+            }
+            0x6C => {
+                let mut buffer = [0u8; 4];
+                self.parser.read_bytes(&mut buffer)?;
+                f32::from_le_bytes(buffer).into()
+            }
+            _ => {
+                let mut buffer = [0u8; 8];
+                self.parser.read_bytes(&mut buffer)?;
+                f64::from_le_bytes(buffer)
+            }
+        };
+
+        Ok(IonValue::Float(value))
+    }
+
+    //            +=========================+
+    // Decimal    :   exponent (FlexInt)    :
+    //            +-------------------------+
+    //            : coefficient (FixedInt)  :
+    //            +=========================+
+    fn consume_decimal_1_1(&mut self, length: usize) -> Result<IonValue, IonParserError> {
+        self.check_value_len(length)?;
+
+        if length == 0 {
+            return Ok(IonValue::Decimal(BigDecimal::from(0u8)));
+        }
+
+        let (exponent, consumed_bytes) = self.parser.consume_flex_int()?;
+
+        let coefficient_size = length
+            .checked_sub(consumed_bytes)
+            .ok_or(IonParserError::DecimalExponentTooBig)?;
+
+        let coefficient = self.parser.consume_fixed_int(coefficient_size)?;
+
+        let exponent: i64 = exponent
+            .try_into()
+            .map_err(|_| IonParserError::DecimalExponentTooBig)?;
+
+        Ok(IonValue::Decimal(BigDecimal::new(coefficient, -exponent)))
+    }
+
+    // Short timestamps pack their components in a little-endian FixedUInt,
+    // starting by the least significant bits:
+    //
+    // 0x80       year (7 bits, since 1970)
+    // 0x81       year, month (4 bits)
+    // 0x82       year, month, day (5 bits)
+    // 0x83-0x87  year, month, day, hour (5 bits), minute (6 bits), UTC flag
+    //            (1 bit), second (6 bits, from 0x84), fraction (from 0x85)
+    // 0x88-0x8C  year, month, day, hour, minute, offset (7 bits, in quarters
+    //            of an hour biased by 56), second (6 bits), fraction (from
+    //            0x8A)
+    //
+    // The fraction is 10 bits of milliseconds, 20 bits of microseconds or
+    // 30 bits of nanoseconds.
+    fn consume_short_timestamp_1_1(&mut self, opcode: u8) -> Result<IonValue, IonParserError> {
+        let length = SHORT_TIMESTAMP_LENGTHS[usize::from(opcode - 0x80)];
+
+        let bits: u128 = self
+            .parser
+            .consume_fixed_uint(length)?
+            .try_into()
+            .map_err(|_| IonParserError::DateValueTooBig)?;
+
+        let field = |start: u32, length: u32| ((bits >> start) & ((1 << length) - 1)) as u32;
+
+        let year = 1970 + field(0, 7);
+
+        let (precision, components) = match opcode {
+            0x80 => (TimestampPrecision::Year, [year, 1, 1, 0, 0, 0]),
+            0x81 => (TimestampPrecision::Month, [year, field(7, 4), 1, 0, 0, 0]),
+            0x82 => (
+                TimestampPrecision::Day,
+                [year, field(7, 4), field(11, 5), 0, 0, 0],
+            ),
+            _ => {
+                let has_offset = opcode >= 0x88;
+                let second_start = if has_offset { 34 } else { 28 };
+
+                let precision = match opcode {
+                    0x83 | 0x88 => TimestampPrecision::Minute,
+                    _ => TimestampPrecision::Second,
+                };
+
+                let second = match precision {
+                    TimestampPrecision::Minute => 0,
+                    _ => field(second_start, 6),
+                };
+
+                (
+                    precision,
+                    [
+                        year,
+                        field(7, 4),
+                        field(11, 5),
+                        field(16, 5),
+                        field(21, 6),
+                        second,
+                    ],
+                )
+            }
+        };
+
+        let offset = match opcode {
+            0x80..=0x82 => None,
+            0x83..=0x87 if field(27, 1) == 1 => Some(0),
+            0x83..=0x87 => None,
+            _ => Some((field(27, 7) as i32 - 56) * 15),
+        };
+
+        let fraction = match opcode {
+            0x85 => Some(BigDecimal::new(field(34, 10).into(), 3)),
+            0x86 => Some(BigDecimal::new(field(34, 20).into(), 6)),
+            0x87 => Some(BigDecimal::new(field(34, 30).into(), 9)),
+            0x8A => Some(BigDecimal::new(field(40, 10).into(), 3)),
+            0x8B => Some(BigDecimal::new(field(40, 20).into(), 6)),
+            0x8C => Some(BigDecimal::new(field(40, 30).into(), 9)),
+            _ => None,
+        };
+
+        let timestamp = IonTimestamp::from_utc_components(precision, components, fraction, offset)?;
+
+        Ok(IonValue::DateTime(timestamp))
+    }
+
+    // Long timestamps pack their components in a little-endian FixedUInt,
+    // with a length depending on the precision:
+    //
+    // 2 bytes   year (14 bits)
+    // 3 bytes   year, month (4 bits), day (5 bits, 0 for month precision)
+    // 6 bytes   year, month, day, hour (5 bits), minute (6 bits), offset
+    //           (12 bits, in minutes biased by 1440, all ones if unknown)
+    // 7 bytes   year, month, day, hour, minute, offset, second (6 bits)
+    //
+    // Longer timestamps are followed by the fraction of the second, as a
+    // FlexUInt scale and a FixedUInt coefficient.
+    fn consume_long_timestamp_1_1(&mut self, length: usize) -> Result<IonValue, IonParserError> {
+        self.check_value_len(length)?;
+
+        if !matches!(length, 2 | 3 | 6..) {
+            return Err(IonParserError::InvalidTimestampLength(length));
+        }
+
+        let bits: u64 = self
+            .parser
+            .consume_fixed_uint(length.min(7))?
+            .try_into()
+            .map_err(|_| IonParserError::DateValueTooBig)?;
+
+        let field = |start: u32, length: u32| ((bits >> start) & ((1 << length) - 1)) as u32;
+
+        let year = field(0, 14);
+
+        let (precision, components) = match length {
+            2 => (TimestampPrecision::Year, [year, 1, 1, 0, 0, 0]),
+            3 if field(18, 5) == 0 => (TimestampPrecision::Month, [year, field(14, 4), 1, 0, 0, 0]),
+            3 => (
+                TimestampPrecision::Day,
+                [year, field(14, 4), field(18, 5), 0, 0, 0],
+            ),
+            _ => {
+                let (precision, second) = match length {
+                    6 => (TimestampPrecision::Minute, 0),
+                    _ => (TimestampPrecision::Second, field(46, 6)),
+                };
+
+                (
+                    precision,
+                    [
+                        year,
+                        field(14, 4),
+                        field(18, 5),
+                        field(23, 5),
+                        field(28, 6),
+                        second,
+                    ],
+                )
+            }
+        };
+
+        let offset = match (length, field(34, 12)) {
+            (2 | 3, _) | (_, 0b1111_1111_1111) => None,
+            (_, offset) => Some(offset as i32 - 1440),
+        };
+
+        let fraction = if length > 7 {
+            let (scale, consumed_bytes) = self.parser.consume_flex_uint()?;
+
+            let coefficient_size = (length - 7)
+                .checked_sub(consumed_bytes)
+                .ok_or(IonParserError::InvalidTimestampLength(length))?;
+
+            let coefficient = self.parser.consume_fixed_uint(coefficient_size)?;
+
+            let scale: i64 = scale
+                .try_into()
+                .map_err(|_| IonParserError::DateValueTooBig)?;
+
+            Some(BigDecimal::new(coefficient.into(), scale))
+        } else {
+            None
+        };
+
+        let timestamp = IonTimestamp::from_utc_components(precision, components, fraction, offset)?;
+
+        Ok(IonValue::DateTime(timestamp))
+    }
+
+    fn consume_string_1_1(&mut self, length: usize) -> Result<IonValue, IonParserError> {
+        let text = self.consume_text_1_1(length)?;
+
+        Ok(IonValue::String(text))
+    }
+
+    fn consume_inline_symbol_1_1(&mut self, length: usize) -> Result<IonValue, IonParserError> {
+        let text = self.consume_text_1_1(length)?;
+
+        Ok(IonValue::Symbol(SymbolToken::new(text)))
+    }
+
+    fn consume_text_1_1(&mut self, length: usize) -> Result<String, IonParserError> {
+        let buffer = self.consume_bytes_1_1(length)?;
+
+        String::from_utf8(buffer).map_err(|_| IonParserError::NonUtf8String)
+    }
+
+    fn consume_bytes_1_1(&mut self, length: usize) -> Result<Vec<u8>, IonParserError> {
+        self.check_value_len(length)?;
+
+        let mut buffer = vec![0; length];
+
+        if length > 0 {
+            self.parser.read_bytes(&mut buffer)?;
+        }
+
+        Ok(buffer)
+    }
+
+    // 0xE1 and 0xE2 are followed by a FixedUInt of 1 and 2 bytes, 0xE3 by a
+    // FlexUInt. Each of them starts where the previous one ends.
+    fn consume_symbol_address_1_1(&mut self, opcode: u8) -> Result<IonValue, IonParserError> {
+        let address = match opcode {
+            0xE1 => self.consume_fixed_len_1_1(1)?,
+            0xE2 => 256 + self.consume_fixed_len_1_1(2)?,
+            _ => 65792 + self.consume_flex_len_1_1()?,
+        };
+
+        let symbol = self
+            .context
+            .get_symbol_token(address)
+            .ok_or(IonParserError::SymbolNotFoundInTable)?;
+
+        Ok(IonValue::Symbol(symbol))
+    }
+
+    fn consume_typed_null_1_1(&mut self) -> Result<IonValue, IonParserError> {
+        let mut null_type = [0u8; 1];
+        self.parser.read_bytes(&mut null_type)?;
+
+        match NULL_TYPES.get(usize::from(null_type[0])) {
+            Some(null) => Ok(IonValue::Null(null.clone())),
+            None => Err(IonParserError::InvalidNullType(null_type[0])),
+        }
+    }
+
+    // Annotations are not a wrapper with a length as in Ion 1.0, the
+    // annotated value just follows them:
+    //
+    // 0xE4, 0xE5  one or two FlexUInt symbol addresses
+    // 0xE6        FlexUInt length followed by FlexUInt symbol addresses
+    // 0xE7, 0xE8  one or two FlexSyms
+    // 0xE9        FlexUInt length followed by FlexSyms
+    fn consume_annotation_1_1(
+        &mut self,
+        opcode: u8,
+        nested_level: u64,
+    ) -> Result<IonValue, IonParserError> {
+        let with_flex_syms = opcode >= 0xE7;
+
+        let mut annotations = vec![];
+
+        match opcode {
+            0xE6 | 0xE9 => {
+                let length = self.consume_len_1_1()?;
+                let end = self.position() + length;
+
+                while self.position() < end {
+                    annotations.push(self.consume_annotation_symbol_1_1(with_flex_syms)?);
+                }
+
+                if self.position() != end {
+                    return Err(IonParserError::BadAnnotationLength);
+                }
+            }
+            _ => {
+                let count = if opcode == 0xE4 || opcode == 0xE7 {
+                    1
+                } else {
+                    2
+                };
+
+                for _ in 0..count {
+                    annotations.push(self.consume_annotation_symbol_1_1(with_flex_syms)?);
+                }
+            }
+        }
+
+        if annotations.is_empty() {
+            return Err(IonParserError::NullAnnotationFound);
+        }
+
+        // A version marker cannot follow the annotations
+        let opcode = self.consume_opcode(nested_level.saturating_add(1))?;
+
+        if (0xE4..=0xE9).contains(&opcode) {
+            return Err(IonParserError::NestedAnnotations);
+        }
+
+        match self.consume_element_1_1(opcode, nested_level)? {
+            Some(value) => Ok(IonValue::Annotation(annotations, Box::new(value))),
+            None => Err(IonParserError::InvalidOpcode(opcode)),
+        }
+    }
+
+    fn consume_annotation_symbol_1_1(
+        &mut self,
+        with_flex_syms: bool,
+    ) -> Result<SymbolToken, IonParserError> {
+        if !with_flex_syms {
+            let address = self.consume_flex_len_1_1()?;
+            return self.get_symbol_name(address);
+        }
+
+        match self.consume_flex_sym_1_1()? {
+            FlexSym::Symbol(symbol) => Ok(symbol),
+            FlexSym::End => Err(IonParserError::InvalidOpcode(DELIMITED_END)),
+        }
+    }
+
+    //            +=========================+
+    // FlexSym    :         FlexInt         :
+    //            +=========================+
+    //
+    // A positive FlexInt is a symbol address and a negative one is the
+    // length of the text that follows. A zero is followed by a byte with
+    // $0 (0x60), a system symbol (0x61 to 0x9F), the empty text (0xA0) or
+    // the end of a delimited struct (0xF0).
+    fn consume_flex_sym_1_1(&mut self) -> Result<FlexSym, IonParserError> {
+        let (value, _) = self.parser.consume_flex_int()?;
+
+        if value > BigInt::from(0u8) {
+            let address = value
+                .try_into()
+                .map_err(|_| IonParserError::SymbolIdTooBig)?;
+
+            return Ok(FlexSym::Symbol(self.get_symbol_name(address)?));
+        }
+
+        if value < BigInt::from(0u8) {
+            let length = usize::try_from(-value).map_err(|_| IonParserError::ValueLenTooBig)?;
+            let text = self.consume_text_1_1(length)?;
+
+            return Ok(FlexSym::Symbol(SymbolToken::new(text)));
+        }
+
+        let mut escape = [0u8; 1];
+        self.parser.read_bytes(&mut escape)?;
+
+        match escape[0] {
+            0x60 => Ok(FlexSym::Symbol(SymbolToken::unknown())),
+            0x61..=0x9F => Ok(FlexSym::Symbol(get_system_symbol(usize::from(
+                escape[0] - 0x60,
+            ))?)),
+            0xA0 => Ok(FlexSym::Symbol(SymbolToken::new(""))),
+            DELIMITED_END => Ok(FlexSym::End),
+            byte => Err(IonParserError::InvalidOpcode(byte)),
+        }
+    }
+
+    /// Consumes the values of a list or a sexp, either until `length` bytes
+    /// have been read or, for delimited ones, until the end opcode.
+    fn consume_sequence_1_1(
+        &mut self,
+        length: Option<usize>,
+        nested_level: u64,
+    ) -> Result<Vec<IonValue>, IonParserError> {
+        self.check_depth(nested_level)?;
+
+        let end = match length {
+            Some(length) => {
+                self.check_value_len(length)?;
+                Some(self.position() + length)
+            }
+            None => None,
+        };
+
+        let mut values = vec![];
+
+        loop {
+            if let Some(end) = end {
+                if self.position() >= end {
+                    break;
+                }
+            }
+
+            let opcode = self.consume_opcode(nested_level.saturating_add(1))?;
+
+            if opcode == DELIMITED_END && end.is_none() {
+                break;
+            }
+
+            match self.consume_element_1_1(opcode, nested_level.saturating_add(1)) {
+                Ok(Some(value)) => values.push(value),
+                Ok(None) => {}
+                Err(error) => {
+                    self.push_error_path(PathComponent::Index(values.len()));
+                    return Err(error);
+                }
+            }
+        }
+
+        if matches!(end, Some(end) if self.position() > end) {
+            return Err(IonParserError::ListLengthWasTooShort);
+        }
+
+        Ok(values)
+    }
+
+    /// Consumes the fields of a struct, either until `length` bytes have
+    /// been read or, for delimited ones, until the end FlexSym.
+    ///
+    /// Field names of structs with a length are symbol addresses, until a
+    /// zero switches them to FlexSyms. Delimited structs always use FlexSyms.
+    fn consume_struct_1_1(
+        &mut self,
+        length: Option<usize>,
+        nested_level: u64,
+    ) -> Result<IonValue, IonParserError> {
+        self.check_depth(nested_level)?;
+
+        let end = match length {
+            Some(length) => {
+                self.check_value_len(length)?;
+                Some(self.position() + length)
+            }
+            None => None,
+        };
+
+        let mut with_flex_syms = end.is_none();
+        let mut fields: Vec<(SymbolToken, IonValue)> = vec![];
+
+        loop {
+            if let Some(end) = end {
+                if self.position() >= end {
+                    break;
+                }
+            }
+
+            let key = if with_flex_syms {
+                match self.consume_flex_sym_1_1()? {
+                    FlexSym::Symbol(symbol) => symbol,
+                    FlexSym::End if end.is_none() => break,
+                    FlexSym::End => return Err(IonParserError::InvalidOpcode(DELIMITED_END)),
+                }
+            } else {
+                match self.consume_flex_len_1_1()? {
+                    0 => {
+                        with_flex_syms = true;
+                        continue;
+                    }
+                    address => self.get_symbol_name(address)?,
+                }
+            };
+
+            trace!("Struct key field: {:?}", key);
+
+            let value = match self.consume_field_value_1_1(nested_level) {
+                Ok(value) => value,
+                Err(error) => {
+                    self.push_error_path(PathComponent::field(&key));
+                    return Err(error);
+                }
+            };
+
+            if let Some(value) = value {
+                fields.push((key, value));
+            }
+        }
+
+        if matches!(end, Some(end) if self.position() > end) {
+            return Err(IonParserError::ListLengthWasTooShort);
+        }
+
+        Ok(new_struct_value(fields, self.ordered_structs))
+    }
+
+    fn consume_field_value_1_1(
+        &mut self,
+        nested_level: u64,
+    ) -> Result<Option<IonValue>, IonParserError> {
+        let opcode = self.consume_opcode(nested_level.saturating_add(1))?;
+
+        self.consume_element_1_1(opcode, nested_level.saturating_add(1))
+    }
+
+    /// Reads the FlexUInt length of a value and checks it.
+    fn consume_len_1_1(&mut self) -> Result<usize, IonParserError> {
+        let length = self.consume_flex_len_1_1()?;
+
+        self.check_value_len(length)?;
+
+        Ok(length)
+    }
+
+    fn consume_flex_len_1_1(&mut self) -> Result<usize, IonParserError> {
+        let (value, _) = self.parser.consume_flex_uint()?;
+
+        usize::try_from(value).map_err(|_| IonParserError::ValueLenTooBig)
+    }
+
+    fn consume_fixed_len_1_1(&mut self, octets: usize) -> Result<usize, IonParserError> {
+        let value: BigUint = self.parser.consume_fixed_uint(octets)?;
+
+        usize::try_from(value).map_err(|_| IonParserError::ValueLenTooBig)
+    }
+}
+
+fn get_system_symbol(address: usize) -> Result<SymbolToken, IonParserError> {
+    match address {
+        0 => Ok(SymbolToken::unknown()),
+        _ => SYSTEM_SYMBOL_TABLE_1_1
+            .get(address)
+            .map(|text| SymbolToken::new(*text))
+            .ok_or(IonParserError::SymbolNotFoundInTable),
+    }
+}
+
+// IEEE 754 half precision: 1 sign bit, 5 exponent bits and 10 fraction bits.
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1f64 } else { 1f64 };
+    let exponent = i32::from((bits >> 10) & 0b1_1111);
+    let fraction = f64::from(bits & 0b11_1111_1111);
+
+    let value = match exponent {
+        0 => fraction * 2f64.powi(-24),
+        0b1_1111 if fraction == 0f64 => f64::INFINITY,
+        0b1_1111 => f64::NAN,
+        _ => (1f64 + fraction / 1024f64) * 2f64.powi(exponent - 15),
+    };
+
+    sign * value
+}
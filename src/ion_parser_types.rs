@@ -1,7 +1,9 @@
 use crate::binary_parser_types::*;
+use crate::ion_timestamp::IonTimestamp;
 use crate::symbol_table::SymbolContextError;
+use crate::symbol_token::SymbolToken;
+use crate::text_parser_types::TextParsingError;
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, FixedOffset};
 use num_bigint::BigInt;
 use std::collections::HashMap;
 use std::error::Error;
@@ -9,6 +11,8 @@ use thiserror::Error;
 
 /// Indicated a problem in the binary blob internal structure. When all data is read
 /// the library will return IonParserError::BinaryError(ParsingError::NoDataToRead).
+/// `IonParser::consume_value_located` returns it inside a `LocatedIonParserError`,
+/// which tells where in the stream it was found.
 #[derive(PartialEq, Debug, Error)]
 pub enum IonParserError {
     #[error("Ion type not implemented")]
@@ -59,6 +63,8 @@ pub enum IonParserError {
     NotValidLengthFloat,
     #[error("Error parsing the ion binary format")]
     BinaryError(ParsingError),
+    #[error("Error parsing the ion text format")]
+    TextError(TextParsingError),
     #[error("Exponent for the decimal value is too big (greater than i64)")]
     DecimalExponentTooBig,
     #[error("Bool cannot have len")]
@@ -79,6 +85,36 @@ pub enum IonParserError {
     DecimalNotANumericValue(f64),
     #[error("The origin type is not supported in the destination type")]
     TypeNotSupported(IonValue),
+    #[error("The reader is not positioned on a value, call next first")]
+    NoCurrentValue,
+    #[error("Only lists, sexps and structs can be stepped in")]
+    NotAContainer,
+    #[error("The reader is not inside a container")]
+    NotInsideContainer,
+    #[error("The values are nested deeper than the maximum depth")]
+    MaxDepthExceeded,
+    #[error("The value is bigger than the maximum value size")]
+    MaxValueSizeExceeded,
+    #[error("The symbol table has more symbols than the maximum")]
+    MaxSymbolsExceeded,
+    #[error("The local symbol table has more imports than the maximum")]
+    MaxImportsExceeded,
+    #[error("The stream is bigger than the maximum total input")]
+    MaxTotalInputExceeded,
+    #[error("Ion 1.1 streams can only be read with the consume_value and consume_all methods of IonParser and IonSliceParser")]
+    Ion11NotSupported,
+    #[error("Opcode {0:#04x} is not valid in Ion 1.1")]
+    InvalidOpcode(u8),
+    #[error("Typed null with an invalid type {0:#04x}")]
+    InvalidNullType(u8),
+    #[error("Ion 1.1 timestamps cannot be {0} bytes long")]
+    InvalidTimestampLength(usize),
+    #[error("Macros are not supported, found an e-expression invoking the macro {0}")]
+    UnsupportedMacro(usize),
+    #[error("Macros are not supported, found an e-expression invoking the system macro {0}")]
+    UnsupportedSystemMacro(usize),
+    #[error("Ion 1.1 encoding directives are not supported")]
+    UnsupportedEncodingDirective,
 }
 
 impl From<ParsingError> for IonParserError {
@@ -87,6 +123,166 @@ impl From<ParsingError> for IonParserError {
     }
 }
 
+impl From<TextParsingError> for IonParserError {
+    fn from(err: TextParsingError) -> Self {
+        IonParserError::TextError(err)
+    }
+}
+
+/// The error returned by `IonParser::consume_value_located` and
+/// `consume_all_located`, with the place of the stream where it was found.
+/// The `IonParserError` is available with `kind`, and both can be compared
+/// directly.
+///
+/// ```rust
+/// use ion_binary_rs::{IonParser, IonParserError};
+///
+/// // A list with a struct whose field "name" is a string that is not UTF-8
+/// let ion = b"\xe0\x01\x00\xea\xb5\xd4\x84\x82\xc3\x28";
+///
+/// let error = IonParser::new(&ion[..]).consume_value_located().unwrap_err();
+///
+/// assert_eq!(error.kind(), &IonParserError::NonUtf8String);
+/// assert_eq!(error.offset(), 10);
+/// assert_eq!(error.value_index(), 0);
+/// assert_eq!(error.path().to_string(), "[0][0].name");
+/// ```
+#[derive(PartialEq, Debug, Error)]
+#[error("{} at byte {} ({})", .0.kind, .0.offset, .0.path)]
+pub struct LocatedIonParserError(Box<ErrorLocation>);
+
+// Boxed so results with a LocatedIonParserError stay small.
+#[derive(PartialEq, Debug)]
+struct ErrorLocation {
+    kind: IonParserError,
+    offset: usize,
+    value_index: usize,
+    path: IonValuePath,
+}
+
+impl LocatedIonParserError {
+    pub(crate) fn new(
+        kind: IonParserError,
+        offset: usize,
+        value_index: usize,
+        path: IonValuePath,
+    ) -> LocatedIonParserError {
+        LocatedIonParserError(Box::new(ErrorLocation {
+            kind,
+            offset,
+            value_index,
+            path,
+        }))
+    }
+
+    /// The kind of error found.
+    pub fn kind(&self) -> &IonParserError {
+        &self.0.kind
+    }
+
+    pub fn into_kind(self) -> IonParserError {
+        self.0.kind
+    }
+
+    /// Amount of bytes read from the stream when the error was found.
+    pub fn offset(&self) -> usize {
+        self.0.offset
+    }
+
+    /// Index of the top-level value being read, not counting symbol tables.
+    pub fn value_index(&self) -> usize {
+        self.0.value_index
+    }
+
+    /// Path to the value being read, starting with the top-level value.
+    pub fn path(&self) -> &IonValuePath {
+        &self.0.path
+    }
+}
+
+impl From<LocatedIonParserError> for IonParserError {
+    fn from(err: LocatedIonParserError) -> Self {
+        err.into_kind()
+    }
+}
+
+impl PartialEq<IonParserError> for LocatedIonParserError {
+    fn eq(&self, other: &IonParserError) -> bool {
+        self.0.kind == *other
+    }
+}
+
+impl PartialEq<LocatedIonParserError> for IonParserError {
+    fn eq(&self, other: &LocatedIonParserError) -> bool {
+        *self == other.0.kind
+    }
+}
+
+/// A step of the path from a top-level value to a nested one.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum PathComponent {
+    /// A struct field name.
+    Field(String),
+    /// The position in the stream, a list or a sexp.
+    Index(usize),
+}
+
+impl PathComponent {
+    // Field names without text are written as their SID, as in `$10`.
+    pub(crate) fn field(name: &SymbolToken) -> PathComponent {
+        match (name.text(), name.sid()) {
+            (Some(text), _) => PathComponent::Field(text.to_string()),
+            (None, Some(sid)) => PathComponent::Field(format!("${}", sid)),
+            (None, None) => PathComponent::Field("$0".to_string()),
+        }
+    }
+}
+
+/// Path to a value in a stream, written like `[3].orders[17].price`: the
+/// index of the top-level value followed by the field names and the list
+/// or sexp indexes. Field names that are not identifiers are quoted.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct IonValuePath(Vec<PathComponent>);
+
+impl IonValuePath {
+    pub(crate) fn new(components: Vec<PathComponent>) -> IonValuePath {
+        IonValuePath(components)
+    }
+
+    pub fn components(&self) -> &[PathComponent] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for IonValuePath {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for component in &self.0 {
+            match component {
+                PathComponent::Index(index) => write!(fmt, "[{}]", index)?,
+                PathComponent::Field(name) if is_identifier(name) => write!(fmt, ".{}", name)?,
+                PathComponent::Field(name) => write!(
+                    fmt,
+                    ".'{}'",
+                    name.replace('\\', "\\\\").replace('\'', "\\'")
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' || first == '$' => {
+            chars.all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$')
+        }
+        _ => false,
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum IonExtractionError {
     #[error("The current type doesn't support the requested transformation")]
@@ -125,7 +321,25 @@ pub enum SerdeJsonParseError {
 ///
 /// Floats are implemented only using f64. Previously there was Float32 and
 /// Float64, but there are some problems with IonHash and QLDB when using Float32.
-#[derive(PartialEq, Debug, Clone)]
+/// 4 bytes floats are read exactly, and `IonEncoder::with_compact_floats` writes
+/// them in 4 bytes again.
+///
+/// Structs are returned as Struct, where the field order is lost and, for
+/// repeated field names, only the last value is kept. Parsers configured with
+/// `with_ordered_structs` return OrderedStruct instead, which keeps all the
+/// fields in the order they were found. Field names are SymbolTokens in
+/// OrderedStruct, so parsers also return it for structs with field names
+/// whose text is unknown, which can't be keys of a Struct.
+///
+/// Timestamps are kept as IonTimestamp, which keeps their precision, unknown
+/// offsets and fractional seconds. It converts from and to chrono's DateTime.
+///
+/// Symbols and annotations are SymbolTokens, as their text may be unknown
+/// (`$0` or symbols from shared tables that are not available). They can be
+/// created from strings with `into()`.
+///
+/// Equality and ordering follow the Ion data model, see `IonValue::ion_eq`.
+#[derive(Debug, Clone)]
 pub enum IonValue {
     Null(NullIonValue),
     Bool(bool),
@@ -133,19 +347,18 @@ pub enum IonValue {
     BigInteger(BigInt),
     Float(f64),
     Decimal(BigDecimal),
-    DateTime(DateTime<FixedOffset>),
+    DateTime(IonTimestamp),
     String(String),
-    Symbol(String),
+    Symbol(SymbolToken),
     Clob(Vec<u8>),
     Blob(Vec<u8>),
     List(Vec<IonValue>),
     SExpr(Vec<IonValue>),
     Struct(HashMap<String, IonValue>),
-    Annotation(Vec<String>, Box<IonValue>),
+    OrderedStruct(Vec<(SymbolToken, IonValue)>),
+    Annotation(Vec<SymbolToken>, Box<IonValue>),
 }
 
-impl Eq for IonValue {}
-
 /// Instead of wrapping each IonValue in an Option in order to represent the
 /// null value, we opted to join all Null values in the IonValue::Null(_) which
 /// contains this struct. Here you can check what kind of null you got. We do this
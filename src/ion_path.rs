@@ -0,0 +1,517 @@
+use crate::binary_parser_types::{ValueHeader, ValueType};
+use crate::ion_parser_types::{is_identifier, IonParserError, IonValue};
+use crate::ion_reader::IonReader;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// One step of an `IonPath`.
+///
+/// `Field` and `Index` (and their wildcards) look through annotations, so
+/// `a[0]` matches `a: tag::[1]`. `Annotation` keeps only the values that
+/// have the given annotation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IonPathStep {
+    Field(String),
+    Index(usize),
+    AnyField,
+    AnyIndex,
+    Annotation(String),
+}
+
+/// Path expression that selects values nested inside an `IonValue`.
+///
+/// Paths are parsed from strings like `orders[3].price`, where:
+///
+/// - `.name` or `.'quoted name'` selects a struct field. The dot can be
+///   omitted in the first step.
+/// - `[3]` selects an element of a list or a sexp.
+/// - `.*` and `[*]` select every field or every element.
+/// - `::name` keeps only the values annotated with `name`.
+///
+/// An empty path selects the value itself. Steps that don't apply to a
+/// value, like an index on a struct, select nothing.
+///
+/// ```rust
+/// use ion_binary_rs::{IonPath, IonValue};
+/// use std::collections::HashMap;
+///
+/// let mut car = HashMap::new();
+/// car.insert("model".to_string(), IonValue::String("CLK 350".to_string()));
+/// car.insert(
+///     "owners".to_string(),
+///     IonValue::List(vec![
+///         IonValue::String("Alice".to_string()),
+///         IonValue::String("Bob".to_string()),
+///     ]),
+/// );
+///
+/// let mut car = IonValue::Struct(car);
+///
+/// let path: IonPath = "owners[1]".parse().unwrap();
+/// assert_eq!(path.get(&car), Some(&IonValue::String("Bob".to_string())));
+///
+/// let path: IonPath = "owners[*]".parse().unwrap();
+/// for owner in path.select_mut(&mut car) {
+///     *owner = IonValue::String("Unknown".to_string());
+/// }
+///
+/// assert_eq!(
+///     path.select_owned(&car),
+///     vec![IonValue::String("Unknown".to_string()); 2]
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IonPath(Vec<IonPathStep>);
+
+impl IonPath {
+    pub fn new(steps: Vec<IonPathStep>) -> IonPath {
+        IonPath(steps)
+    }
+
+    pub fn steps(&self) -> &[IonPathStep] {
+        &self.0
+    }
+
+    /// References to every value matched by the path.
+    ///
+    /// Lists, s-expressions and `OrderedStruct`s are walked in document
+    /// order. `Struct` fields are kept in a `HashMap`, so the values matched
+    /// by `.*` inside them come in no particular order.
+    pub fn select<'a>(&self, value: &'a IonValue) -> Vec<&'a IonValue> {
+        let mut values = vec![];
+        select_into(&self.0, value, &mut values);
+        values
+    }
+
+    /// Same as `select`, but returns copies of the matched values.
+    pub fn select_owned(&self, value: &IonValue) -> Vec<IonValue> {
+        self.select(value).into_iter().cloned().collect()
+    }
+
+    /// Mutable references to every value matched by the path, so they can
+    /// be edited in place.
+    pub fn select_mut<'a>(&self, value: &'a mut IonValue) -> Vec<&'a mut IonValue> {
+        let mut values = vec![];
+        select_mut_into(&self.0, value, &mut values);
+        values
+    }
+
+    /// First value matched by the path, in the order of `select`.
+    pub fn get<'a>(&self, value: &'a IonValue) -> Option<&'a IonValue> {
+        self.select(value).into_iter().next()
+    }
+
+    /// First value matched by the path, mutable.
+    pub fn get_mut<'a>(&self, value: &'a mut IonValue) -> Option<&'a mut IonValue> {
+        self.select_mut(value).into_iter().next()
+    }
+
+    /// Evaluates the path against every remaining value of the reader's
+    /// current level (the whole stream when called on a new reader) and
+    /// returns the matched values.
+    ///
+    /// Only the matched values are decoded, the rest of the stream is
+    /// skipped, so values can be pulled out of big streams without
+    /// building the top-level values.
+    ///
+    /// As the `IonReader`, it only reads Ion 1.0 and returns
+    /// `Ion11NotSupported` for Ion 1.1 values. Those streams can be parsed
+    /// with the `IonParser` and queried with `select`.
+    pub fn read<T: Read>(
+        &self,
+        reader: &mut IonReader<T>,
+    ) -> Result<Vec<IonValue>, IonParserError> {
+        let mut values = vec![];
+
+        while let Some(header) = reader.next()? {
+            read_into(&self.0, header, reader, &mut values)?;
+        }
+
+        Ok(values)
+    }
+}
+
+impl From<Vec<IonPathStep>> for IonPath {
+    fn from(steps: Vec<IonPathStep>) -> IonPath {
+        IonPath(steps)
+    }
+}
+
+fn has_annotation(value: &IonValue, text: &str) -> bool {
+    match value {
+        IonValue::Annotation(annotations, _) => annotations
+            .iter()
+            .any(|annotation| annotation.text() == Some(text)),
+        _ => false,
+    }
+}
+
+fn without_annotations(mut value: &IonValue) -> &IonValue {
+    while let IonValue::Annotation(_, inner) = value {
+        value = inner;
+    }
+
+    value
+}
+
+fn without_annotations_mut(mut value: &mut IonValue) -> &mut IonValue {
+    while let IonValue::Annotation(_, inner) = value {
+        value = inner;
+    }
+
+    value
+}
+
+fn select_into<'a>(steps: &[IonPathStep], value: &'a IonValue, values: &mut Vec<&'a IonValue>) {
+    let (step, rest) = match steps.split_first() {
+        Some(first) => first,
+        None => return values.push(value),
+    };
+
+    if let IonPathStep::Annotation(text) = step {
+        if has_annotation(value, text) {
+            select_into(rest, value, values);
+        }
+
+        return;
+    }
+
+    match (step, without_annotations(value)) {
+        (IonPathStep::Index(index), IonValue::List(list) | IonValue::SExpr(list)) => {
+            if let Some(element) = list.get(*index) {
+                select_into(rest, element, values);
+            }
+        }
+        (IonPathStep::AnyIndex, IonValue::List(list) | IonValue::SExpr(list)) => {
+            for element in list {
+                select_into(rest, element, values);
+            }
+        }
+        (IonPathStep::Field(name), IonValue::Struct(fields)) => {
+            if let Some(field) = fields.get(name) {
+                select_into(rest, field, values);
+            }
+        }
+        (IonPathStep::AnyField, IonValue::Struct(fields)) => {
+            for field in fields.values() {
+                select_into(rest, field, values);
+            }
+        }
+        (IonPathStep::Field(name), IonValue::OrderedStruct(fields)) => {
+            for (_, field) in fields.iter().filter(|(key, _)| key == name) {
+                select_into(rest, field, values);
+            }
+        }
+        (IonPathStep::AnyField, IonValue::OrderedStruct(fields)) => {
+            for (_, field) in fields {
+                select_into(rest, field, values);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn select_mut_into<'a>(
+    steps: &[IonPathStep],
+    value: &'a mut IonValue,
+    values: &mut Vec<&'a mut IonValue>,
+) {
+    let (step, rest) = match steps.split_first() {
+        Some(first) => first,
+        None => return values.push(value),
+    };
+
+    if let IonPathStep::Annotation(text) = step {
+        if has_annotation(value, text) {
+            select_mut_into(rest, value, values);
+        }
+
+        return;
+    }
+
+    match (step, without_annotations_mut(value)) {
+        (IonPathStep::Index(index), IonValue::List(list) | IonValue::SExpr(list)) => {
+            if let Some(element) = list.get_mut(*index) {
+                select_mut_into(rest, element, values);
+            }
+        }
+        (IonPathStep::AnyIndex, IonValue::List(list) | IonValue::SExpr(list)) => {
+            for element in list {
+                select_mut_into(rest, element, values);
+            }
+        }
+        (IonPathStep::Field(name), IonValue::Struct(fields)) => {
+            if let Some(field) = fields.get_mut(name) {
+                select_mut_into(rest, field, values);
+            }
+        }
+        (IonPathStep::AnyField, IonValue::Struct(fields)) => {
+            for field in fields.values_mut() {
+                select_mut_into(rest, field, values);
+            }
+        }
+        (IonPathStep::Field(name), IonValue::OrderedStruct(fields)) => {
+            for (_, field) in fields.iter_mut().filter(|(key, _)| key == name) {
+                select_mut_into(rest, field, values);
+            }
+        }
+        (IonPathStep::AnyField, IonValue::OrderedStruct(fields)) => {
+            for (_, field) in fields {
+                select_mut_into(rest, field, values);
+            }
+        }
+        _ => {}
+    }
+}
+
+// The reader is positioned on the value described by `header`. Values that
+// don't match are left unread, so the next call to `next` skips them.
+fn read_into<T: Read>(
+    steps: &[IonPathStep],
+    header: ValueHeader,
+    reader: &mut IonReader<T>,
+    values: &mut Vec<IonValue>,
+) -> Result<(), IonParserError> {
+    let (step, rest) = match steps.split_first() {
+        Some(first) => first,
+        None => {
+            values.push(reader.read_value()?);
+            return Ok(());
+        }
+    };
+
+    if let IonPathStep::Annotation(text) = step {
+        let is_annotated = reader
+            .annotations()
+            .iter()
+            .any(|annotation| annotation.text() == Some(text.as_str()));
+
+        if is_annotated {
+            read_into(rest, header, reader, values)?;
+        }
+
+        return Ok(());
+    }
+
+    if header.is_len_null_value() {
+        return Ok(());
+    }
+
+    let is_match = matches!(
+        (step, header.get_type()),
+        (
+            IonPathStep::Field(_) | IonPathStep::AnyField,
+            ValueType::Struct
+        ) | (
+            IonPathStep::Index(_) | IonPathStep::AnyIndex,
+            ValueType::List | ValueType::SExpr
+        )
+    );
+
+    if !is_match {
+        return Ok(());
+    }
+
+    reader.step_in()?;
+
+    let mut index = 0;
+
+    while let Some(header) = reader.next()? {
+        match step {
+            IonPathStep::Field(name) if reader.field_name() == Some(name.as_str()) => {
+                read_into(rest, header, reader, values)?
+            }
+            IonPathStep::Index(wanted) if index == *wanted => {
+                read_into(rest, header, reader, values)?;
+                break;
+            }
+            IonPathStep::AnyField | IonPathStep::AnyIndex => {
+                read_into(rest, header, reader, values)?
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    reader.step_out()
+}
+
+impl Display for IonPath {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        for step in &self.0 {
+            match step {
+                IonPathStep::Field(name) if is_identifier(name) => write!(fmt, ".{}", name)?,
+                IonPathStep::Field(name) => write!(fmt, ".{}", quote(name))?,
+                IonPathStep::Index(index) => write!(fmt, "[{}]", index)?,
+                IonPathStep::AnyField => write!(fmt, ".*")?,
+                IonPathStep::AnyIndex => write!(fmt, "[*]")?,
+                IonPathStep::Annotation(name) if is_identifier(name) => write!(fmt, "::{}", name)?,
+                IonPathStep::Annotation(name) => write!(fmt, "::{}", quote(name))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("'{}'", name.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Errors returned when parsing an `IonPath` from a string. Positions are
+/// byte offsets into the string.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IonPathError {
+    #[error("Unexpected character {1:?} at position {0} of the path")]
+    UnexpectedCharacter(usize, char),
+    #[error("The path ended in the middle of a step")]
+    UnexpectedEnd,
+    #[error("Invalid index at position {0} of the path")]
+    InvalidIndex(usize),
+}
+
+impl FromStr for IonPath {
+    type Err = IonPathError;
+
+    fn from_str(path: &str) -> Result<IonPath, IonPathError> {
+        let mut parser = PathParser { path, position: 0 };
+
+        let mut steps = vec![];
+
+        // The first field doesn't need a dot
+        if let Some(char) = parser.peek() {
+            if char != '.' && char != '[' && char != ':' {
+                steps.push(parser.parse_field()?);
+            }
+        }
+
+        while let Some(char) = parser.next() {
+            let step = match char {
+                '.' => parser.parse_field()?,
+                '[' => parser.parse_index()?,
+                ':' => {
+                    parser.expect(':')?;
+
+                    IonPathStep::Annotation(parser.parse_name()?)
+                }
+                char => {
+                    return Err(IonPathError::UnexpectedCharacter(
+                        parser.position - char.len_utf8(),
+                        char,
+                    ))
+                }
+            };
+
+            steps.push(step);
+        }
+
+        Ok(IonPath(steps))
+    }
+}
+
+struct PathParser<'a> {
+    path: &'a str,
+    position: usize,
+}
+
+impl PathParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.path[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.position += char.len_utf8();
+        Some(char)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), IonPathError> {
+        match self.next() {
+            Some(char) if char == expected => Ok(()),
+            Some(char) => Err(IonPathError::UnexpectedCharacter(
+                self.position - char.len_utf8(),
+                char,
+            )),
+            None => Err(IonPathError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_field(&mut self) -> Result<IonPathStep, IonPathError> {
+        if self.peek() == Some('*') {
+            self.position += 1;
+            return Ok(IonPathStep::AnyField);
+        }
+
+        Ok(IonPathStep::Field(self.parse_name()?))
+    }
+
+    fn parse_index(&mut self) -> Result<IonPathStep, IonPathError> {
+        if self.peek() == Some('*') {
+            self.position += 1;
+            self.expect(']')?;
+            return Ok(IonPathStep::AnyIndex);
+        }
+
+        let start = self.position;
+
+        while self.peek().is_some_and(|char| char.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        let index = self.path[start..self.position]
+            .parse()
+            .map_err(|_| IonPathError::InvalidIndex(start))?;
+
+        self.expect(']')?;
+
+        Ok(IonPathStep::Index(index))
+    }
+
+    fn parse_name(&mut self) -> Result<String, IonPathError> {
+        match self.peek() {
+            Some('\'') => {
+                self.position += 1;
+                self.parse_quoted_name()
+            }
+            Some(char) if char.is_ascii_alphanumeric() || char == '_' || char == '$' => {
+                let start = self.position;
+
+                while self
+                    .peek()
+                    .is_some_and(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$')
+                {
+                    self.position += 1;
+                }
+
+                Ok(self.path[start..self.position].to_string())
+            }
+            Some(char) => Err(IonPathError::UnexpectedCharacter(self.position, char)),
+            None => Err(IonPathError::UnexpectedEnd),
+        }
+    }
+
+    fn parse_quoted_name(&mut self) -> Result<String, IonPathError> {
+        let mut name = String::new();
+
+        loop {
+            match self.next() {
+                Some('\'') => return Ok(name),
+                Some('\\') => match self.next() {
+                    Some(char @ ('\'' | '\\')) => name.push(char),
+                    Some(char) => {
+                        return Err(IonPathError::UnexpectedCharacter(
+                            self.position - char.len_utf8(),
+                            char,
+                        ))
+                    }
+                    None => return Err(IonPathError::UnexpectedEnd),
+                },
+                Some(char) => name.push(char),
+                None => return Err(IonPathError::UnexpectedEnd),
+            }
+        }
+    }
+}
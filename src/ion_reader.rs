@@ -0,0 +1,439 @@
+use crate::binary_parser_types::*;
+use crate::catalog::Catalog;
+use crate::ion_parser::IonParser;
+use crate::ion_parser_types::*;
+use crate::ion_timestamp::IonTimestamp;
+use crate::symbol_table::SymbolContextError;
+use crate::symbol_token::SymbolToken;
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+use std::io::{Read, Seek};
+
+/// Cursor style reader over an Ion binary stream.
+///
+/// Unlike the `IonParser`, which builds whole top-level values, the
+/// `IonReader` moves value by value and only decodes what is asked for,
+/// so big streams can be processed with constant memory:
+///
+/// - `next` moves to the next value of the current level, skipping the
+///   rest of the previous one, and returns its header.
+/// - `step_in` and `step_out` enter and leave lists, sexps and structs.
+/// - `read_*` methods decode the current value.
+/// - `field_name` and `annotations` are available without decoding the value.
+///
+/// Symbol tables and NOP padding are processed automatically.
+///
+/// Only Ion 1.0 is supported, as the headers returned are Ion 1.0 type
+/// descriptors. `next` returns `Ion11NotSupported` at the first Ion 1.1
+/// value, use the `IonParser` in order to read those streams.
+///
+/// ```rust
+/// use ion_binary_rs::{IonEncoder, IonReader, IonValue, ValueType};
+/// use std::collections::HashMap;
+///
+/// let mut journal = HashMap::new();
+/// journal.insert("name".to_string(), IonValue::String("cars".to_string()));
+/// journal.insert(
+///     "entries".to_string(),
+///     IonValue::List(vec![IonValue::Integer(2019), IonValue::Integer(2020)]),
+/// );
+///
+/// let mut encoder = IonEncoder::new();
+/// encoder.add(IonValue::Struct(journal));
+/// let bytes = encoder.encode();
+///
+/// let mut reader = IonReader::new(&bytes[..]);
+///
+/// reader.next().unwrap();
+/// reader.step_in().unwrap();
+///
+/// let mut years = vec![];
+///
+/// while let Some(header) = reader.next().unwrap() {
+///     if reader.field_name() == Some("entries") && header.get_type() == ValueType::List {
+///         reader.step_in().unwrap();
+///
+///         while reader.next().unwrap().is_some() {
+///             years.push(reader.read_i64().unwrap());
+///         }
+///
+///         reader.step_out().unwrap();
+///     }
+/// }
+///
+/// reader.step_out().unwrap();
+///
+/// assert_eq!(years, vec![2019, 2020]);
+/// assert!(reader.next().unwrap().is_none());
+/// ```
+#[derive(Debug)]
+pub struct IonReader<T: Read> {
+    parser: IonParser<T>,
+    containers: Vec<ReaderContainer>,
+    current: Option<ReaderValue>,
+    text_buffer: String,
+}
+
+#[derive(Debug)]
+struct ReaderValue {
+    header: ValueHeader,
+    field_name: Option<SymbolToken>,
+    annotations: Vec<SymbolToken>,
+    annotation_end: Option<usize>,
+    pending_body: bool,
+}
+
+#[derive(Debug)]
+struct ReaderContainer {
+    value_type: ValueType,
+    end: usize,
+    annotation_end: Option<usize>,
+}
+
+impl<T: Read> IonReader<T> {
+    /// Creates a new reader. It accepts anything that implements the trait
+    /// [Read Trait](https://doc.rust-lang.org/stable/std/io/trait.Read.html)
+    #[inline]
+    pub fn new(reader: T) -> IonReader<T> {
+        IonReader {
+            parser: IonParser::new(reader),
+            containers: vec![],
+            current: None,
+            text_buffer: String::new(),
+        }
+    }
+
+    /// Same as `new`, but for readers that also implement the trait
+    /// [Seek Trait](https://doc.rust-lang.org/stable/std/io/trait.Seek.html).
+    /// Values that are not read are seeked over instead of read.
+    #[inline]
+    pub fn new_seekable(reader: T) -> IonReader<T>
+    where
+        T: Seek,
+    {
+        IonReader {
+            parser: IonParser::new_seekable(reader),
+            containers: vec![],
+            current: None,
+            text_buffer: String::new(),
+        }
+    }
+
+    /// Same as `IonParser::with_shared_table`.
+    #[inline]
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        self.parser.with_shared_table(name, version, symbols)
+    }
+
+    /// Same as `IonParser::with_catalog`.
+    #[inline]
+    pub fn with_catalog<C: Catalog + 'static>(&mut self, catalog: C) {
+        self.parser.with_catalog(catalog)
+    }
+
+    /// Moves to the next value in the current level and returns its header,
+    /// which tells the type of the value and whether it is a null. If the
+    /// previous value was not read it is skipped without decoding it.
+    ///
+    /// Returns None at the end of the current container or, in the top
+    /// level, at the end of the stream.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<ValueHeader>, IonParserError> {
+        self.finish_current_value()?;
+
+        let nested_level = self.containers.len() as u64;
+
+        loop {
+            let (end, is_struct) = match self.containers.last() {
+                Some(container) => (
+                    Some(container.end),
+                    container.value_type == ValueType::Struct,
+                ),
+                None => (None, false),
+            };
+
+            if let Some(end) = end {
+                if self.is_container_end(end)? {
+                    return Ok(None);
+                }
+            }
+
+            let field_name = if is_struct {
+                Some(self.parser.consume_field_name()?.0)
+            } else {
+                None
+            };
+
+            let header = match self.parser.consume_value_header(nested_level) {
+                Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) if end.is_none() => {
+                    return Ok(None)
+                }
+                header => header?,
+            };
+
+            if let Some(value) = self.parser.resolve_value_header(header, nested_level)? {
+                self.current = Some(ReaderValue {
+                    header: value.header,
+                    field_name,
+                    annotations: value.annotations,
+                    annotation_end: value.annotation_end,
+                    pending_body: true,
+                });
+
+                return Ok(Some(value.header));
+            }
+        }
+    }
+
+    /// Enters the current value, which needs to be a non null list, sexp or
+    /// struct. After it, `next` iterates over the container elements.
+    pub fn step_in(&mut self) -> Result<(), IonParserError> {
+        let value = match self.current.take() {
+            Some(value) if value.pending_body => value,
+            _ => return Err(IonParserError::NoCurrentValue),
+        };
+
+        let value_type = value.header.get_type();
+
+        let is_container = matches!(
+            value_type,
+            ValueType::List | ValueType::SExpr | ValueType::Struct
+        );
+
+        if !is_container || value.header.is_len_null_value() {
+            self.current = Some(value);
+            return Err(IonParserError::NotAContainer);
+        }
+
+        let (length, _, _) = if value_type == ValueType::Struct {
+            self.parser.consume_value_len_for_struct(&value.header)?
+        } else {
+            self.parser.consume_value_len(&value.header)?
+        };
+
+        self.containers.push(ReaderContainer {
+            value_type,
+            end: self.parser.position() + length,
+            annotation_end: value.annotation_end,
+        });
+
+        Ok(())
+    }
+
+    /// Leaves the current container, skipping any of its values that
+    /// haven't been read. After it, `next` moves to the value that follows
+    /// the container.
+    pub fn step_out(&mut self) -> Result<(), IonParserError> {
+        let container = self
+            .containers
+            .pop()
+            .ok_or(IonParserError::NotInsideContainer)?;
+
+        self.current = None;
+
+        let remaining = container
+            .end
+            .checked_sub(self.parser.position())
+            .ok_or(IonParserError::ListLengthWasTooShort)?;
+
+        self.parser.skip_bytes(remaining)?;
+
+        match container.annotation_end {
+            Some(end) if end != self.parser.position() => Err(IonParserError::BadAnnotationLength),
+            _ => Ok(()),
+        }
+    }
+
+    /// Amount of containers the reader is inside of. It is 0 for the top level.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.containers.len()
+    }
+
+    /// Returns the amount of bytes consumed from the reader so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.parser.position()
+    }
+
+    /// Field name of the current value when it is inside a struct and the
+    /// text of the name is known.
+    #[inline]
+    pub fn field_name(&self) -> Option<&str> {
+        self.field_name_token().and_then(SymbolToken::text)
+    }
+
+    /// Field name of the current value when it is inside a struct, with
+    /// its import location when its text is unknown.
+    #[inline]
+    pub fn field_name_token(&self) -> Option<&SymbolToken> {
+        self.current
+            .as_ref()
+            .and_then(|value| value.field_name.as_ref())
+    }
+
+    /// Annotations of the current value. Empty if it has none.
+    #[inline]
+    pub fn annotations(&self) -> &[SymbolToken] {
+        match &self.current {
+            Some(value) => &value.annotations,
+            None => &[],
+        }
+    }
+
+    /// Whether the current value is a null of any type.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.current
+            .as_ref()
+            .is_some_and(|value| value.header.is_len_null_value())
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Bool(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Integer(value) => Ok(value),
+            IonValue::BigInteger(value) => Ok(i64::try_from(value)?),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_big_int(&mut self) -> Result<BigInt, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Integer(value) => Ok(BigInt::from(value)),
+            IonValue::BigInteger(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Float(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_decimal(&mut self) -> Result<BigDecimal, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Decimal(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_timestamp(&mut self) -> Result<IonTimestamp, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::DateTime(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    /// Reads the current string. The returned text is only valid until the
+    /// next read.
+    pub fn read_str(&mut self) -> Result<&str, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::String(value) => {
+                self.text_buffer = value;
+                Ok(&self.text_buffer)
+            }
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    /// Reads the text of the current symbol. The returned text is only
+    /// valid until the next read. Symbols with unknown text return
+    /// `SymbolNotFoundInTable`, use `read_symbol_token` to read them.
+    pub fn read_symbol(&mut self) -> Result<&str, IonParserError> {
+        match self.read_symbol_token()?.into_text() {
+            Some(value) => {
+                self.text_buffer = value;
+                Ok(&self.text_buffer)
+            }
+            None => Err(IonParserError::SymbolNotFoundInTable),
+        }
+    }
+
+    /// Reads the current symbol, whose text may be unknown.
+    pub fn read_symbol_token(&mut self) -> Result<SymbolToken, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Symbol(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_blob(&mut self) -> Result<Vec<u8>, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Blob(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    pub fn read_clob(&mut self) -> Result<Vec<u8>, IonParserError> {
+        match self.consume_current_value()? {
+            IonValue::Clob(value) => Ok(value),
+            value => Err(IonParserError::TypeNotSupported(value)),
+        }
+    }
+
+    /// Reads the whole current value, containers included. It returns the
+    /// same value `IonParser::consume_value` would, annotations included.
+    pub fn read_value(&mut self) -> Result<IonValue, IonParserError> {
+        let value = self.consume_current_value()?;
+
+        match self.annotations() {
+            [] => Ok(value),
+            annotations => Ok(IonValue::Annotation(annotations.to_vec(), Box::new(value))),
+        }
+    }
+
+    fn consume_current_value(&mut self) -> Result<IonValue, IonParserError> {
+        let nested_level = self.containers.len() as u64;
+
+        let header = match &mut self.current {
+            Some(value) if value.pending_body => {
+                value.pending_body = false;
+                value.header
+            }
+            _ => return Err(IonParserError::NoCurrentValue),
+        };
+
+        Ok(self.parser.consume_value_body(&header, nested_level)?.0)
+    }
+
+    fn finish_current_value(&mut self) -> Result<(), IonParserError> {
+        let value = match self.current.take() {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+
+        if value.pending_body {
+            self.parser.skip_value_body(&value.header)?;
+        }
+
+        match value.annotation_end {
+            Some(end) if end != self.parser.position() => Err(IonParserError::BadAnnotationLength),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_container_end(&self, end: usize) -> Result<bool, IonParserError> {
+        let position = self.parser.position();
+
+        if position > end {
+            return Err(IonParserError::ListLengthWasTooShort);
+        }
+
+        Ok(position == end)
+    }
+}
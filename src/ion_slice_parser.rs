@@ -0,0 +1,456 @@
+use crate::binary_parser_types::*;
+use crate::catalog::Catalog;
+use crate::ion_parser::{AnnotationKind, IonParser, IonParserOptions};
+use crate::ion_parser_types::*;
+use crate::ion_value_ref::IonValueRef;
+use crate::symbol_table::SymbolContextError;
+use crate::symbol_token::SymbolToken;
+use log::trace;
+use std::borrow::Cow;
+use std::io::Cursor;
+
+pub type RefConsumerResult<'a> = Result<(IonValueRef<'a>, usize), IonParserError>;
+
+/// Parser for Ion binary that is already in memory. It works as the
+/// `IonParser`, with the same validations and errors, but it returns
+/// `IonValueRef`s whose strings, clobs and blobs borrow from the input
+/// instead of being copied. Ion 1.1 values are read too, but they are
+/// decoded by the `IonParser`, so they don't borrow from the input.
+///
+/// ```rust
+/// use ion_binary_rs::{IonEncoder, IonSliceParser, IonValue, IonValueRef};
+/// use std::borrow::Cow;
+///
+/// let mut encoder = IonEncoder::new();
+/// encoder.add(IonValue::List(vec![
+///     IonValue::String("text".into()),
+///     IonValue::Blob(vec![1, 2, 3]),
+/// ]));
+/// let bytes = encoder.encode();
+///
+/// let values = IonSliceParser::new(&bytes).consume_all().unwrap();
+///
+/// match &values[0] {
+///     IonValueRef::List(list) => {
+///         assert!(matches!(&list[0], IonValueRef::String(Cow::Borrowed("text"))));
+///         assert!(matches!(&list[1], IonValueRef::Blob(Cow::Borrowed([1, 2, 3]))));
+///     }
+///     other => panic!("Unexpected value {:?}", other),
+/// }
+/// ```
+#[derive(Debug)]
+pub struct IonSliceParser<'a> {
+    input: &'a [u8],
+    parser: IonParser<Cursor<&'a [u8]>>,
+    ordered_structs: bool,
+}
+
+impl<'a> IonSliceParser<'a> {
+    #[inline]
+    pub fn new(input: &'a [u8]) -> IonSliceParser<'a> {
+        IonSliceParser {
+            input,
+            parser: IonParser::new_seekable(Cursor::new(input)),
+            ordered_structs: false,
+        }
+    }
+
+    /// Same as `IonParser::with_shared_table`.
+    #[inline]
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        self.parser.with_shared_table(name, version, symbols)
+    }
+
+    /// Same as `IonParser::with_catalog`.
+    #[inline]
+    pub fn with_catalog<C: Catalog + 'static>(&mut self, catalog: C) {
+        self.parser.with_catalog(catalog)
+    }
+
+    /// Same as `IonParser::with_options`.
+    #[inline]
+    pub fn with_options(&mut self, options: IonParserOptions) {
+        self.parser.with_options(options)
+    }
+
+    /// Same as `IonParser::with_ordered_structs`.
+    #[inline]
+    pub fn with_ordered_structs(&mut self) {
+        self.parser.with_ordered_structs();
+        self.ordered_structs = true;
+    }
+
+    /// Consumes all the IonValues in the input and returns an array with them.
+    #[inline]
+    pub fn consume_all(&mut self) -> Result<Vec<IonValueRef<'a>>, IonParserError> {
+        self.consume_all_located().map_err(IonParserError::from)
+    }
+
+    /// Same as `IonParser::consume_all_located`.
+    #[inline]
+    pub fn consume_all_located(&mut self) -> Result<Vec<IonValueRef<'a>>, LocatedIonParserError> {
+        let mut values = Vec::with_capacity(1);
+
+        loop {
+            match self.consume_value_located() {
+                Err(e) if *e.kind() == IonParserError::BinaryError(ParsingError::NoDataToRead) => {
+                    break
+                }
+                Ok((value, _)) => values.push(value),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Consumes **one** IonValue and stops, as `IonParser::consume_value` does.
+    #[inline]
+    pub fn consume_value(&mut self) -> Result<(IonValueRef<'a>, usize), IonParserError> {
+        self.consume_value_located().map_err(IonParserError::from)
+    }
+
+    /// Same as `IonParser::consume_value_located`.
+    #[inline]
+    pub fn consume_value_located(
+        &mut self,
+    ) -> Result<(IonValueRef<'a>, usize), LocatedIonParserError> {
+        let result = self.consume_next_value(0);
+        self.parser.locate(result)
+    }
+
+    /// Same as `IonParser::consume_next_value`.
+    #[inline]
+    fn consume_next_value(&mut self, nested_level: u64) -> RefConsumerResult<'a> {
+        let opcode = self.parser.consume_opcode(nested_level)?;
+
+        // Ion 1.1 values are decoded by the IonParser, so they are owned
+        if self.parser.is_ion_1_1() {
+            let (value, consumed_bytes) = self.parser.consume_top_level_value_1_1(opcode)?;
+            return Ok((value.into(), consumed_bytes));
+        }
+
+        let value_header = ValueHeader::new(opcode)?;
+
+        let mut value = self.consume_value_body(&value_header, nested_level)?;
+
+        let already_consumed_value_header = 1;
+        value.1 += already_consumed_value_header;
+
+        Ok(value)
+    }
+
+    /// Same as `IonParser::skip_value`.
+    #[inline]
+    pub fn skip_value(&mut self) -> Result<usize, IonParserError> {
+        self.parser.skip_value()
+    }
+
+    /// Returns the amount of bytes consumed from the input so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.parser.position()
+    }
+
+    fn consume_value_body(
+        &mut self,
+        value_header: &ValueHeader,
+        nested_level: u64,
+    ) -> RefConsumerResult<'a> {
+        if value_header.is_nop() {
+            let consumed_bytes = self.parser.consume_nop(value_header)?;
+            let value = self.consume_next_value(nested_level)?;
+            return Ok((value.0, value.1 + consumed_bytes));
+        }
+
+        match value_header.get_type() {
+            ValueType::Annotation => match self.consume_annotation(value_header, nested_level)? {
+                (Some(annotation), consumed_bytes) => Ok((annotation, consumed_bytes)),
+                (None, consumed_bytes) => {
+                    let value = self.consume_next_value(nested_level)?;
+                    Ok((value.0, value.1 + consumed_bytes))
+                }
+            },
+            ValueType::Struct => self.consume_struct(value_header, nested_level),
+            ValueType::List => self.consume_list(value_header, nested_level),
+            ValueType::SExpr => self.consume_sexpr(value_header, nested_level),
+            ValueType::String => self.consume_string(value_header),
+            ValueType::Clob => self.consume_clob(value_header),
+            ValueType::Blob => self.consume_blob(value_header),
+            // Scalars that do not borrow from the input are decoded by the
+            // IonParser itself.
+            _ => {
+                let (value, consumed_bytes) =
+                    self.parser.consume_value_body(value_header, nested_level)?;
+                Ok((value.into(), consumed_bytes))
+            }
+        }
+    }
+
+    /// Returns the next `length` bytes of the input and moves past them,
+    /// failing the same way reading them from a reader would.
+    #[inline]
+    fn borrow_bytes(&mut self, length: usize) -> Result<&'a [u8], IonParserError> {
+        let start = self.position();
+
+        let bytes = match start
+            .checked_add(length)
+            .and_then(|end| self.input.get(start..end))
+        {
+            Some(bytes) => bytes,
+            None => {
+                // Readers consume what is left before failing.
+                let available = self.input.len().saturating_sub(start);
+                self.parser.skip_bytes(available)?;
+
+                return Err(if available == 0 {
+                    ParsingError::NoDataToRead.into()
+                } else {
+                    ParsingError::NotEnoughtDataToRead(available).into()
+                });
+            }
+        };
+
+        self.parser.skip_bytes(length)?;
+
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn consume_string(&mut self, header: &ValueHeader) -> RefConsumerResult<'a> {
+        trace!("Consuming String");
+
+        if header.is_len_null_value() {
+            return Ok((IonValueRef::Null(NullIonValue::String), 0));
+        }
+
+        if header.get_len() == 0 {
+            return Ok((IonValueRef::String(Cow::Borrowed("")), 0));
+        }
+
+        let (length, _, total) = self.parser.consume_value_len(header)?;
+        let bytes = self.borrow_bytes(length)?;
+
+        let text = std::str::from_utf8(bytes).map_err(|_| IonParserError::NonUtf8String)?;
+
+        Ok((IonValueRef::String(Cow::Borrowed(text)), total))
+    }
+
+    #[inline]
+    fn consume_clob(&mut self, header: &ValueHeader) -> RefConsumerResult<'a> {
+        trace!("Consuming clob");
+
+        if header.is_len_null_value() {
+            return Ok((IonValueRef::Null(NullIonValue::Clob), 0));
+        }
+
+        let (bytes, total) = self.consume_lob_bytes(header)?;
+
+        Ok((IonValueRef::Clob(Cow::Borrowed(bytes)), total))
+    }
+
+    #[inline]
+    fn consume_blob(&mut self, header: &ValueHeader) -> RefConsumerResult<'a> {
+        trace!("Consuming blob");
+
+        if header.is_len_null_value() {
+            return Ok((IonValueRef::Null(NullIonValue::Blob), 0));
+        }
+
+        let (bytes, total) = self.consume_lob_bytes(header)?;
+
+        Ok((IonValueRef::Blob(Cow::Borrowed(bytes)), total))
+    }
+
+    #[inline]
+    fn consume_lob_bytes(
+        &mut self,
+        header: &ValueHeader,
+    ) -> Result<(&'a [u8], usize), IonParserError> {
+        if header.get_len() == 0 {
+            return Ok((&[], 0));
+        }
+
+        let (length, _, total) = self.parser.consume_value_len(header)?;
+
+        Ok((self.borrow_bytes(length)?, total))
+    }
+
+    #[inline]
+    fn consume_struct(&mut self, header: &ValueHeader, nested_level: u64) -> RefConsumerResult<'a> {
+        trace!("Consuming Struct");
+
+        if header.is_len_null_value() {
+            return Ok((IonValueRef::Null(NullIonValue::Struct), 0));
+        }
+
+        self.parser.check_depth(nested_level)?;
+
+        let (length, _, total) = self.parser.consume_value_len_for_struct(header)?;
+        let mut consumed_bytes = 0;
+        let mut values: Vec<(SymbolToken, IonValueRef<'a>)> = Vec::new();
+
+        while length - consumed_bytes > 0 {
+            let (key, consumed) = self.parser.consume_field_name()?;
+            consumed_bytes += consumed;
+
+            let (value, consumed) = match self.consume_element(nested_level) {
+                Ok(element) => element,
+                Err(error) => {
+                    self.parser.push_error_path(PathComponent::field(&key));
+                    return Err(error);
+                }
+            };
+
+            consumed_bytes += consumed;
+
+            if let Some(value) = value {
+                values.push((key, value));
+            }
+        }
+
+        if length.checked_sub(consumed_bytes).is_none() {
+            return Err(IonParserError::ListLengthWasTooShort);
+        }
+
+        // Field names without text can't be keys of a Struct
+        let value = if self.ordered_structs || values.iter().any(|(name, _)| name.is_unknown()) {
+            IonValueRef::OrderedStruct(values)
+        } else {
+            IonValueRef::Struct(
+                values
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name.into_text()?, value)))
+                    .collect(),
+            )
+        };
+
+        Ok((value, total))
+    }
+
+    #[inline]
+    fn consume_list(&mut self, header: &ValueHeader, nested_level: u64) -> RefConsumerResult<'a> {
+        trace!("Consuming List");
+
+        if header.is_len_null_value() {
+            return Ok((IonValueRef::Null(NullIonValue::List), 0));
+        }
+
+        self.parser.check_depth(nested_level)?;
+
+        let (length, _, total) = self.parser.consume_value_len(header)?;
+        let mut consumed_bytes = 0;
+        let mut values = vec![];
+
+        while length - consumed_bytes > 0 {
+            let (value, consumed) = match self.consume_element(nested_level) {
+                Ok(element) => element,
+                Err(error) => {
+                    self.parser
+                        .push_error_path(PathComponent::Index(values.len()));
+                    return Err(error);
+                }
+            };
+
+            consumed_bytes += consumed;
+
+            if let Some(value) = value {
+                values.push(value);
+            }
+
+            if consumed_bytes > length {
+                return Err(IonParserError::ListLengthWasTooShort);
+            }
+        }
+
+        Ok((IonValueRef::List(values), total))
+    }
+
+    /// Same as `IonParser::consume_element`.
+    #[inline]
+    fn consume_element(
+        &mut self,
+        nested_level: u64,
+    ) -> Result<(Option<IonValueRef<'a>>, usize), IonParserError> {
+        let value_header = self
+            .parser
+            .consume_value_header(nested_level.saturating_add(1))?;
+
+        if value_header.is_nop() {
+            return Ok((None, self.parser.consume_nop(&value_header)? + 1));
+        }
+
+        let value = self.consume_value_body(&value_header, nested_level.saturating_add(1))?;
+
+        Ok((Some(value.0), value.1 + 1))
+    }
+
+    #[inline]
+    fn consume_sexpr(&mut self, header: &ValueHeader, nested_level: u64) -> RefConsumerResult<'a> {
+        trace!("Consuming SExpr");
+
+        if header.is_len_null_value() {
+            return Ok((IonValueRef::Null(NullIonValue::SExpr), 0));
+        }
+
+        match self.consume_list(header, nested_level)? {
+            (IonValueRef::List(list), len) => Ok((IonValueRef::SExpr(list), len)),
+            _ => Err(IonParserError::DidNotGetAListConsumingAListThisIsABug),
+        }
+    }
+
+    #[inline]
+    fn consume_annotation(
+        &mut self,
+        header: &ValueHeader,
+        nested_level: u64,
+    ) -> Result<(Option<IonValueRef<'a>>, usize), IonParserError> {
+        trace!("Consuming Annotation");
+
+        let annotation = self.parser.consume_annotation_header(header)?;
+
+        match self.parser.get_annotation_kind(&annotation.symbols)? {
+            AnnotationKind::SharedTable => {
+                let table = self
+                    .parser
+                    .consume_annotated_value(&annotation, nested_level)?;
+                self.parser.load_shared_table(table)?;
+                Ok((None, annotation.total_consumed_bytes))
+            }
+            AnnotationKind::LocalTable => {
+                let table = self
+                    .parser
+                    .consume_annotated_value(&annotation, nested_level)?;
+                self.parser.load_local_table(table)?;
+                Ok((None, annotation.total_consumed_bytes))
+            }
+            AnnotationKind::User => {
+                let value = self.consume_next_value(nested_level)?;
+
+                if let IonValueRef::Annotation(_, _) = value.0 {
+                    return Err(IonParserError::NestedAnnotations);
+                }
+
+                if annotation.consumed_bytes + value.1 != annotation.length {
+                    return Err(IonParserError::BadAnnotationLength);
+                }
+
+                let mut symbols = Vec::with_capacity(annotation.symbols.len());
+
+                for symbol in annotation.symbols {
+                    symbols.push(self.parser.get_symbol_name(symbol)?);
+                }
+
+                Ok((
+                    Some(IonValueRef::Annotation(symbols, Box::new(value.0))),
+                    annotation.total_consumed_bytes,
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::text_encoder::{
+    encode_text_blob, encode_text_clob, encode_text_datetime, encode_text_decimal,
+    encode_text_float, encode_text_null, encode_text_string, encode_text_symbol,
+    encode_text_symbol_token,
+};
+use crate::IonValue;
+use std::fmt::{Display, Formatter};
+
+const PRETTY_INDENTATION: &str = "  ";
+
+/// Allows to encode one or multiple IonValue as Ion text.
+///
+/// It follows the same approach as the `IonEncoder`, values are added
+/// with `add` and `encode` returns all of them as a String. There are
+/// two output modes:
+///
+/// - `new` creates a compact encoder, which writes each value in a
+///   single line with no extra whitespace.
+/// - `new_pretty` creates an encoder that writes one element per line,
+///   indenting the content of lists, sexps and structs.
+///
+/// Struct fields are written sorted by name, so the output is the same
+/// for equal values. OrderedStruct fields are written in their order.
+///
+/// ```rust
+///
+/// use ion_binary_rs::{IonTextEncoder, IonTextParser, IonValue};
+/// use std::collections::HashMap;
+///
+/// let mut ion_struct = HashMap::new();
+///
+/// ion_struct.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
+/// ion_struct.insert("Year".to_string(), IonValue::Integer(2019));
+///
+/// let ion_value = IonValue::Struct(ion_struct);
+///
+/// let mut encoder = IonTextEncoder::new();
+///
+/// encoder.add(ion_value.clone());
+/// let text = encoder.encode();
+///
+/// assert_eq!(text, "{Model:\"CLK 350\",Year:2019}");
+///
+/// let resulting_ion_value = IonTextParser::new(text.as_bytes()).consume_value().unwrap().0;
+///
+/// assert_eq!(ion_value, resulting_ion_value);
+/// ```
+#[derive(Debug)]
+pub struct IonTextEncoder {
+    current_buffer: Vec<IonValue>,
+    pretty: bool,
+}
+
+impl Default for IonTextEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IonTextEncoder {
+    pub fn new() -> IonTextEncoder {
+        IonTextEncoder {
+            current_buffer: vec![],
+            pretty: false,
+        }
+    }
+
+    pub fn new_pretty() -> IonTextEncoder {
+        IonTextEncoder {
+            current_buffer: vec![],
+            pretty: true,
+        }
+    }
+
+    pub fn add(&mut self, value: IonValue) {
+        self.current_buffer.push(value);
+    }
+
+    pub fn encode(&mut self) -> String {
+        let mut values = vec![];
+
+        values.append(&mut self.current_buffer);
+
+        let separator = if self.pretty { "\n" } else { " " };
+
+        let mut buffer = String::new();
+
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(separator);
+            }
+
+            self.encode_value(&mut buffer, value, 0);
+        }
+
+        buffer
+    }
+
+    pub(crate) fn encode_value(&self, buffer: &mut String, value: &IonValue, depth: usize) {
+        match value {
+            IonValue::Null(value) => encode_text_null(buffer, value),
+            IonValue::Bool(value) => buffer.push_str(if *value { "true" } else { "false" }),
+            IonValue::Integer(value) => buffer.push_str(&value.to_string()),
+            IonValue::BigInteger(value) => buffer.push_str(&value.to_string()),
+            IonValue::Float(value) => encode_text_float(buffer, *value),
+            IonValue::Decimal(value) => encode_text_decimal(buffer, value),
+            IonValue::String(value) => encode_text_string(buffer, value),
+            IonValue::Clob(value) => encode_text_clob(buffer, value),
+            IonValue::Blob(value) => encode_text_blob(buffer, value),
+            IonValue::DateTime(value) => encode_text_datetime(buffer, value),
+            IonValue::List(values) => self.encode_list(buffer, values, false, depth),
+            IonValue::SExpr(values) => self.encode_list(buffer, values, true, depth),
+            IonValue::Symbol(value) => encode_text_symbol_token(buffer, value),
+            IonValue::Struct(values) => {
+                let mut fields: Vec<(Option<&str>, &IonValue)> = values
+                    .iter()
+                    .map(|(name, value)| (Some(name.as_str()), value))
+                    .collect();
+                fields.sort_by_key(|(name, _)| *name);
+                self.encode_struct(buffer, &fields, depth)
+            }
+            IonValue::OrderedStruct(values) => {
+                let fields: Vec<(Option<&str>, &IonValue)> = values
+                    .iter()
+                    .map(|(name, value)| (name.text(), value))
+                    .collect();
+                self.encode_struct(buffer, &fields, depth)
+            }
+            IonValue::Annotation(annotations, value) => {
+                for annotation in annotations {
+                    encode_text_symbol_token(buffer, annotation);
+                    buffer.push_str("::");
+                }
+
+                self.encode_value(buffer, value, depth);
+            }
+        }
+    }
+
+    fn encode_list(&self, buffer: &mut String, values: &[IonValue], is_sexp: bool, depth: usize) {
+        let (open, close, separator) = if is_sexp {
+            ('(', ')', if self.pretty { "" } else { " " })
+        } else {
+            ('[', ']', ",")
+        };
+
+        buffer.push(open);
+
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(separator);
+            }
+
+            self.encode_new_line(buffer, depth + 1);
+            self.encode_value(buffer, value, depth + 1);
+        }
+
+        if !values.is_empty() {
+            self.encode_new_line(buffer, depth);
+        }
+
+        buffer.push(close);
+    }
+
+    // Field names without text are written as `$0`, as symbols are
+    fn encode_struct(
+        &self,
+        buffer: &mut String,
+        fields: &[(Option<&str>, &IonValue)],
+        depth: usize,
+    ) {
+        buffer.push('{');
+
+        for (index, (name, value)) in fields.iter().enumerate() {
+            if index > 0 {
+                buffer.push(',');
+            }
+
+            self.encode_new_line(buffer, depth + 1);
+            match name {
+                Some(name) => encode_text_symbol(buffer, name),
+                None => buffer.push_str("$0"),
+            }
+            buffer.push_str(if self.pretty { ": " } else { ":" });
+            self.encode_value(buffer, value, depth + 1);
+        }
+
+        if !fields.is_empty() {
+            self.encode_new_line(buffer, depth);
+        }
+
+        buffer.push('}');
+    }
+
+    fn encode_new_line(&self, buffer: &mut String, depth: usize) {
+        if self.pretty {
+            buffer.push('\n');
+
+            for _ in 0..depth {
+                buffer.push_str(PRETTY_INDENTATION);
+            }
+        }
+    }
+}
+
+/// Writes the value as Ion text. The alternate flag (`{:#}`) uses the
+/// pretty mode of the `IonTextEncoder`.
+impl Display for IonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let encoder = if f.alternate() {
+            IonTextEncoder::new_pretty()
+        } else {
+            IonTextEncoder::new()
+        };
+
+        let mut buffer = String::new();
+
+        encoder.encode_value(&mut buffer, self, 0);
+
+        f.write_str(&buffer)
+    }
+}
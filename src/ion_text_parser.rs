@@ -0,0 +1,863 @@
+use crate::binary_parser_types::ParsingError;
+use crate::catalog::Catalog;
+use crate::ion_parser::{
+    load_local_table, load_shared_table, new_struct_value, ConsumerResult, IonParserOptions,
+};
+use crate::ion_parser_types::*;
+use crate::ion_timestamp::{IonTimestamp, TimestampPrecision};
+use crate::symbol_table::*;
+use crate::symbol_token::SymbolToken;
+use crate::text_parser::*;
+use crate::text_parser_types::TextParsingError;
+use bigdecimal::BigDecimal;
+use log::trace;
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Parser for the Ion text format. It returns the same IonValue and IonParserError
+/// types as the binary IonParser, so both can be used interchangeably. In order to
+/// use it call the new method and then the "consume_all" method.
+///
+/// It implements the whole Ion 1.0 text grammar: comments, long strings,
+/// s-expression operators, blobs, clobs, symbol ids and local symbol tables
+/// declared in text.
+///
+/// ### Example
+///
+/// ```rust,no_run
+///
+/// use ion_binary_rs::IonTextParser;
+///
+/// let ion_test = r#"{ Model: "CLK 350", Type: "Sedan", Year: 2019, tags: [sedan, 'white car'] }"#;
+///
+/// let mut parser = IonTextParser::new(ion_test.as_bytes());
+///
+/// println!("Decoded Ion: {:?}", parser.consume_all().unwrap())
+/// // Decoded Ion: [Struct({"Year": Integer(2019), "tags": List([Symbol("sedan"), Symbol("white car")]), "Model": String("CLK 350"), "Type": String("Sedan")})]
+///
+/// ```
+#[derive(Debug)]
+pub struct IonTextParser<T: Read> {
+    lexer: IonTextLexer<T>,
+    context: SymbolContext,
+    ordered_structs: bool,
+}
+
+// A top level element can be a version marker instead of a value.
+enum TextElement {
+    Value(IonValue),
+    VersionMarker(String),
+}
+
+enum TextSymbol {
+    Identifier(String),
+    Quoted(String),
+}
+
+const ION_1_0_VERSION_MARKER: &str = "$ion_1_0";
+const LOCAL_SYMBOL_TABLE_ANNOTATION: &str = "$ion_symbol_table";
+const SHARED_SYMBOL_TABLE_ANNOTATION: &str = "$ion_shared_symbol_table";
+
+impl<T: Read> IonTextParser<T> {
+    /// Creates a new parser. It accepts anything that implements the trait
+    /// [Read Trait](https://doc.rust-lang.org/stable/std/io/trait.Read.html)
+    #[inline]
+    pub fn new(reader: T) -> IonTextParser<T> {
+        IonTextParser {
+            lexer: IonTextLexer::new(reader),
+            context: SymbolContext::new(),
+            ordered_structs: false,
+        }
+    }
+
+    /// Allows to set up shared tables in order to resolve symbol ids (`$10`) and
+    /// local table imports that refer to symbols that are not in the text.
+    #[inline]
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        let symbols: Vec<Symbol> = symbols
+            .iter()
+            .map(|s| Symbol::Symbol(s.to_string()))
+            .collect();
+
+        self.context.add_shared_table(name, version, &symbols)
+    }
+
+    /// Sets a catalog where the parser looks for the shared tables imported
+    /// by local symbol tables that have not been added with
+    /// `with_shared_table`.
+    #[inline]
+    pub fn with_catalog<C: Catalog + 'static>(&mut self, catalog: C) {
+        self.context.set_catalog(Arc::new(catalog));
+    }
+
+    /// Shared tables declared in the stream read so far, plus the ones
+    /// added with `with_shared_table` or taken from the catalog.
+    pub(crate) fn get_shared_tables(&self) -> Vec<SharedSymbolTable> {
+        self.context.get_shared_tables()
+    }
+
+    /// Makes the parser return structs as IonValue::OrderedStruct, keeping
+    /// the order of the fields and repeated field names.
+    #[inline]
+    pub fn with_ordered_structs(&mut self) {
+        self.ordered_structs = true;
+    }
+
+    /// Consumes all the IonValues in the text and returns an array with them.
+    #[inline]
+    pub fn consume_all(&mut self) -> Result<Vec<IonValue>, IonParserError> {
+        let mut values = Vec::with_capacity(1);
+
+        loop {
+            match self.consume_value() {
+                Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => break,
+                Ok((value, _)) => values.push(value),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Consumes **one** IonValue and stops. Version markers and local or shared
+    /// symbol tables are processed automatically, continuing with the next value.
+    /// The second element of the tuple is the amount of bytes consumed. When there
+    /// are no more values it returns the same error as the binary parser:
+    /// `IonParserError::BinaryError(ParsingError::NoDataToRead)`.
+    #[inline]
+    pub fn consume_value(&mut self) -> ConsumerResult {
+        let start = self.lexer.position();
+
+        loop {
+            self.lexer.skip_whitespace(true)?;
+
+            if self.lexer.peek()?.is_none() {
+                return Err(IonParserError::BinaryError(ParsingError::NoDataToRead));
+            }
+
+            match self.consume_element(0, false)? {
+                TextElement::VersionMarker(marker) => self.process_version_marker(marker)?,
+                TextElement::Value(IonValue::Annotation(annotations, value))
+                    if is_symbol_table(&annotations, &value) =>
+                {
+                    if annotations[0] == LOCAL_SYMBOL_TABLE_ANNOTATION {
+                        load_local_table(&mut self.context, *value, &IonParserOptions::default())?;
+                    } else {
+                        load_shared_table(&mut self.context, *value, &IonParserOptions::default())?;
+                    }
+                }
+                TextElement::Value(value) => {
+                    return Ok((value, self.lexer.position() - start));
+                }
+            }
+        }
+    }
+
+    /// Returns the amount of bytes consumed from the reader so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.lexer.position()
+    }
+
+    #[inline]
+    fn process_version_marker(&mut self, marker: String) -> Result<(), IonParserError> {
+        trace!("Version marker found: {}", marker);
+
+        if marker != ION_1_0_VERSION_MARKER {
+            return Err(IonParserError::TextError(
+                TextParsingError::UnsupportedVersion(marker),
+            ));
+        }
+
+        self.context
+            .set_new_table(&[], &[])
+            .map_err(IonParserError::ErrorAddingCreatingLocal)
+    }
+
+    // Consumes a value with all its annotations. Symbols need to be read before
+    // knowing if they are the value or an annotation of the value.
+    fn consume_element(
+        &mut self,
+        nested_level: u64,
+        is_sexpr: bool,
+    ) -> Result<TextElement, IonParserError> {
+        let mut annotations = Vec::new();
+
+        loop {
+            self.lexer.skip_whitespace(true)?;
+
+            let symbol = match self.peek_or_fail()? {
+                byte if is_identifier_start(byte) => {
+                    let identifier = self.lexer.consume_identifier()?;
+
+                    if identifier == "null" && self.lexer.peek()? == Some(b'.') {
+                        let value = self.consume_typed_null()?;
+                        return Ok(construct_element(annotations, value));
+                    }
+
+                    TextSymbol::Identifier(identifier)
+                }
+                b'\'' if !self.lexer.starts_with(b"'''")? => {
+                    let text = self.lexer.consume_quoted(b'\'', StringMode::Text)?;
+                    TextSymbol::Quoted(bytes_to_string(text)?)
+                }
+                _ => break,
+            };
+
+            self.lexer.skip_whitespace(true)?;
+
+            if self.lexer.starts_with(b"::")? {
+                self.lexer.skip(2)?;
+                annotations.push(self.get_annotation(symbol)?);
+                continue;
+            }
+
+            if let TextSymbol::Identifier(identifier) = &symbol {
+                let is_top_level_value = nested_level == 0 && annotations.is_empty();
+
+                if is_top_level_value && is_version_marker(identifier) {
+                    return Ok(TextElement::VersionMarker(identifier.clone()));
+                }
+            }
+
+            let value = self.get_symbol_value(symbol)?;
+            return Ok(construct_element(annotations, value));
+        }
+
+        let value = self.consume_non_symbol_value(nested_level, is_sexpr)?;
+
+        Ok(construct_element(annotations, value))
+    }
+
+    #[inline]
+    fn consume_nested_value(
+        &mut self,
+        nested_level: u64,
+        is_sexpr: bool,
+    ) -> Result<IonValue, IonParserError> {
+        match self.consume_element(nested_level, is_sexpr)? {
+            TextElement::Value(value) => Ok(value),
+            TextElement::VersionMarker(marker) => Err(IonParserError::TextError(
+                TextParsingError::UnsupportedVersion(marker),
+            )),
+        }
+    }
+
+    fn consume_non_symbol_value(
+        &mut self,
+        nested_level: u64,
+        is_sexpr: bool,
+    ) -> Result<IonValue, IonParserError> {
+        match self.peek_or_fail()? {
+            b'{' if self.lexer.peek_at(1)? == Some(b'{') => self.consume_lob(),
+            b'{' => self.consume_struct(nested_level),
+            b'[' => self.consume_list(nested_level),
+            b'(' => self.consume_sexpr(nested_level),
+            b'"' => {
+                let text = self.lexer.consume_quoted(b'"', StringMode::Text)?;
+                Ok(IonValue::String(bytes_to_string(text)?))
+            }
+            b'\'' => {
+                let text = self.consume_long_strings(StringMode::Text)?;
+                Ok(IonValue::String(bytes_to_string(text)?))
+            }
+            b'0'..=b'9' => self.consume_numeric(),
+            byte @ (b'+' | b'-') => {
+                if self.is_infinity()? {
+                    self.lexer.skip(4)?;
+                    let infinity = if byte == b'+' {
+                        f64::INFINITY
+                    } else {
+                        f64::NEG_INFINITY
+                    };
+                    return Ok(IonValue::Float(infinity));
+                }
+
+                let is_number = byte == b'-'
+                    && matches!(self.lexer.peek_at(1)?, Some(digit) if digit.is_ascii_digit());
+
+                if is_number {
+                    self.consume_numeric()
+                } else if is_sexpr {
+                    Ok(IonValue::Symbol(self.lexer.consume_operator()?.into()))
+                } else {
+                    Err(unexpected_character(byte))
+                }
+            }
+            byte if is_sexpr && is_operator_part(byte) => {
+                Ok(IonValue::Symbol(self.lexer.consume_operator()?.into()))
+            }
+            byte => Err(unexpected_character(byte)),
+        }
+    }
+
+    fn consume_struct(&mut self, nested_level: u64) -> Result<IonValue, IonParserError> {
+        trace!("Consuming Struct");
+
+        self.lexer.expect(b'{')?;
+
+        let mut values = Vec::new();
+
+        loop {
+            self.lexer.skip_whitespace(true)?;
+
+            if self.peek_or_fail()? == b'}' {
+                self.lexer.skip(1)?;
+                break;
+            }
+
+            let key = self.consume_field_name()?;
+
+            self.lexer.skip_whitespace(true)?;
+            self.lexer.expect(b':')?;
+
+            if self.lexer.peek()? == Some(b':') {
+                return Err(unexpected_character(b':'));
+            }
+
+            let value = self.consume_nested_value(nested_level + 1, false)?;
+
+            values.push((key, value));
+
+            self.lexer.skip_whitespace(true)?;
+
+            match self.lexer.consume_byte()? {
+                b',' => continue,
+                b'}' => break,
+                byte => return Err(unexpected_character(byte)),
+            }
+        }
+
+        Ok(new_struct_value(values, self.ordered_structs))
+    }
+
+    fn consume_field_name(&mut self) -> Result<SymbolToken, IonParserError> {
+        let name = match self.peek_or_fail()? {
+            b'"' => self.lexer.consume_quoted(b'"', StringMode::Text)?,
+            b'\'' if self.lexer.starts_with(b"'''")? => {
+                self.consume_long_strings(StringMode::Text)?
+            }
+            b'\'' => self.lexer.consume_quoted(b'\'', StringMode::Text)?,
+            byte if is_identifier_start(byte) => {
+                let identifier = self.lexer.consume_identifier()?;
+                return self.resolve_identifier(identifier);
+            }
+            byte => return Err(unexpected_character(byte)),
+        };
+
+        Ok(bytes_to_string(name)?.into())
+    }
+
+    fn consume_list(&mut self, nested_level: u64) -> Result<IonValue, IonParserError> {
+        trace!("Consuming List");
+
+        self.lexer.expect(b'[')?;
+
+        let mut values = Vec::new();
+
+        loop {
+            self.lexer.skip_whitespace(true)?;
+
+            if self.peek_or_fail()? == b']' {
+                self.lexer.skip(1)?;
+                break;
+            }
+
+            values.push(self.consume_nested_value(nested_level + 1, false)?);
+
+            self.lexer.skip_whitespace(true)?;
+
+            match self.lexer.consume_byte()? {
+                b',' => continue,
+                b']' => break,
+                byte => return Err(unexpected_character(byte)),
+            }
+        }
+
+        Ok(IonValue::List(values))
+    }
+
+    fn consume_sexpr(&mut self, nested_level: u64) -> Result<IonValue, IonParserError> {
+        trace!("Consuming SExpr");
+
+        self.lexer.expect(b'(')?;
+
+        let mut values = Vec::new();
+
+        loop {
+            self.lexer.skip_whitespace(true)?;
+
+            if self.peek_or_fail()? == b')' {
+                self.lexer.skip(1)?;
+                break;
+            }
+
+            values.push(self.consume_nested_value(nested_level + 1, true)?);
+        }
+
+        Ok(IonValue::SExpr(values))
+    }
+
+    // Blobs and clobs don't allow comments between their delimiters.
+    fn consume_lob(&mut self) -> Result<IonValue, IonParserError> {
+        trace!("Consuming Lob");
+
+        self.lexer.skip(2)?;
+        self.lexer.skip_whitespace(false)?;
+
+        let value = match self.peek_or_fail()? {
+            b'"' => IonValue::Clob(self.lexer.consume_quoted(b'"', StringMode::Clob)?),
+            b'\'' => IonValue::Clob(self.consume_long_strings(StringMode::Clob)?),
+            _ => IonValue::Blob(self.lexer.consume_base64()?),
+        };
+
+        self.lexer.skip_whitespace(false)?;
+        self.lexer.expect(b'}')?;
+        self.lexer.expect(b'}')?;
+
+        Ok(value)
+    }
+
+    // Adjacent long strings are concatenated into a single value.
+    fn consume_long_strings(&mut self, mode: StringMode) -> Result<Vec<u8>, IonParserError> {
+        let mut buffer = Vec::new();
+        let skip_comments = mode == StringMode::Text;
+
+        loop {
+            buffer.append(&mut self.lexer.consume_long_string(mode)?);
+
+            self.lexer.skip_whitespace(skip_comments)?;
+
+            if !self.lexer.starts_with(b"'''")? {
+                return Ok(buffer);
+            }
+        }
+    }
+
+    fn consume_typed_null(&mut self) -> Result<IonValue, IonParserError> {
+        self.lexer.expect(b'.')?;
+
+        let null_type = self.lexer.consume_identifier()?;
+
+        let null_value = match null_type.as_str() {
+            "null" => NullIonValue::Null,
+            "bool" => NullIonValue::Bool,
+            "int" => NullIonValue::Integer,
+            "float" => NullIonValue::Float,
+            "decimal" => NullIonValue::Decimal,
+            "timestamp" => NullIonValue::DateTime,
+            "string" => NullIonValue::String,
+            "symbol" => NullIonValue::Symbol,
+            "blob" => NullIonValue::Blob,
+            "clob" => NullIonValue::Clob,
+            "struct" => NullIonValue::Struct,
+            "list" => NullIonValue::List,
+            "sexp" => NullIonValue::SExpr,
+            _ => {
+                return Err(IonParserError::TextError(
+                    TextParsingError::InvalidNullType(null_type),
+                ))
+            }
+        };
+
+        Ok(IonValue::Null(null_value))
+    }
+
+    fn consume_numeric(&mut self) -> Result<IonValue, IonParserError> {
+        let token = self.lexer.consume_numeric_token()?;
+
+        if is_timestamp_token(&token) {
+            parse_timestamp(&token)
+        } else {
+            parse_number(&token)
+        }
+    }
+
+    #[inline]
+    fn is_infinity(&mut self) -> Result<bool, IonParserError> {
+        if !(self.lexer.starts_with(b"+inf")? || self.lexer.starts_with(b"-inf")?) {
+            return Ok(false);
+        }
+
+        Ok(match self.lexer.peek_at(4)? {
+            None => true,
+            Some(byte) if is_value_stop_character(byte) => true,
+            Some(b'/') => matches!(self.lexer.peek_at(5)?, Some(b'/') | Some(b'*')),
+            Some(_) => false,
+        })
+    }
+
+    #[inline]
+    fn get_symbol_value(&self, symbol: TextSymbol) -> Result<IonValue, IonParserError> {
+        match symbol {
+            TextSymbol::Quoted(text) => Ok(IonValue::Symbol(text.into())),
+            TextSymbol::Identifier(identifier) => match identifier.as_str() {
+                "null" => Ok(IonValue::Null(NullIonValue::Null)),
+                "true" => Ok(IonValue::Bool(true)),
+                "false" => Ok(IonValue::Bool(false)),
+                "nan" => Ok(IonValue::Float(f64::NAN)),
+                _ => Ok(IonValue::Symbol(self.resolve_identifier(identifier)?)),
+            },
+        }
+    }
+
+    #[inline]
+    fn get_annotation(&self, symbol: TextSymbol) -> Result<SymbolToken, IonParserError> {
+        match symbol {
+            TextSymbol::Quoted(text) => Ok(text.into()),
+            TextSymbol::Identifier(identifier) if is_keyword(&identifier) => Err(
+                IonParserError::TextError(TextParsingError::KeywordAsAnnotation(identifier)),
+            ),
+            TextSymbol::Identifier(identifier) => self.resolve_identifier(identifier),
+        }
+    }
+
+    // Identifiers like `$10` are symbol ids that need to be resolved using the
+    // current symbol table. Their text is unknown for `$0` and for symbols of
+    // shared tables that are not available.
+    #[inline]
+    fn resolve_identifier(&self, identifier: String) -> Result<SymbolToken, IonParserError> {
+        let symbol_id = match identifier.strip_prefix('$') {
+            Some(id) if !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_digit()) => id,
+            _ => return Ok(identifier.into()),
+        };
+
+        let symbol_id: usize = symbol_id
+            .parse()
+            .map_err(|_| IonParserError::SymbolIdTooBig)?;
+
+        self.context
+            .get_symbol_token(symbol_id)
+            .ok_or(IonParserError::SymbolIdNotDefined)
+    }
+
+    #[inline]
+    fn peek_or_fail(&mut self) -> Result<u8, IonParserError> {
+        self.lexer.peek()?.ok_or(IonParserError::TextError(
+            TextParsingError::UnexpectedEndOfStream,
+        ))
+    }
+}
+
+#[inline]
+fn construct_element(annotations: Vec<SymbolToken>, value: IonValue) -> TextElement {
+    if annotations.is_empty() {
+        TextElement::Value(value)
+    } else {
+        TextElement::Value(IonValue::Annotation(annotations, Box::new(value)))
+    }
+}
+
+#[inline]
+fn is_symbol_table(annotations: &[SymbolToken], value: &IonValue) -> bool {
+    let is_table_annotation = annotations[0] == LOCAL_SYMBOL_TABLE_ANNOTATION
+        || annotations[0] == SHARED_SYMBOL_TABLE_ANNOTATION;
+
+    is_table_annotation && matches!(value, IonValue::Struct(_) | IonValue::OrderedStruct(_))
+}
+
+#[inline]
+fn is_keyword(identifier: &str) -> bool {
+    matches!(identifier, "null" | "true" | "false" | "nan")
+}
+
+// Version markers have the shape `$ion_<major>_<minor>`
+#[inline]
+fn is_version_marker(identifier: &str) -> bool {
+    let version = match identifier.strip_prefix("$ion_") {
+        Some(version) => version,
+        None => return false,
+    };
+
+    match version.split_once('_') {
+        Some((major, minor)) => {
+            !major.is_empty()
+                && !minor.is_empty()
+                && major.bytes().all(|byte| byte.is_ascii_digit())
+                && minor.bytes().all(|byte| byte.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+#[inline]
+fn bytes_to_string(bytes: Vec<u8>) -> Result<String, IonParserError> {
+    String::from_utf8(bytes).map_err(|_| IonParserError::NonUtf8String)
+}
+
+#[inline]
+fn is_timestamp_token(token: &str) -> bool {
+    let bytes = token.as_bytes();
+
+    bytes.len() >= 5
+        && bytes[..4].iter().all(|byte| byte.is_ascii_digit())
+        && (bytes[4] == b'-' || bytes[4] == b'T')
+}
+
+// Digits can be separated by single underscores, but they cannot start
+// or end with one.
+#[inline]
+fn is_valid_digits(digits: &str, radix: u32) -> bool {
+    !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && !digits.contains("__")
+        && digits.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+fn parse_number(token: &str) -> Result<IonValue, IonParserError> {
+    let invalid_number =
+        || IonParserError::TextError(TextParsingError::InvalidNumber(token.to_string()));
+
+    let (sign, unsigned) = match token.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", token),
+    };
+
+    let radix_digits = [("0x", 16), ("0X", 16), ("0b", 2), ("0B", 2)]
+        .iter()
+        .find_map(|(prefix, radix)| unsigned.strip_prefix(prefix).map(|digits| (digits, *radix)));
+
+    if let Some((digits, radix)) = radix_digits {
+        if !is_valid_digits(digits, radix) {
+            return Err(invalid_number());
+        }
+
+        let digits = format!("{}{}", sign, digits.replace('_', ""));
+        let value = BigInt::parse_bytes(digits.as_bytes(), radix).ok_or_else(invalid_number)?;
+
+        return Ok(big_int_to_ion_value(value));
+    }
+
+    let integer_end = unsigned
+        .find(|c: char| !(c.is_ascii_digit() || c == '_'))
+        .unwrap_or(unsigned.len());
+
+    let (integer, mut remaining) = unsigned.split_at(integer_end);
+
+    let has_leading_zeros = integer.len() > 1 && integer.starts_with('0');
+
+    if !is_valid_digits(integer, 10) || has_leading_zeros {
+        return Err(invalid_number());
+    }
+
+    let mut fraction = None;
+
+    if let Some(after_dot) = remaining.strip_prefix('.') {
+        let fraction_end = after_dot
+            .find(|c: char| !(c.is_ascii_digit() || c == '_'))
+            .unwrap_or(after_dot.len());
+
+        let (digits, rest) = after_dot.split_at(fraction_end);
+
+        if !digits.is_empty() && !is_valid_digits(digits, 10) {
+            return Err(invalid_number());
+        }
+
+        fraction = Some(digits.replace('_', ""));
+        remaining = rest;
+    }
+
+    let mut exponent = None;
+
+    if let Some(marker) = remaining.chars().next() {
+        if !matches!(marker, 'e' | 'E' | 'd' | 'D') {
+            return Err(invalid_number());
+        }
+
+        let exponent_text = &remaining[1..];
+        let (exponent_sign, exponent_digits) = match exponent_text.as_bytes().first() {
+            Some(b'-') => ("-", &exponent_text[1..]),
+            Some(b'+') => ("", &exponent_text[1..]),
+            _ => ("", exponent_text),
+        };
+
+        if !is_valid_digits(exponent_digits, 10) {
+            return Err(invalid_number());
+        }
+
+        let is_float = marker == 'e' || marker == 'E';
+        let exponent_digits = exponent_digits.replace('_', "");
+        exponent = Some((is_float, format!("{}{}", exponent_sign, exponent_digits)));
+    }
+
+    let integer = integer.replace('_', "");
+
+    match (fraction, exponent) {
+        (None, None) => {
+            let value =
+                BigInt::from_str(&format!("{}{}", sign, integer)).map_err(|_| invalid_number())?;
+            Ok(big_int_to_ion_value(value))
+        }
+        (fraction, Some((true, exponent))) => {
+            let fraction = fraction
+                .filter(|f| !f.is_empty())
+                .unwrap_or_else(|| "0".to_string());
+            let text = format!("{}{}.{}e{}", sign, integer, fraction, exponent);
+            let value = f64::from_str(&text).map_err(|_| invalid_number())?;
+            Ok(IonValue::Float(value))
+        }
+        (fraction, exponent) => {
+            let mut text = format!("{}{}", sign, integer);
+
+            if let Some(fraction) = fraction.filter(|f| !f.is_empty()) {
+                text.push('.');
+                text.push_str(&fraction);
+            }
+
+            if let Some((_, exponent)) = exponent {
+                text.push('e');
+                text.push_str(&exponent);
+            }
+
+            let value = BigDecimal::from_str(&text).map_err(|_| invalid_number())?;
+            Ok(IonValue::Decimal(value))
+        }
+    }
+}
+
+#[inline]
+fn big_int_to_ion_value(value: BigInt) -> IonValue {
+    match i64::try_from(&value) {
+        Ok(value) => IonValue::Integer(value),
+        Err(_) => IonValue::BigInteger(value),
+    }
+}
+
+// Timestamps can have year, month, day, minute, second or fractional second
+// precision. Values with time need an offset, where `-00:00` means unknown.
+fn parse_timestamp(token: &str) -> Result<IonValue, IonParserError> {
+    let invalid_timestamp =
+        || IonParserError::TextError(TextParsingError::InvalidTimestamp(token.to_string()));
+
+    let bytes = token.as_bytes();
+
+    let digits = |start: usize, len: usize| -> Result<u32, IonParserError> {
+        match bytes.get(start..start + len) {
+            Some(digits) if digits.iter().all(|byte| byte.is_ascii_digit()) => Ok(digits
+                .iter()
+                .fold(0, |value, digit| value * 10 + u32::from(digit - b'0'))),
+            _ => Err(invalid_timestamp()),
+        }
+    };
+
+    let expect = |index: usize, expected: u8| -> Result<(), IonParserError> {
+        match bytes.get(index) {
+            Some(byte) if *byte == expected => Ok(()),
+            _ => Err(invalid_timestamp()),
+        }
+    };
+
+    let year = digits(0, 4)?;
+    let mut month = 1;
+    let mut day = 1;
+    let mut precision = TimestampPrecision::Day;
+
+    let is_date_only = match bytes[4] {
+        b'T' => {
+            precision = TimestampPrecision::Year;
+            bytes.len() == 5
+        }
+        _ => {
+            month = digits(5, 2)?;
+
+            match bytes.get(7) {
+                Some(b'T') => {
+                    precision = TimestampPrecision::Month;
+                    bytes.len() == 8
+                }
+                _ => {
+                    expect(7, b'-')?;
+                    day = digits(8, 2)?;
+                    bytes.len() == 10 || (bytes.len() == 11 && bytes[10] == b'T')
+                }
+            }
+        }
+    };
+
+    if year == 0 {
+        return Err(invalid_timestamp());
+    }
+
+    let timestamp = IonTimestamp::from_ymd(year, month, day).map_err(|_| invalid_timestamp())?;
+
+    if is_date_only {
+        return Ok(IonValue::DateTime(timestamp.with_precision(precision)));
+    }
+
+    expect(4, b'-')?;
+    expect(7, b'-')?;
+    expect(10, b'T')?;
+    let hour = digits(11, 2)?;
+    expect(13, b':')?;
+    let minute = digits(14, 2)?;
+    let mut index = 16;
+    let mut second = 0;
+    let mut fraction = None;
+
+    precision = TimestampPrecision::Minute;
+
+    if bytes.get(index) == Some(&b':') {
+        second = digits(index + 1, 2)?;
+        index += 3;
+        precision = TimestampPrecision::Second;
+
+        if bytes.get(index) == Some(&b'.') {
+            index += 1;
+            let fraction_start = index;
+
+            while bytes.get(index).is_some_and(|byte| byte.is_ascii_digit()) {
+                index += 1;
+            }
+
+            if index == fraction_start {
+                return Err(invalid_timestamp());
+            }
+
+            let fraction_text = format!("0.{}", &token[fraction_start..index]);
+            fraction = Some(BigDecimal::from_str(&fraction_text).map_err(|_| invalid_timestamp())?);
+        }
+    }
+
+    let offset = match bytes.get(index) {
+        Some(b'Z') if bytes.len() == index + 1 => Some(0),
+        Some(sign @ (b'+' | b'-')) if bytes.len() == index + 6 => {
+            let offset_hours = digits(index + 1, 2)?;
+            expect(index + 3, b':')?;
+            let offset_minute = digits(index + 4, 2)?;
+
+            if offset_hours >= 24 || offset_minute >= 60 {
+                return Err(invalid_timestamp());
+            }
+
+            let offset_minutes = (offset_hours * 60 + offset_minute) as i32;
+
+            match sign {
+                b'-' if offset_minutes == 0 => None,
+                b'-' => Some(-offset_minutes),
+                _ => Some(offset_minutes),
+            }
+        }
+        _ => return Err(invalid_timestamp()),
+    };
+
+    let mut timestamp = IonTimestamp::from_ymd_hms(year, month, day, hour, minute, second, offset)
+        .map_err(|_| invalid_timestamp())?
+        .with_precision(precision);
+
+    if let Some(fraction) = fraction {
+        timestamp = timestamp
+            .with_fraction(fraction)
+            .map_err(|_| invalid_timestamp())?;
+    }
+
+    Ok(IonValue::DateTime(timestamp))
+}
@@ -0,0 +1,470 @@
+use crate::text_encoder::encode_text_datetime;
+use crate::IonParserError;
+use bigdecimal::{BigDecimal, One, Signed, ToPrimitive};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Timelike, Utc};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+const MAX_OFFSET_MINUTES: i32 = 24 * 60;
+
+/// Last component defined by an IonTimestamp. Components after it are not
+/// part of the timestamp, so `2007T` (year precision) and `2007-01-01`
+/// (day precision) are different Ion values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimestampPrecision {
+    Year,
+    Month,
+    Day,
+    Minute,
+    Second,
+}
+
+/// An Ion timestamp keeping everything that the Ion format can represent,
+/// unlike chrono's DateTime:
+///
+/// - The precision: `2007T`, `2007-02T`, `2007-02-23`, `2007-02-23T12:14Z`
+///   and `2007-02-23T12:14:00Z` are all different.
+/// - The unknown offset (`-00:00`), which is not the same as UTC (`Z`).
+/// - The fractional seconds as a BigDecimal, with any precision, so `.1`
+///   and `.100` are different too.
+///
+/// Components are kept as they are written in the text format, so they
+/// are local to the offset. Only timestamps with minute or second precision
+/// have an offset, for the rest it is always unknown.
+///
+/// It converts from and to chrono's DateTime. Converting to a DateTime is
+/// lossy, as precision is lost, unknown offsets become UTC and fractions are
+/// truncated to nanoseconds.
+///
+/// ```rust
+/// use ion_binary_rs::{IonTimestamp, TimestampPrecision};
+/// use bigdecimal::BigDecimal;
+/// use std::str::FromStr;
+///
+/// let timestamp = IonTimestamp::from_ymd_hms(2007, 2, 23, 12, 14, 33, None)
+///     .unwrap()
+///     .with_fraction(BigDecimal::from_str("0.100").unwrap())
+///     .unwrap();
+///
+/// assert_eq!(timestamp.precision(), TimestampPrecision::Second);
+/// assert_eq!(timestamp.to_string(), "2007-02-23T12:14:33.100-00:00");
+///
+/// let day = timestamp.with_precision(TimestampPrecision::Day);
+///
+/// assert_eq!(day.to_string(), "2007-02-23");
+/// assert_eq!(day.to_datetime().to_rfc3339(), "2007-02-23T00:00:00+00:00");
+/// ```
+#[derive(Debug, Clone)]
+pub struct IonTimestamp {
+    precision: TimestampPrecision,
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    fraction: Option<BigDecimal>,
+    offset: Option<i32>,
+}
+
+impl IonTimestamp {
+    /// Timestamp with year precision, like `2007T`.
+    pub fn from_year(year: u32) -> Result<IonTimestamp, IonParserError> {
+        IonTimestamp::from_ymd(year, 1, 1)
+            .map(|value| value.with_precision(TimestampPrecision::Year))
+    }
+
+    /// Timestamp with month precision, like `2007-02T`.
+    pub fn from_year_month(year: u32, month: u32) -> Result<IonTimestamp, IonParserError> {
+        IonTimestamp::from_ymd(year, month, 1)
+            .map(|value| value.with_precision(TimestampPrecision::Month))
+    }
+
+    /// Timestamp with day precision, like `2007-02-23`.
+    pub fn from_ymd(year: u32, month: u32, day: u32) -> Result<IonTimestamp, IonParserError> {
+        let timestamp = IonTimestamp {
+            precision: TimestampPrecision::Day,
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            fraction: None,
+            offset: None,
+        };
+
+        timestamp.validate()?;
+
+        Ok(timestamp)
+    }
+
+    /// Timestamp with second precision, like `2007-02-23T12:14:33Z`. The
+    /// offset is in minutes, None being the unknown offset (`-00:00`).
+    ///
+    /// Use `with_precision` for a minute precision timestamp and
+    /// `with_fraction` in order to add fractional seconds.
+    pub fn from_ymd_hms(
+        year: u32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        offset: Option<i32>,
+    ) -> Result<IonTimestamp, IonParserError> {
+        let timestamp = IonTimestamp {
+            precision: TimestampPrecision::Second,
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            fraction: None,
+            offset,
+        };
+
+        timestamp.validate()?;
+
+        Ok(timestamp)
+    }
+
+    /// Changes the precision of the timestamp. Components after the new
+    /// precision are dropped, and the offset too if the new precision is
+    /// lower than minutes.
+    pub fn with_precision(mut self, precision: TimestampPrecision) -> IonTimestamp {
+        self.precision = precision;
+
+        if precision < TimestampPrecision::Second {
+            self.second = 0;
+            self.fraction = None;
+        }
+
+        if precision < TimestampPrecision::Minute {
+            self.hour = 0;
+            self.minute = 0;
+            self.offset = None;
+        }
+
+        if precision < TimestampPrecision::Day {
+            self.day = 1;
+        }
+
+        if precision < TimestampPrecision::Month {
+            self.month = 1;
+        }
+
+        self
+    }
+
+    /// Adds fractional seconds, which need to be in the range [0, 1). The
+    /// scale of the decimal is kept, so `0.1` and `0.100` are different.
+    /// The timestamp gets second precision.
+    pub fn with_fraction(mut self, fraction: BigDecimal) -> Result<IonTimestamp, IonParserError> {
+        if fraction.is_negative() {
+            return Err(IonParserError::DateNegativeSecondFraction);
+        }
+
+        if fraction >= BigDecimal::one() {
+            return Err(IonParserError::DateSecondFractionOverflow);
+        }
+
+        self.precision = TimestampPrecision::Second;
+        self.fraction = Some(fraction);
+
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn precision(&self) -> TimestampPrecision {
+        self.precision
+    }
+
+    #[inline]
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    #[inline]
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    #[inline]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    #[inline]
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    #[inline]
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+
+    #[inline]
+    pub fn second(&self) -> u32 {
+        self.second
+    }
+
+    /// Fractional seconds, if the timestamp has them.
+    #[inline]
+    pub fn fraction(&self) -> Option<&BigDecimal> {
+        self.fraction.as_ref()
+    }
+
+    /// Offset in minutes. None means unknown offset (`-00:00`).
+    #[inline]
+    pub fn offset(&self) -> Option<i32> {
+        self.offset
+    }
+
+    /// Converts the timestamp to a chrono's DateTime. Unknown offsets are
+    /// taken as UTC and the fraction is truncated to nanoseconds.
+    pub fn to_datetime(&self) -> DateTime<FixedOffset> {
+        let nanoseconds = self
+            .fraction
+            .as_ref()
+            .and_then(|fraction| (fraction * BigDecimal::from(1_000_000_000)).to_u32())
+            .unwrap_or(0);
+
+        // Components are validated when the timestamp is built, so this
+        // cannot fail.
+        let datetime = NaiveDate::from_ymd_opt(self.year as i32, self.month, self.day)
+            .and_then(|date| {
+                date.and_hms_nano_opt(self.hour, self.minute, self.second, nanoseconds)
+            })
+            .expect("IonTimestamp with invalid components");
+
+        let offset = FixedOffset::east_opt(self.offset.unwrap_or(0) * 60)
+            .expect("IonTimestamp with invalid offset");
+
+        offset
+            .from_local_datetime(&datetime)
+            .single()
+            .expect("IonTimestamp with invalid offset")
+    }
+
+    // The binary format stores the components in UTC, so they need to be
+    // moved to the offset when the timestamp has a time.
+    pub(crate) fn from_utc_components(
+        precision: TimestampPrecision,
+        components: [u32; 6],
+        fraction: Option<BigDecimal>,
+        offset: Option<i32>,
+    ) -> Result<IonTimestamp, IonParserError> {
+        let [year, month, day, hour, minute, second] = components;
+
+        let mut timestamp = IonTimestamp {
+            precision,
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            fraction: None,
+            offset: None,
+        };
+
+        if precision >= TimestampPrecision::Minute {
+            let offset_minutes = offset.unwrap_or(0);
+
+            if offset_minutes.abs() >= MAX_OFFSET_MINUTES {
+                return Err(timestamp.invalid_date_error());
+            }
+
+            let local = NaiveDate::from_ymd_opt(year as i32, month, day)
+                .and_then(|date| date.and_hms_opt(hour, minute, 0))
+                .and_then(|datetime| {
+                    datetime.checked_add_signed(Duration::minutes(offset_minutes.into()))
+                })
+                .ok_or_else(|| timestamp.invalid_date_error())?;
+
+            timestamp.year = local.year() as u32;
+            timestamp.month = local.month();
+            timestamp.day = local.day();
+            timestamp.hour = local.hour();
+            timestamp.minute = local.minute();
+            timestamp.offset = offset;
+        }
+
+        timestamp.validate()?;
+
+        match fraction {
+            Some(fraction) => timestamp.with_fraction(fraction),
+            None => Ok(timestamp),
+        }
+    }
+
+    pub(crate) fn utc_components(&self) -> [u32; 6] {
+        if self.precision < TimestampPrecision::Minute {
+            return [self.year, self.month, self.day, 0, 0, 0];
+        }
+
+        let offset = Duration::minutes(self.offset.unwrap_or(0).into());
+
+        let utc = NaiveDate::from_ymd_opt(self.year as i32, self.month, self.day)
+            .and_then(|date| date.and_hms_opt(self.hour, self.minute, 0))
+            .and_then(|datetime| datetime.checked_sub_signed(offset))
+            .expect("IonTimestamp with invalid components");
+
+        [
+            utc.year() as u32,
+            utc.month(),
+            utc.day(),
+            utc.hour(),
+            utc.minute(),
+            self.second,
+        ]
+    }
+
+    fn validate(&self) -> Result<(), IonParserError> {
+        let is_valid_date = self.year <= 9999
+            && NaiveDate::from_ymd_opt(self.year as i32, self.month, self.day).is_some();
+
+        let is_valid_time = self.hour < 24 && self.minute < 60 && self.second < 60;
+
+        let is_valid_offset = self
+            .offset
+            .is_none_or(|offset| offset.abs() < MAX_OFFSET_MINUTES);
+
+        if is_valid_date && is_valid_time && is_valid_offset {
+            Ok(())
+        } else {
+            Err(self.invalid_date_error())
+        }
+    }
+
+    fn invalid_date_error(&self) -> IonParserError {
+        IonParserError::InvalidDate(
+            self.year as i32,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            0,
+        )
+    }
+
+    fn fraction_representation(&self) -> Option<(num_bigint::BigInt, i64)> {
+        self.fraction
+            .as_ref()
+            .map(|fraction| fraction.as_bigint_and_exponent())
+    }
+}
+
+// Two timestamps are equal when they have the same precision, components
+// and offset, and the same fraction including its scale.
+impl PartialEq for IonTimestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.precision == other.precision
+            && self.year == other.year
+            && self.month == other.month
+            && self.day == other.day
+            && self.hour == other.hour
+            && self.minute == other.minute
+            && self.second == other.second
+            && self.offset == other.offset
+            && self.fraction_representation() == other.fraction_representation()
+    }
+}
+
+impl Eq for IonTimestamp {}
+
+// Timestamps are ordered by the point in time they represent. The ones at
+// the same point in time are ordered by precision, offset and the scale of
+// their fraction, so the order is consistent with `eq`.
+impl Ord for IonTimestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_datetime()
+            .cmp(&other.to_datetime())
+            .then_with(|| self.fraction.cmp(&other.fraction))
+            .then_with(|| self.precision.cmp(&other.precision))
+            .then_with(|| self.offset.cmp(&other.offset))
+            .then_with(|| {
+                let scale = |timestamp: &IonTimestamp| {
+                    timestamp
+                        .fraction_representation()
+                        .map(|(_, exponent)| exponent)
+                };
+
+                scale(self).cmp(&scale(other))
+            })
+    }
+}
+
+impl PartialOrd for IonTimestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for IonTimestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut buffer = String::new();
+        encode_text_datetime(&mut buffer, self);
+        f.write_str(&buffer)
+    }
+}
+
+// DateTimes become timestamps with second precision and the shortest
+// fraction that keeps their nanoseconds.
+impl From<DateTime<FixedOffset>> for IonTimestamp {
+    fn from(value: DateTime<FixedOffset>) -> IonTimestamp {
+        let mut nanosecond = value.nanosecond();
+
+        // Accounting for the case of a leap second, which shouldn't ever happen.
+        // https://docs.rs/chrono/0.4.19/chrono/naive/struct.NaiveTime.html#leap-second-handling
+        if nanosecond >= 1_000_000_000 {
+            nanosecond -= 1_000_000_000;
+        }
+
+        let fraction = if nanosecond > 0 {
+            Some(BigDecimal::new(nanosecond.into(), 9).normalized())
+        } else {
+            None
+        };
+
+        IonTimestamp {
+            precision: TimestampPrecision::Second,
+            year: value.year() as u32,
+            month: value.month(),
+            day: value.day(),
+            hour: value.hour(),
+            minute: value.minute(),
+            second: value.second(),
+            fraction,
+            offset: Some(value.offset().local_minus_utc() / 60),
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for IonTimestamp {
+    fn from(value: DateTime<Utc>) -> IonTimestamp {
+        IonTimestamp::from(DateTime::<FixedOffset>::from(value))
+    }
+}
+
+impl From<IonTimestamp> for DateTime<FixedOffset> {
+    fn from(value: IonTimestamp) -> DateTime<FixedOffset> {
+        value.to_datetime()
+    }
+}
+
+impl From<&IonTimestamp> for DateTime<FixedOffset> {
+    fn from(value: &IonTimestamp) -> DateTime<FixedOffset> {
+        value.to_datetime()
+    }
+}
+
+impl From<IonTimestamp> for DateTime<Utc> {
+    fn from(value: IonTimestamp) -> DateTime<Utc> {
+        value.to_datetime().with_timezone(&Utc)
+    }
+}
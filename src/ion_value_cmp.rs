@@ -0,0 +1,169 @@
+use crate::ion_parser_types::{IonValue, NullIonValue};
+use crate::symbol_token::{ImportLocation, SymbolToken};
+use num_bigint::BigInt;
+use std::cmp::Ordering;
+
+impl IonValue {
+    /// Whether both values are equivalent following the equivalence rules
+    /// of the Ion data model. It is what `==` uses.
+    ///
+    /// - Values need to have the same Ion type and annotations, in the same
+    ///   order. `Integer` and `BigInteger` are the same type, as are
+    ///   `Struct` and `OrderedStruct`.
+    /// - Nulls need to have the same type.
+    /// - Floats are equivalent if both are NaN or if they have the same
+    ///   value and sign, so `0e0` and `-0e0` are not equivalent.
+    /// - Decimals need the same coefficient and exponent, so `1.0` and
+    ///   `1.00` are not equivalent.
+    /// - Timestamps need the same point in time, precision and offset, with
+    ///   fractions of the same scale.
+    /// - Structs need the same fields, compared as a multiset of name and
+    ///   value pairs, so repeated fields count.
+    ///
+    /// ```rust
+    /// use ion_binary_rs::IonValue;
+    /// use bigdecimal::BigDecimal;
+    /// use num_bigint::BigInt;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(IonValue::Integer(5).ion_eq(&IonValue::BigInteger(BigInt::from(5))));
+    /// assert!(IonValue::Float(f64::NAN).ion_eq(&IonValue::Float(f64::NAN)));
+    /// assert!(!IonValue::Float(0.0).ion_eq(&IonValue::Float(-0.0)));
+    ///
+    /// let one = BigDecimal::from_str("1.0").unwrap();
+    /// let other_one = BigDecimal::from_str("1.00").unwrap();
+    ///
+    /// assert!(!IonValue::Decimal(one).ion_eq(&IonValue::Decimal(other_one)));
+    /// ```
+    pub fn ion_eq(&self, other: &IonValue) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialEq for IonValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.ion_eq(other)
+    }
+}
+
+impl Eq for IonValue {}
+
+// Values are ordered by type (null, bool, int, float, decimal, timestamp,
+// symbol, string, clob, blob, list, sexp and struct), with the typed nulls
+// before the rest of the values of their type, then by value and then by
+// annotations. Values are only equal when they are equivalent.
+impl Ord for IonValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (annotations, value) = split_annotations(self);
+        let (other_annotations, other_value) = split_annotations(other);
+
+        cmp_values(value, other_value).then_with(|| annotations.cmp(&other_annotations))
+    }
+}
+
+impl PartialOrd for IonValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn split_annotations(mut value: &IonValue) -> (Vec<&SymbolToken>, &IonValue) {
+    let mut annotations = vec![];
+
+    while let IonValue::Annotation(value_annotations, inner) = value {
+        annotations.extend(value_annotations);
+        value = inner;
+    }
+
+    (annotations, value)
+}
+
+fn type_rank(value: &IonValue) -> u8 {
+    match value {
+        // Both are written as an untyped null
+        IonValue::Null(NullIonValue::Null | NullIonValue::Annotation) => 0,
+        IonValue::Null(NullIonValue::Bool) | IonValue::Bool(_) => 1,
+        IonValue::Null(NullIonValue::Integer) | IonValue::Integer(_) | IonValue::BigInteger(_) => 2,
+        IonValue::Null(NullIonValue::Float) | IonValue::Float(_) => 3,
+        IonValue::Null(NullIonValue::Decimal) | IonValue::Decimal(_) => 4,
+        IonValue::Null(NullIonValue::DateTime) | IonValue::DateTime(_) => 5,
+        IonValue::Null(NullIonValue::Symbol) | IonValue::Symbol(_) => 6,
+        IonValue::Null(NullIonValue::String) | IonValue::String(_) => 7,
+        IonValue::Null(NullIonValue::Clob) | IonValue::Clob(_) => 8,
+        IonValue::Null(NullIonValue::Blob) | IonValue::Blob(_) => 9,
+        IonValue::Null(NullIonValue::List) | IonValue::List(_) => 10,
+        IonValue::Null(NullIonValue::SExpr) | IonValue::SExpr(_) => 11,
+        IonValue::Null(NullIonValue::Struct) | IonValue::Struct(_) | IonValue::OrderedStruct(_) => {
+            12
+        }
+        IonValue::Annotation(_, value) => type_rank(value),
+    }
+}
+
+// Both values need to be without annotations
+fn cmp_values(value: &IonValue, other: &IonValue) -> Ordering {
+    let rank = type_rank(value).cmp(&type_rank(other));
+
+    if rank != Ordering::Equal {
+        return rank;
+    }
+
+    match (value, other) {
+        (IonValue::Null(_), IonValue::Null(_)) => Ordering::Equal,
+        (IonValue::Null(_), _) => Ordering::Less,
+        (_, IonValue::Null(_)) => Ordering::Greater,
+        (IonValue::Bool(value), IonValue::Bool(other)) => value.cmp(other),
+        (IonValue::Integer(value), IonValue::Integer(other)) => value.cmp(other),
+        (IonValue::Integer(value), IonValue::BigInteger(other)) => BigInt::from(*value).cmp(other),
+        (IonValue::BigInteger(value), IonValue::Integer(other)) => value.cmp(&BigInt::from(*other)),
+        (IonValue::BigInteger(value), IonValue::BigInteger(other)) => value.cmp(other),
+        (IonValue::Float(value), IonValue::Float(other)) => cmp_floats(*value, *other),
+        (IonValue::Decimal(value), IonValue::Decimal(other)) => value.cmp(other).then_with(|| {
+            let (_, scale) = value.as_bigint_and_exponent();
+            let (_, other_scale) = other.as_bigint_and_exponent();
+
+            scale.cmp(&other_scale)
+        }),
+        (IonValue::DateTime(value), IonValue::DateTime(other)) => value.cmp(other),
+        (IonValue::Symbol(value), IonValue::Symbol(other)) => value.cmp(other),
+        (IonValue::String(value), IonValue::String(other)) => value.cmp(other),
+        (IonValue::Clob(value), IonValue::Clob(other))
+        | (IonValue::Blob(value), IonValue::Blob(other)) => value.cmp(other),
+        (IonValue::List(value), IonValue::List(other))
+        | (IonValue::SExpr(value), IonValue::SExpr(other)) => value.cmp(other),
+        _ => sorted_fields(value).cmp(&sorted_fields(other)),
+    }
+}
+
+// All NaNs are equivalent and go after the rest of the floats. Zeros keep
+// their sign, so -0e0 goes before 0e0.
+fn cmp_floats(value: f64, other: f64) -> Ordering {
+    match (value.is_nan(), other.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => value.total_cmp(&other),
+    }
+}
+
+type FieldKey<'a> = Result<&'a str, Option<&'a ImportLocation>>;
+
+// Structs are compared as multisets of fields, so the fields are sorted
+// before comparing them. Names with text go first, as in SymbolToken, and
+// the ones without it are compared by their import location.
+fn sorted_fields(value: &IonValue) -> Vec<(FieldKey<'_>, &IonValue)> {
+    let mut fields: Vec<(FieldKey, &IonValue)> = match value {
+        IonValue::Struct(fields) => fields
+            .iter()
+            .map(|(name, value)| (Ok(name.as_str()), value))
+            .collect(),
+        IonValue::OrderedStruct(fields) => fields
+            .iter()
+            .map(|(name, value)| (name.text().ok_or(name.import_location()), value))
+            .collect(),
+        _ => unreachable!("Only structs have fields"),
+    };
+
+    fields.sort();
+    fields
+}
@@ -1,4 +1,6 @@
-use crate::{IonExtractionError, IonParserError, IonValue, NullIonValue, SerdeJsonParseError};
+use crate::{
+    IonExtractionError, IonParserError, IonTimestamp, IonValue, NullIonValue, SerdeJsonParseError,
+};
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, FixedOffset, Utc};
 use num_bigint::{BigInt, BigUint};
@@ -13,6 +15,15 @@ impl TryFrom<IonValue> for std::collections::HashMap<String, IonValue> {
     fn try_from(value: IonValue) -> Result<Self, Self::Error> {
         match value {
             IonValue::Struct(value) => Ok(value),
+            // Field names without text can't be keys of the map
+            IonValue::OrderedStruct(values)
+                if values.iter().all(|(name, _)| !name.is_unknown()) =>
+            {
+                Ok(values
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name.into_text()?, value)))
+                    .collect())
+            }
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -25,7 +36,10 @@ impl TryFrom<IonValue> for String {
 
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::String(value) | IonValue::Symbol(value) => Ok(value),
+            IonValue::String(value) => Ok(value),
+            IonValue::Symbol(symbol) if !symbol.is_unknown() => {
+                Ok(symbol.into_text().unwrap_or_default())
+            }
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -200,7 +214,7 @@ impl TryFrom<IonValue> for DateTime<Utc> {
 
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::DateTime(value) => Ok(value.with_timezone(&Utc)),
+            IonValue::DateTime(value) => Ok(value.to_datetime().with_timezone(&Utc)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -211,6 +225,19 @@ impl TryFrom<IonValue> for DateTime<Utc> {
 impl TryFrom<IonValue> for DateTime<FixedOffset> {
     type Error = IonParserError;
 
+    fn try_from(value: IonValue) -> Result<Self, IonParserError> {
+        match value {
+            IonValue::DateTime(value) => Ok(value.to_datetime()),
+            _ => Err(ValueExtractionFailure(
+                IonExtractionError::TypeNotSupported(value),
+            )),
+        }
+    }
+}
+
+impl TryFrom<IonValue> for IonTimestamp {
+    type Error = IonParserError;
+
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::DateTime(value) => Ok(value),
@@ -289,6 +316,18 @@ impl TryFrom<IonValue> for serde_json::Value {
                 }
                 Ok(Value::Object(result_map))
             }
+            IonValue::OrderedStruct(values) => {
+                let mut result_map = serde_json::Map::with_capacity(values.len());
+
+                for (key, ion_value) in values {
+                    let key = key
+                        .into_text()
+                        .ok_or(IonParserError::SymbolNotFoundInTable)?;
+
+                    result_map.insert(key, Value::try_from(ion_value)?);
+                }
+                Ok(Value::Object(result_map))
+            }
             _ => Err(IonParserError::TypeNotSupported(value)),
         }
     }
@@ -311,10 +350,14 @@ impl TryFrom<&IonValue> for Vec<IonValue> {
 impl TryFrom<&IonValue> for HashMap<String, IonValue> {
     type Error = ();
     fn try_from(value: &IonValue) -> Result<Self, Self::Error> {
-        if let IonValue::Struct(value) = value {
-            Ok(value.clone())
-        } else {
-            Err(())
+        match value {
+            IonValue::Struct(value) => Ok(value.clone()),
+            IonValue::OrderedStruct(values) => values
+                .iter()
+                .map(|(name, value)| Some((name.text()?.to_string(), value.clone())))
+                .collect::<Option<_>>()
+                .ok_or(()),
+            _ => Err(()),
         }
     }
 }
@@ -324,7 +367,10 @@ impl TryFrom<&IonValue> for String {
 
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::String(value) | IonValue::Symbol(value) => Ok(value.clone()),
+            IonValue::String(value) => Ok(value.clone()),
+            IonValue::Symbol(symbol) if !symbol.is_unknown() => {
+                Ok(symbol.text().unwrap_or_default().to_string())
+            }
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -481,7 +527,7 @@ impl TryFrom<&IonValue> for DateTime<Utc> {
 
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::DateTime(value) => Ok(value.with_timezone(&Utc)),
+            IonValue::DateTime(value) => Ok(value.to_datetime().with_timezone(&Utc)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -494,7 +540,20 @@ impl TryFrom<&IonValue> for DateTime<FixedOffset> {
 
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::DateTime(value) => Ok(*value),
+            IonValue::DateTime(value) => Ok(value.to_datetime()),
+            _ => Err(ValueExtractionFailure(
+                IonExtractionError::TypeNotSupported(value.clone()),
+            )),
+        }
+    }
+}
+
+impl TryFrom<&IonValue> for IonTimestamp {
+    type Error = IonParserError;
+
+    fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
+        match value {
+            IonValue::DateTime(value) => Ok(value.clone()),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -614,6 +673,12 @@ impl From<BigUint> for IonValue {
 
 impl From<DateTime<FixedOffset>> for IonValue {
     fn from(value: DateTime<FixedOffset>) -> IonValue {
+        IonValue::DateTime(value.into())
+    }
+}
+
+impl From<IonTimestamp> for IonValue {
+    fn from(value: IonTimestamp) -> IonValue {
         IonValue::DateTime(value)
     }
 }
@@ -0,0 +1,176 @@
+use crate::ion_timestamp::IonTimestamp;
+use crate::symbol_token::SymbolToken;
+use crate::{IonValue, NullIonValue};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Same as `IonValue`, but strings, clobs and blobs can borrow their
+/// content from the buffer they were parsed from. It is returned by the
+/// `IonSliceParser`, which never copies them.
+///
+/// Symbols, annotations and field names are resolved in the symbol tables,
+/// so they are not part of the input and are owned as in `IonValue`.
+///
+/// Use `to_owned` or `into_owned` in order to get an `IonValue` that does
+/// not depend on the input buffer.
+///
+/// ```rust
+/// use ion_binary_rs::{IonEncoder, IonSliceParser, IonValue, IonValueRef};
+///
+/// let mut encoder = IonEncoder::new();
+/// encoder.add(IonValue::String("borrowed".into()));
+/// let bytes = encoder.encode();
+///
+/// let mut parser = IonSliceParser::new(&bytes);
+/// let (value, _) = parser.consume_value().unwrap();
+///
+/// assert_eq!(value, IonValueRef::String("borrowed".into()));
+/// assert_eq!(value.to_owned(), IonValue::String("borrowed".into()));
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+pub enum IonValueRef<'a> {
+    Null(NullIonValue),
+    Bool(bool),
+    Integer(i64),
+    BigInteger(BigInt),
+    Float(f64),
+    Decimal(BigDecimal),
+    DateTime(IonTimestamp),
+    String(Cow<'a, str>),
+    Symbol(SymbolToken),
+    Clob(Cow<'a, [u8]>),
+    Blob(Cow<'a, [u8]>),
+    List(Vec<IonValueRef<'a>>),
+    SExpr(Vec<IonValueRef<'a>>),
+    Struct(HashMap<String, IonValueRef<'a>>),
+    OrderedStruct(Vec<(SymbolToken, IonValueRef<'a>)>),
+    Annotation(Vec<SymbolToken>, Box<IonValueRef<'a>>),
+}
+
+impl Eq for IonValueRef<'_> {}
+
+impl IonValueRef<'_> {
+    /// Copies the value into an `IonValue`, leaving this one untouched.
+    pub fn to_owned(&self) -> IonValue {
+        match self {
+            IonValueRef::Null(null) => IonValue::Null(null.clone()),
+            IonValueRef::Bool(value) => IonValue::Bool(*value),
+            IonValueRef::Integer(value) => IonValue::Integer(*value),
+            IonValueRef::BigInteger(value) => IonValue::BigInteger(value.clone()),
+            IonValueRef::Float(value) => IonValue::Float(*value),
+            IonValueRef::Decimal(value) => IonValue::Decimal(value.clone()),
+            IonValueRef::DateTime(value) => IonValue::DateTime(value.clone()),
+            IonValueRef::String(value) => IonValue::String(value.to_string()),
+            IonValueRef::Symbol(value) => IonValue::Symbol(value.clone()),
+            IonValueRef::Clob(value) => IonValue::Clob(value.to_vec()),
+            IonValueRef::Blob(value) => IonValue::Blob(value.to_vec()),
+            IonValueRef::List(values) => {
+                IonValue::List(values.iter().map(IonValueRef::to_owned).collect())
+            }
+            IonValueRef::SExpr(values) => {
+                IonValue::SExpr(values.iter().map(IonValueRef::to_owned).collect())
+            }
+            IonValueRef::Struct(fields) => IonValue::Struct(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_owned()))
+                    .collect(),
+            ),
+            IonValueRef::OrderedStruct(fields) => IonValue::OrderedStruct(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_owned()))
+                    .collect(),
+            ),
+            IonValueRef::Annotation(annotations, value) => {
+                IonValue::Annotation(annotations.clone(), Box::new(IonValueRef::to_owned(value)))
+            }
+        }
+    }
+
+    /// Same as `to_owned`, but consuming the value, so the parts that are
+    /// already owned are moved instead of copied.
+    pub fn into_owned(self) -> IonValue {
+        match self {
+            IonValueRef::Null(null) => IonValue::Null(null),
+            IonValueRef::Bool(value) => IonValue::Bool(value),
+            IonValueRef::Integer(value) => IonValue::Integer(value),
+            IonValueRef::BigInteger(value) => IonValue::BigInteger(value),
+            IonValueRef::Float(value) => IonValue::Float(value),
+            IonValueRef::Decimal(value) => IonValue::Decimal(value),
+            IonValueRef::DateTime(value) => IonValue::DateTime(value),
+            IonValueRef::String(value) => IonValue::String(value.into_owned()),
+            IonValueRef::Symbol(value) => IonValue::Symbol(value),
+            IonValueRef::Clob(value) => IonValue::Clob(value.into_owned()),
+            IonValueRef::Blob(value) => IonValue::Blob(value.into_owned()),
+            IonValueRef::List(values) => {
+                IonValue::List(values.into_iter().map(IonValueRef::into_owned).collect())
+            }
+            IonValueRef::SExpr(values) => {
+                IonValue::SExpr(values.into_iter().map(IonValueRef::into_owned).collect())
+            }
+            IonValueRef::Struct(fields) => IonValue::Struct(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, value.into_owned()))
+                    .collect(),
+            ),
+            IonValueRef::OrderedStruct(fields) => IonValue::OrderedStruct(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, value.into_owned()))
+                    .collect(),
+            ),
+            IonValueRef::Annotation(annotations, value) => {
+                IonValue::Annotation(annotations, Box::new(value.into_owned()))
+            }
+        }
+    }
+}
+
+impl<'a> From<IonValueRef<'a>> for IonValue {
+    fn from(value: IonValueRef<'a>) -> IonValue {
+        value.into_owned()
+    }
+}
+
+impl From<IonValue> for IonValueRef<'static> {
+    fn from(value: IonValue) -> IonValueRef<'static> {
+        match value {
+            IonValue::Null(null) => IonValueRef::Null(null),
+            IonValue::Bool(value) => IonValueRef::Bool(value),
+            IonValue::Integer(value) => IonValueRef::Integer(value),
+            IonValue::BigInteger(value) => IonValueRef::BigInteger(value),
+            IonValue::Float(value) => IonValueRef::Float(value),
+            IonValue::Decimal(value) => IonValueRef::Decimal(value),
+            IonValue::DateTime(value) => IonValueRef::DateTime(value),
+            IonValue::String(value) => IonValueRef::String(Cow::Owned(value)),
+            IonValue::Symbol(value) => IonValueRef::Symbol(value),
+            IonValue::Clob(value) => IonValueRef::Clob(Cow::Owned(value)),
+            IonValue::Blob(value) => IonValueRef::Blob(Cow::Owned(value)),
+            IonValue::List(values) => {
+                IonValueRef::List(values.into_iter().map(IonValueRef::from).collect())
+            }
+            IonValue::SExpr(values) => {
+                IonValueRef::SExpr(values.into_iter().map(IonValueRef::from).collect())
+            }
+            IonValue::Struct(fields) => IonValueRef::Struct(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, value.into()))
+                    .collect(),
+            ),
+            IonValue::OrderedStruct(fields) => IonValueRef::OrderedStruct(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, value.into()))
+                    .collect(),
+            ),
+            IonValue::Annotation(annotations, value) => {
+                IonValueRef::Annotation(annotations, Box::new((*value).into()))
+            }
+        }
+    }
+}
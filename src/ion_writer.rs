@@ -0,0 +1,156 @@
+use crate::{IonEncoder, IonValue};
+use std::io::Write;
+
+pub(crate) const DEFAULT_FLUSH_THRESHOLD: usize = 64 * 1024;
+
+/// Writes IonValues as Ion binary into anything implementing the trait
+/// [Write Trait](https://doc.rust-lang.org/stable/std/io/trait.Write.html)
+/// as they come in, instead of keeping all of them in memory as the
+/// `IonEncoder` does.
+///
+/// Encoded values are kept in an internal buffer until it reaches the flush
+/// threshold (64 KiB by default, see `with_flush_threshold`). Then the buffer
+/// is written, preceded by a local symbol table with the symbols that are
+/// new since the last flush. Those tables import the previous one, so
+/// symbols are declared only once in the whole stream.
+///
+/// Symbols without text that come from a shared table need the table to be
+/// imported. When a value needs a new import, the buffered values are written
+/// and a new local symbol table declaring the imports and all the symbols is
+/// started.
+///
+/// The `flush` or `finish` methods need to be called at the end in order to
+/// write the remaining values.
+///
+/// ```rust
+/// use ion_binary_rs::{IonParser, IonValue, IonWriter};
+///
+/// let mut writer = IonWriter::new(Vec::new());
+///
+/// for index in 0..1000 {
+///     writer.write(&IonValue::Symbol(format!("symbol_{}", index % 10).into())).unwrap();
+/// }
+///
+/// let bytes = writer.finish().unwrap();
+///
+/// let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+///
+/// assert_eq!(values.len(), 1000);
+/// assert_eq!(values[15], IonValue::Symbol("symbol_5".into()));
+/// ```
+#[derive(Debug)]
+pub struct IonWriter<W: Write> {
+    writer: W,
+    encoder: BufferedEncoder,
+}
+
+impl<W: Write> IonWriter<W> {
+    pub fn new(writer: W) -> IonWriter<W> {
+        IonWriter::with_flush_threshold(writer, DEFAULT_FLUSH_THRESHOLD)
+    }
+
+    /// Creates a writer that writes the buffered values once they take
+    /// `flush_threshold` bytes or more. With a threshold of 0 every value
+    /// is written as soon as it is added.
+    pub fn with_flush_threshold(writer: W, flush_threshold: usize) -> IonWriter<W> {
+        IonWriter {
+            writer,
+            encoder: BufferedEncoder::new(flush_threshold),
+        }
+    }
+
+    /// Writes floats in 4 bytes when no precision is lost, see
+    /// `IonEncoder::with_compact_floats`.
+    pub fn with_compact_floats(&mut self) {
+        self.encoder.with_compact_floats();
+    }
+
+    pub fn write(&mut self, value: &IonValue) -> std::io::Result<()> {
+        self.encoder.add(value);
+        self.write_output()
+    }
+
+    /// Writes all the buffered values and flushes the underlying writer.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.encoder.flush_values();
+        self.write_output()?;
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Flushes the writer and returns the underlying one.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_output(&mut self) -> std::io::Result<()> {
+        if !self.encoder.output().is_empty() {
+            self.writer.write_all(self.encoder.output())?;
+            self.encoder.clear_output();
+        }
+
+        Ok(())
+    }
+}
+
+/// Encodes the values for the writers, leaving the bytes that are ready to
+/// be written in an output buffer, so the `IonWriter` and the
+/// `AsyncIonWriter` only differ in how they write it.
+#[derive(Debug)]
+pub(crate) struct BufferedEncoder {
+    encoder: IonEncoder,
+    values_buffer: Vec<u8>,
+    output: Vec<u8>,
+    flush_threshold: usize,
+}
+
+impl BufferedEncoder {
+    pub(crate) fn new(flush_threshold: usize) -> BufferedEncoder {
+        BufferedEncoder {
+            encoder: IonEncoder::new(),
+            values_buffer: vec![],
+            output: vec![],
+            flush_threshold,
+        }
+    }
+
+    pub(crate) fn with_compact_floats(&mut self) {
+        self.encoder.with_compact_floats();
+    }
+
+    pub(crate) fn add(&mut self, value: &IonValue) {
+        if let Some(imports) = self.encoder.get_imports_for(value) {
+            self.flush_values();
+            self.encoder.set_imports(&imports);
+        }
+
+        self.encoder
+            .encode_value_into(value, &mut self.values_buffer);
+
+        if self.values_buffer.len() >= self.flush_threshold {
+            self.flush_values();
+        }
+    }
+
+    /// Moves the buffered values to the output, preceded by the symbol table
+    /// declaring the symbols they use.
+    pub(crate) fn flush_values(&mut self) {
+        self.encoder
+            .encode_stream_symbol_table_into(&mut self.output);
+
+        self.output.append(&mut self.values_buffer);
+    }
+
+    pub(crate) fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub(crate) fn clear_output(&mut self) {
+        self.output.clear();
+    }
+}
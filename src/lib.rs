@@ -20,10 +20,10 @@
 //!
 //! First of all, you need to be aware of the trade offs that we took for this library:
 //!
-//! - The API returns strings instead of Symbols. If needed we can add symbol, but we
-//! think string is the the most ergonomic way.
+//! - Symbols are returned as `SymbolToken`, which is usually just their text. The text
+//!   can be unknown for `$0` or for symbols from shared tables not available.
 //! - When parsing/decoding you can add shared tables for binary blobs that doesn't have
-//! all the required symbols.
+//!   all the required symbols, or a `Catalog` where they are looked up when needed.
 //!
 //! We have implemented the whole amazon ion test-suite for parsing.
 //! Encoding and Hashing fully tested. We are working in expading the coverage.
@@ -47,6 +47,205 @@
 //!
 //! ```
 //!
+//! Errors are `IonParserError`s. `consume_value_located` and `consume_all_located`
+//! return a `LocatedIonParserError` instead, which also tells the byte offset, the
+//! index of the top-level value and the path inside it where the error was found.
+//!
+//! ### Parsing untrusted input
+//!
+//! By default the parser trusts the lengths declared in the stream. When the
+//! input comes from untrusted sources, `IonParserOptions` sets limits for the
+//! nesting depth, the value size, the symbol tables and the total input size.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::{IonParser, IonParserOptions};
+//!
+//! let ion_binary = std::fs::File::open("export.10n").unwrap();
+//!
+//! let mut parser = IonParser::new(std::io::BufReader::new(ion_binary));
+//!
+//! parser.with_options(IonParserOptions {
+//!     max_depth: Some(64),
+//!     max_value_size: Some(1024 * 1024),
+//!     max_symbols: Some(10_000),
+//!     max_imports: Some(8),
+//!     max_total_input: Some(200 * 1024 * 1024),
+//! });
+//!
+//! println!("Decoded Ion: {:?}", parser.consume_all().unwrap())
+//! ```
+//!
+//! ### Parsing Ion text
+//!
+//! The `IonTextParser` has the same API as the `IonParser` and returns the same
+//! `IonValue` and `IonParserError` types, but it reads the Ion text format.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::IonTextParser;
+//!
+//! let ion_text = r#"$ion_1_0 { Model: "CLK 350", Year: 2019, tags: [sedan, 'white car'] }"#;
+//!
+//! let mut parser = IonTextParser::new(ion_text.as_bytes());
+//!
+//! println!("Decoded Ion: {:?}", parser.consume_all().unwrap())
+//! ```
+//!
+//! ### Parsing Ion 1.1
+//!
+//! The `IonParser` also reads Ion 1.1 binary. Once it finds the `E0 01 01 EA`
+//! version marker, `consume_value` and `consume_all` decode the values that follow
+//! into the same `IonValue` type, resolving symbol addresses with the usual symbol
+//! tables. Streams can switch between Ion 1.0 and Ion 1.1 at any version marker.
+//!
+//! ```rust
+//! use ion_binary_rs::{IonParser, IonValue};
+//!
+//! // [1, "a"] in Ion 1.1
+//! let ion_1_1 = [0xE0, 0x01, 0x01, 0xEA, 0xB4, 0x61, 0x01, 0x91, b'a'];
+//!
+//! let mut parser = IonParser::new(&ion_1_1[..]);
+//!
+//! assert_eq!(
+//!     parser.consume_all().unwrap(),
+//!     vec![IonValue::List(vec![IonValue::Integer(1), IonValue::String("a".into())])]
+//! );
+//! ```
+//!
+//! Macros are not supported yet, so e-expressions and `$ion_encoding` directives
+//! return an error. The `IonSliceParser` reads Ion 1.1 too, but its values don't
+//! borrow from the input. The rest of the readers only support Ion 1.0 and return
+//! `Ion11NotSupported` at the first Ion 1.1 value: `IonParser::skip_value`, the
+//! `IonReader`, `IonPath::read` and the `AsyncIonParser`.
+//!
+//! ### Parsing from memory without copies
+//!
+//! When the Ion binary is already in a byte slice, the `IonSliceParser` returns
+//! `IonValueRef`s, whose strings, clobs and blobs borrow from the slice instead
+//! of being copied. `to_owned` converts them into `IonValue`s.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::{IonSliceParser, IonValueRef};
+//!
+//! let ion_binary = std::fs::read("journal.10n").unwrap();
+//!
+//! let mut parser = IonSliceParser::new(&ion_binary);
+//!
+//! for value in parser.consume_all().unwrap() {
+//!     if let IonValueRef::String(text) = value {
+//!         println!("String: {}", text);
+//!     }
+//! }
+//! ```
+//!
+//! ### Streaming reader
+//!
+//! The `IonReader` moves over the binary stream value by value, entering and
+//! leaving containers with `step_in` and `step_out` and only decoding the
+//! values that are read. Useful for big streams that don't fit in memory.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::{IonReader, ValueType};
+//!
+//! let ion_binary = std::fs::File::open("journal.10n").unwrap();
+//!
+//! let mut reader = IonReader::new(std::io::BufReader::new(ion_binary));
+//!
+//! while let Some(header) = reader.next().unwrap() {
+//!     if header.get_type() == ValueType::Struct {
+//!         reader.step_in().unwrap();
+//!
+//!         while reader.next().unwrap().is_some() {
+//!             println!("Field: {:?}", reader.field_name());
+//!         }
+//!
+//!         reader.step_out().unwrap();
+//!     }
+//! }
+//! ```
+//!
+//! ### Querying values
+//!
+//! An `IonPath` selects values nested inside an `IonValue`, like
+//! `orders[3].price`, with `.*` and `[*]` as wildcards and `::name` to keep
+//! only the values with that annotation. It returns references, owned copies
+//! or mutable references to edit the values in place. It can also be
+//! evaluated over an `IonReader`, which only decodes the matched values.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::{IonParser, IonPath, IonReader, IonValue};
+//!
+//! let path: IonPath = "orders[*].price".parse().unwrap();
+//!
+//! let ion_binary = std::fs::read("journal.10n").unwrap();
+//!
+//! // Every price of every order in the stream, without building the orders
+//! let prices = path.read(&mut IonReader::new(&ion_binary[..])).unwrap();
+//!
+//! // Or over parsed values, editing them in place
+//! let mut values = IonParser::new(&ion_binary[..]).consume_all().unwrap();
+//!
+//! for value in &mut values {
+//!     for price in path.select_mut(value) {
+//!         *price = IonValue::Integer(0);
+//!     }
+//! }
+//! ```
+//!
+//! ### Building values
+//!
+//! The `ion!` macro builds an `IonValue` from Ion-like syntax, instead of
+//! nesting the `IonValue` variants by hand. Identifiers are symbols, and
+//! decimals, timestamps and blobs are written as `decimal("...")`,
+//! `timestamp("...")` and `blob(...)`. Rust expressions go between braces.
+//!
+//! ```rust
+//! use ion_binary_rs::{ion, IonEncoder, IonParser};
+//!
+//! let year = 2019;
+//!
+//! let car = ion!({
+//!     model: "CLK 350",
+//!     kind: sedan,
+//!     year: { year },
+//!     price: usd::decimal("48500.00"),
+//!     sold: timestamp("2020-03-15T"),
+//!     owners: ["Alice", "Bob"],
+//!     notes: null.string,
+//! });
+//!
+//! let mut encoder = IonEncoder::new();
+//! encoder.add(car.clone());
+//!
+//! let bytes = encoder.encode();
+//!
+//! assert_eq!(IonParser::new(&bytes[..]).consume_value().unwrap().0, car);
+//! ```
+//!
+//! ### Comparing values
+//!
+//! `IonValue` equality follows the equivalence rules of the Ion data model,
+//! also available as `ion_eq`. An `Integer` and a `BigInteger` with the same
+//! value are equal, NaNs are equal to each other, decimals need the same
+//! precision (`1.0` is not `1.00`) and structs are compared as multisets of
+//! fields. Values also have a total order, so they can be sorted or used as
+//! `BTreeMap` keys.
+//!
+//! ```rust
+//! use ion_binary_rs::IonValue;
+//! use num_bigint::BigInt;
+//!
+//! assert_eq!(IonValue::Integer(5), IonValue::BigInteger(BigInt::from(5)));
+//! assert_eq!(IonValue::Float(f64::NAN), IonValue::Float(f64::NAN));
+//!
+//! let mut values = vec![IonValue::Float(1.5), IonValue::Integer(3), IonValue::Bool(true)];
+//! values.sort();
+//!
+//! assert_eq!(
+//!     values,
+//!     vec![IonValue::Bool(true), IonValue::Integer(3), IonValue::Float(1.5)]
+//! );
+//! ```
+//!
 //! ### Encoding
 //!
 //! ```rust,no_run
@@ -78,6 +277,70 @@
 //! assert_eq!(ion_value, resulting_ion_value);
 //! ```
 //!
+//! Struct fields are written in no particular order. Use `IonEncoder::new_canonical`
+//! when equal values need to be encoded to the same bytes, for example in order
+//! to sign them or to use them as cache keys.
+//!
+//! ### Streaming writer
+//!
+//! The `IonWriter` encodes values as they are written, writing them into any
+//! `std::io::Write` every time its buffer reaches the flush threshold.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::{IonValue, IonWriter};
+//!
+//! let file = std::fs::File::create("values.10n").unwrap();
+//!
+//! let mut writer = IonWriter::new(std::io::BufWriter::new(file));
+//!
+//! for year in 2000..2020 {
+//!     writer.write(&IonValue::Integer(year)).unwrap();
+//! }
+//!
+//! writer.finish().unwrap();
+//! ```
+//!
+//! ### Encoding Ion 1.1
+//!
+//! `with_target_version` makes the `IonEncoder` write Ion 1.1, which the
+//! `IonParser` reads too. Symbols are only added to the symbol table when they are
+//! used enough times for that to save space, and written inline otherwise, so small
+//! outputs usually don't need a table. Long containers are delimited, and integers
+//! and timestamps take fewer bytes. Macros are not used.
+//!
+//! ```rust
+//! use ion_binary_rs::{IonEncoder, IonParser, IonValue, IonVersion};
+//!
+//! let value = IonValue::List(vec![IonValue::Symbol("sedan".into()), IonValue::Integer(2019)]);
+//!
+//! let mut encoder = IonEncoder::new();
+//! encoder.with_target_version(IonVersion::Ion1_1);
+//!
+//! encoder.add(value.clone());
+//! let bytes = encoder.encode();
+//!
+//! assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), vec![value]);
+//! ```
+//!
+//! ### Encoding Ion text
+//!
+//! The `IonTextEncoder` writes values as Ion text, either compact or pretty
+//! printed. `IonValue` also implements `Display`, using `{:#}` for the pretty
+//! mode.
+//!
+//! ```rust,no_run
+//! use ion_binary_rs::{IonTextEncoder, IonValue};
+//!
+//! let ion_value = IonValue::List(vec![IonValue::Integer(1), IonValue::Symbol("two".into())]);
+//!
+//! let mut encoder = IonTextEncoder::new_pretty();
+//!
+//! encoder.add(ion_value.clone());
+//!
+//! println!("Encoded Ion: {}", encoder.encode());
+//! println!("Compact Ion: {}", ion_value);
+//! ```
+//!
 //! ### Hashing
 //!
 //! ```rust,no_run
@@ -104,6 +367,79 @@
 //! println!("{:X?}", hash);
 //! ```
 //!
+//! ### Serde
+//!
+//! With the `serde` feature enabled you can serialize and deserialize your own types
+//! directly, without building an intermediate `IonValue`.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use ion_binary_rs::{from_slice, to_vec};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! enum Engine {
+//!     Electric,
+//!     Combustion { cylinders: u8 },
+//! }
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Car {
+//!     model: String,
+//!     year: u32,
+//!     engine: Engine,
+//! }
+//!
+//! let car = Car {
+//!     model: "CLK 350".to_string(),
+//!     year: 2019,
+//!     engine: Engine::Combustion { cylinders: 6 },
+//! };
+//!
+//! let bytes = to_vec(&car).unwrap();
+//!
+//! assert_eq!(car, from_slice(&bytes).unwrap());
+//! # }
+//! ```
+//!
+//! Structs and maps are encoded as Ion structs, sequences and tuples as lists, `None`
+//! and unit as null and byte buffers (via `serde_bytes`) as blobs. Unit enum variants
+//! are encoded as symbols and the rest of variants as their value annotated with the
+//! variant name. Use `SerdeSymbol`, `SerdeDecimal` and `SerdeTimestamp` in order to
+//! encode symbols, decimals and timestamps.
+//!
+//! When deserializing, decimals are read as `f64` unless the target is a `SerdeDecimal`,
+//! so they may lose precision, and annotations are ignored except to pick enum variants.
+//!
+//! ### Tokio
+//!
+//! With the `tokio` feature enabled, the `AsyncIonParser` and the `AsyncIonWriter`
+//! read and write Ion binary from tokio's `AsyncRead` and `AsyncWrite`, so values can
+//! be decoded from sockets or object-store streams as they arrive.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "tokio")]
+//! # {
+//! use ion_binary_rs::{AsyncIonParser, AsyncIonWriter};
+//! use tokio::io::{AsyncRead, AsyncWrite};
+//!
+//! async fn forward(input: impl AsyncRead + Unpin, output: impl AsyncWrite + Unpin) {
+//!     let mut parser = AsyncIonParser::new(input);
+//!     let mut writer = AsyncIonWriter::new(output);
+//!
+//!     while let Some(value) = parser.next_value().await.unwrap() {
+//!         writer.write(&value).await.unwrap();
+//!     }
+//!
+//!     writer.finish().await.unwrap();
+//! }
+//! # }
+//! ```
+//!
+//! The parser reads one top-level value at a time and decodes it in the same way as
+//! the `IonParser`, including the limits from `IonParserOptions`.
+//!
 //! ## Safety
 //!
 //! In order to speed up the encoding of data, we use Uninit vector buffers, as otherwise
@@ -131,25 +467,77 @@
 //! </sub>
 //!
 
+#[cfg(feature = "tokio")]
+pub(crate) mod async_ion_parser;
+#[cfg(feature = "tokio")]
+pub(crate) mod async_ion_writer;
 pub(crate) mod binary_encoder;
 pub(crate) mod binary_parser;
 pub(crate) mod binary_parser_types;
+pub(crate) mod catalog;
 pub(crate) mod ion_encoder;
+pub(crate) mod ion_encoder_1_1;
 pub(crate) mod ion_hash;
 pub(crate) mod ion_hash_encoder;
+pub(crate) mod ion_macro;
 pub(crate) mod ion_parser;
+pub(crate) mod ion_parser_1_1;
 pub(crate) mod ion_parser_types;
+pub(crate) mod ion_path;
+pub(crate) mod ion_reader;
+pub(crate) mod ion_slice_parser;
+pub(crate) mod ion_text_encoder;
+pub(crate) mod ion_text_parser;
+pub(crate) mod ion_timestamp;
+pub(crate) mod ion_value_cmp;
 pub(crate) mod ion_value_impl;
+pub(crate) mod ion_value_ref;
+pub(crate) mod ion_writer;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_encoder;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_parser;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_types;
 pub(crate) mod symbol_table;
+pub(crate) mod symbol_token;
+pub(crate) mod text_encoder;
+pub(crate) mod text_parser;
+pub(crate) mod text_parser_types;
 
 #[cfg(test)]
 mod tests;
 
-pub use binary_parser_types::ParsingError;
-pub use ion_encoder::IonEncoder;
+pub use binary_parser_types::{ParsingError, ValueHeader, ValueType};
+pub use catalog::{Catalog, CatalogError, FileCatalog, InMemoryCatalog};
+pub use ion_encoder::{IonEncoder, IonVersion};
 pub use ion_hash::IonHash;
-pub use ion_parser::IonParser;
+#[doc(hidden)]
+pub use ion_macro::{__ion_annotate, __ion_decimal, __ion_timestamp};
+pub use ion_parser::{IonParser, IonParserOptions};
 pub use ion_parser_types::{
-    IonExtractionError, IonParserError, IonValue, NullIonValue, SerdeJsonParseError,
+    IonExtractionError, IonParserError, IonValue, IonValuePath, LocatedIonParserError,
+    NullIonValue, PathComponent, SerdeJsonParseError,
 };
-pub use symbol_table::{Symbol, SymbolContextError};
+pub use ion_path::{IonPath, IonPathError, IonPathStep};
+pub use ion_reader::IonReader;
+pub use ion_slice_parser::IonSliceParser;
+pub use ion_text_encoder::IonTextEncoder;
+pub use ion_text_parser::IonTextParser;
+pub use ion_timestamp::{IonTimestamp, TimestampPrecision};
+pub use ion_value_ref::IonValueRef;
+pub use ion_writer::IonWriter;
+pub use symbol_table::{SharedSymbolTable, Symbol, SymbolContextError};
+pub use symbol_token::{ImportLocation, SymbolToken};
+pub use text_parser_types::TextParsingError;
+
+#[cfg(feature = "tokio")]
+pub use async_ion_parser::AsyncIonParser;
+#[cfg(feature = "tokio")]
+pub use async_ion_writer::AsyncIonWriter;
+#[cfg(feature = "serde")]
+pub use serde_encoder::to_vec;
+#[cfg(feature = "serde")]
+pub use serde_parser::{from_reader, from_slice};
+#[cfg(feature = "serde")]
+pub use serde_types::{IonSerdeError, SerdeDecimal, SerdeSymbol, SerdeTimestamp};
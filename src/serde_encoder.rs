@@ -0,0 +1,603 @@
+use crate::binary_encoder::{
+    encode_blob, encode_bool, encode_datetime, encode_decimal, encode_float64, encode_integer,
+    encode_null, encode_varuint,
+};
+use crate::serde_types::{
+    parse_decimal, parse_timestamp, IonSerdeError, DECIMAL_NEWTYPE_NAME, SYMBOL_NEWTYPE_NAME,
+    TIMESTAMP_NEWTYPE_NAME,
+};
+use crate::{IonEncoder, NullIonValue, SymbolToken};
+use num_bigint::BigInt;
+use serde::ser::{self, Impossible, Serialize};
+
+const LIST_TYPE: u8 = 0xB;
+const STRUCT_TYPE: u8 = 0xD;
+
+/// Serializes any value implementing serde's `Serialize` directly into an
+/// Ion binary stream, including the ion header and the symbol table.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{from_slice, to_vec};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Car {
+///     model: String,
+///     year: u32,
+/// }
+///
+/// let car = Car { model: "CLK 350".to_string(), year: 2019 };
+///
+/// let bytes = to_vec(&car).unwrap();
+///
+/// assert_eq!(car, from_slice::<Car>(&bytes).unwrap());
+/// ```
+pub fn to_vec<T: ?Sized + Serialize>(value: &T) -> Result<Vec<u8>, IonSerdeError> {
+    let mut encoder = IonEncoder::new();
+
+    let values_buffer = value.serialize(IonSerializer {
+        encoder: &mut encoder,
+    })?;
+
+    Ok(encoder.encode_with_values(values_buffer))
+}
+
+// Every method returns the encoded bytes of the value, same as the
+// `encode_*` methods of the IonEncoder. Symbols are added to the encoder's
+// symbol table, which is written before the values once everything is
+// serialized.
+struct IonSerializer<'a> {
+    encoder: &'a mut IonEncoder,
+}
+
+// Container types are written as a header, the length and the already
+// encoded content, which is exactly what `encode_blob` does.
+fn encode_container(ion_type: u8, content: &[u8]) -> Vec<u8> {
+    encode_blob(ion_type, content)
+}
+
+impl<'a> ser::Serializer for IonSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeTuple = SeqSerializer<'a>;
+    type SerializeTupleStruct = SeqSerializer<'a>;
+    type SerializeTupleVariant = SeqSerializer<'a>;
+    type SerializeMap = StructSerializer<'a>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = StructSerializer<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_bool(&value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_integer(&BigInt::from(value)))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_integer(&BigInt::from(value)))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_u64(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_integer(&BigInt::from(value)))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_integer(&BigInt::from(value)))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_f64(value.into())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_float64(&value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_str(value.encode_utf8(&mut [0u8; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_blob(8, value.as_bytes()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_blob(10, value))
+    }
+
+    fn serialize_none(self) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_null(&NullIonValue::Null))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Vec<u8>, IonSerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(encode_null(&NullIonValue::Null))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Vec<u8>, IonSerdeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Vec<u8>, IonSerdeError> {
+        Ok(self.encoder.encode_symbol(variant))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Vec<u8>, IonSerdeError> {
+        match name {
+            SYMBOL_NEWTYPE_NAME => {
+                let text = value.serialize(TextSerializer)?;
+                Ok(self.encoder.encode_symbol(&text))
+            }
+            DECIMAL_NEWTYPE_NAME => {
+                let text = value.serialize(TextSerializer)?;
+                Ok(encode_decimal(&parse_decimal(&text)?))
+            }
+            TIMESTAMP_NEWTYPE_NAME => {
+                let text = value.serialize(TextSerializer)?;
+                Ok(encode_datetime(&parse_timestamp(&text)?.into()))
+            }
+            _ => value.serialize(self),
+        }
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Vec<u8>, IonSerdeError> {
+        let annot_buffer = self
+            .encoder
+            .encode_annotation_symbols(&[SymbolToken::from(variant)]);
+
+        let value_bytes = value.serialize(IonSerializer {
+            encoder: &mut *self.encoder,
+        })?;
+
+        Ok(IonEncoder::encode_annotation_wrapper(
+            annot_buffer,
+            value_bytes,
+        ))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSerializer<'a>, IonSerdeError> {
+        Ok(SeqSerializer::new(self.encoder, None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer<'a>, IonSerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer<'a>, IonSerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SeqSerializer<'a>, IonSerdeError> {
+        let annot_buffer = self
+            .encoder
+            .encode_annotation_symbols(&[SymbolToken::from(variant)]);
+        Ok(SeqSerializer::new(self.encoder, Some(annot_buffer)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<StructSerializer<'a>, IonSerdeError> {
+        Ok(StructSerializer::new(self.encoder, None))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<StructSerializer<'a>, IonSerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer<'a>, IonSerdeError> {
+        let annot_buffer = self
+            .encoder
+            .encode_annotation_symbols(&[SymbolToken::from(variant)]);
+        Ok(StructSerializer::new(self.encoder, Some(annot_buffer)))
+    }
+}
+
+struct SeqSerializer<'a> {
+    encoder: &'a mut IonEncoder,
+    buffer: Vec<u8>,
+    annotation: Option<Vec<u8>>,
+}
+
+impl<'a> SeqSerializer<'a> {
+    fn new(encoder: &'a mut IonEncoder, annotation: Option<Vec<u8>>) -> SeqSerializer<'a> {
+        SeqSerializer {
+            encoder,
+            buffer: vec![],
+            annotation,
+        }
+    }
+
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        let mut bytes = value.serialize(IonSerializer {
+            encoder: &mut *self.encoder,
+        })?;
+
+        self.buffer.append(&mut bytes);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Vec<u8>, IonSerdeError> {
+        let list = encode_container(LIST_TYPE, &self.buffer);
+
+        Ok(match self.annotation {
+            Some(annot_buffer) => IonEncoder::encode_annotation_wrapper(annot_buffer, list),
+            None => list,
+        })
+    }
+}
+
+impl<'a> ser::SerializeSeq for SeqSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for SeqSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for SeqSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for SeqSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+struct StructSerializer<'a> {
+    encoder: &'a mut IonEncoder,
+    buffer: Vec<u8>,
+    annotation: Option<Vec<u8>>,
+}
+
+impl<'a> StructSerializer<'a> {
+    fn new(encoder: &'a mut IonEncoder, annotation: Option<Vec<u8>>) -> StructSerializer<'a> {
+        StructSerializer {
+            encoder,
+            buffer: vec![],
+            annotation,
+        }
+    }
+
+    fn push_key(&mut self, key: &str) {
+        let symbol = self.encoder.insert_symbol(key);
+        let mut symbol_bytes = encode_varuint(&symbol.to_be_bytes());
+        self.buffer.append(&mut symbol_bytes);
+    }
+
+    fn push_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        let mut bytes = value.serialize(IonSerializer {
+            encoder: &mut *self.encoder,
+        })?;
+
+        self.buffer.append(&mut bytes);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Vec<u8>, IonSerdeError> {
+        let ion_struct = encode_container(STRUCT_TYPE, &self.buffer);
+
+        Ok(match self.annotation {
+            Some(annot_buffer) => IonEncoder::encode_annotation_wrapper(annot_buffer, ion_struct),
+            None => ion_struct,
+        })
+    }
+}
+
+impl<'a> ser::SerializeMap for StructSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), IonSerdeError> {
+        let key = key.serialize(TextSerializer)?;
+        self.push_key(&key);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IonSerdeError> {
+        self.push_value(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for StructSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), IonSerdeError> {
+        self.push_key(key);
+        self.push_value(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for StructSerializer<'a> {
+    type Ok = Vec<u8>;
+    type Error = IonSerdeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), IonSerdeError> {
+        self.push_key(key);
+        self.push_value(value)
+    }
+
+    fn end(self) -> Result<Vec<u8>, IonSerdeError> {
+        self.finish()
+    }
+}
+
+// Extracts the text of struct keys and of the special newtypes
+// (symbols, decimals and timestamps). Anything else is an error.
+struct TextSerializer;
+
+impl ser::Serializer for TextSerializer {
+    type Ok = String;
+    type Error = IonSerdeError;
+
+    type SerializeSeq = Impossible<String, IonSerdeError>;
+    type SerializeTuple = Impossible<String, IonSerdeError>;
+    type SerializeTupleStruct = Impossible<String, IonSerdeError>;
+    type SerializeTupleVariant = Impossible<String, IonSerdeError>;
+    type SerializeMap = Impossible<String, IonSerdeError>;
+    type SerializeStruct = Impossible<String, IonSerdeError>;
+    type SerializeStructVariant = Impossible<String, IonSerdeError>;
+
+    fn serialize_str(self, value: &str) -> Result<String, IonSerdeError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_char(self, value: char) -> Result<String, IonSerdeError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, IonSerdeError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, IonSerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _value: bool) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_i8(self, _value: i8) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_i16(self, _value: i16) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_i32(self, _value: i32) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_i64(self, _value: i64) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_u8(self, _value: u8) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_u16(self, _value: u16) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_u32(self, _value: u32) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_u64(self, _value: u64) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_none(self) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_unit(self) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, IonSerdeError> {
+        Err(IonSerdeError::KeyMustBeAString)
+    }
+}
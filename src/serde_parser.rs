@@ -0,0 +1,465 @@
+use crate::binary_parser_types::ValueType;
+use crate::ion_parser::{IonParser, PeekedValue};
+use crate::serde_types::{
+    IonSerdeError, DECIMAL_NEWTYPE_NAME, SYMBOL_NEWTYPE_NAME, TIMESTAMP_NEWTYPE_NAME,
+};
+use crate::{IonParserError, IonValue, ParsingError};
+use chrono::SecondsFormat;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use serde::de::{
+    self, value::SeqDeserializer, Deserialize, DeserializeOwned, DeserializeSeed, IntoDeserializer,
+    Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::io::Read;
+
+/// Deserializes one value from an Ion binary slice into any type
+/// implementing serde's `Deserialize`. The slice must contain exactly one
+/// value (plus any system values like symbol tables or NOP padding).
+///
+/// Ion decimals are read as `f64` when the type asks for a float or for any
+/// value (like `serde_json::Value`), so they may lose precision. Use
+/// `SerdeDecimal` to keep them exact. Annotations are only used to pick the
+/// variant of an enum and are ignored on any other value.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, IonSerdeError> {
+    from_reader(bytes)
+}
+
+/// Same as `from_slice` but reading from anything implementing
+/// [Read Trait](https://doc.rust-lang.org/stable/std/io/trait.Read.html).
+/// Values are read directly from the stream without building an IonValue.
+/// Decimals and annotations behave as described in `from_slice`.
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T, IonSerdeError> {
+    let mut deserializer = IonDeserializer::new(reader);
+
+    let value = T::deserialize(&mut deserializer)?;
+
+    deserializer.end()?;
+
+    Ok(value)
+}
+
+struct IonDeserializer<R: Read> {
+    parser: IonParser<R>,
+    nested_level: u64,
+    peeked: Option<PeekedValue>,
+}
+
+impl<R: Read> IonDeserializer<R> {
+    fn new(reader: R) -> IonDeserializer<R> {
+        IonDeserializer {
+            parser: IonParser::new(reader),
+            nested_level: 0,
+            peeked: None,
+        }
+    }
+
+    fn end(&mut self) -> Result<(), IonSerdeError> {
+        match self.next_value_until(None) {
+            Err(IonSerdeError::ParserError(IonParserError::BinaryError(
+                ParsingError::NoDataToRead,
+            ))) => Ok(()),
+            Err(err) => Err(err),
+            Ok(_) => Err(IonSerdeError::TrailingValues),
+        }
+    }
+
+    fn next_value(&mut self) -> Result<PeekedValue, IonSerdeError> {
+        if let Some(value) = self.peeked.take() {
+            return Ok(value);
+        }
+
+        match self.next_value_until(None)? {
+            Some(value) => Ok(value),
+            None => Err(ParsingError::NoDataToRead.into()),
+        }
+    }
+
+    // Reads the next user value, skipping NOP padding and symbol tables.
+    // When `end` is defined it returns None once that position is reached.
+    fn next_value_until(
+        &mut self,
+        end: Option<usize>,
+    ) -> Result<Option<PeekedValue>, IonSerdeError> {
+        loop {
+            if let Some(end) = end {
+                if self.check_container_end(end)? {
+                    return Ok(None);
+                }
+            }
+
+            let header = self.parser.consume_value_header(self.nested_level)?;
+
+            if let Some(value) = self
+                .parser
+                .resolve_value_header(header, self.nested_level)?
+            {
+                return Ok(Some(value));
+            }
+        }
+    }
+
+    fn check_container_end(&self, end: usize) -> Result<bool, IonSerdeError> {
+        let position = self.parser.position();
+
+        if position > end {
+            return Err(IonParserError::ListLengthWasTooShort.into());
+        }
+
+        Ok(position == end)
+    }
+
+    fn end_value(&self, value: &PeekedValue) -> Result<(), IonSerdeError> {
+        match value.annotation_end {
+            Some(end) if end != self.parser.position() => {
+                Err(IonParserError::BadAnnotationLength.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn consume_scalar(&mut self, value: &PeekedValue) -> Result<IonValue, IonSerdeError> {
+        Ok(self
+            .parser
+            .consume_value_body(&value.header, self.nested_level)?
+            .0)
+    }
+
+    fn visit_value<'de, V: Visitor<'de>>(
+        &mut self,
+        value: &PeekedValue,
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        if value.header.is_len_null_value() {
+            self.consume_scalar(value)?;
+            return visitor.visit_unit();
+        }
+
+        match value.header.get_type() {
+            ValueType::List | ValueType::SExpr => self.visit_list(value, visitor),
+            ValueType::Struct => self.visit_struct(value, visitor),
+            _ => match self.consume_scalar(value)? {
+                IonValue::Bool(value) => visitor.visit_bool(value),
+                IonValue::Integer(value) => visitor.visit_i64(value),
+                IonValue::BigInteger(value) => visit_big_integer(value, visitor),
+                IonValue::Float(value) => visitor.visit_f64(value),
+                IonValue::Decimal(value) => match value.to_f64() {
+                    Some(value) => visitor.visit_f64(value),
+                    None => Err(IonSerdeError::InvalidDecimal(value.to_string())),
+                },
+                IonValue::DateTime(value) => visitor.visit_string(
+                    value
+                        .to_datetime()
+                        .to_rfc3339_opts(SecondsFormat::AutoSi, false),
+                ),
+                IonValue::String(value) => visitor.visit_string(value),
+                IonValue::Symbol(value) if !value.is_unknown() => {
+                    visitor.visit_string(value.into_text().unwrap_or_default())
+                }
+                IonValue::Clob(value) | IonValue::Blob(value) => visitor.visit_byte_buf(value),
+                IonValue::Null(_) => visitor.visit_unit(),
+                value => Err(IonParserError::TypeNotSupported(value).into()),
+            },
+        }
+    }
+
+    fn visit_list<'de, V: Visitor<'de>>(
+        &mut self,
+        value: &PeekedValue,
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        let (length, _, _) = self.parser.consume_value_len(&value.header)?;
+        let end = self.parser.position() + length;
+
+        self.nested_level += 1;
+        let result = visitor.visit_seq(ListAccess { de: self, end });
+        self.nested_level -= 1;
+        let result = result?;
+
+        if !self.check_container_end(end)? {
+            return Err(IonSerdeError::TrailingElements);
+        }
+
+        Ok(result)
+    }
+
+    fn visit_struct<'de, V: Visitor<'de>>(
+        &mut self,
+        value: &PeekedValue,
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        let (length, _, _) = self.parser.consume_value_len_for_struct(&value.header)?;
+        let end = self.parser.position() + length;
+
+        self.nested_level += 1;
+        let result = visitor.visit_map(StructAccess { de: self, end });
+        self.nested_level -= 1;
+        let result = result?;
+
+        if !self.check_container_end(end)? {
+            return Err(IonSerdeError::TrailingElements);
+        }
+
+        Ok(result)
+    }
+}
+
+fn visit_big_integer<'de, V: Visitor<'de>>(
+    value: BigInt,
+    visitor: V,
+) -> Result<V::Value, IonSerdeError> {
+    if let Some(value) = value.to_u64() {
+        return visitor.visit_u64(value);
+    }
+
+    if let Some(value) = value.to_i128() {
+        return visitor.visit_i128(value);
+    }
+
+    match value.to_u128() {
+        Some(value) => visitor.visit_u128(value),
+        None => Err(IonSerdeError::IntegerTooBig),
+    }
+}
+
+impl<'de, R: Read> de::Deserializer<'de> for &mut IonDeserializer<R> {
+    type Error = IonSerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, IonSerdeError> {
+        let value = self.next_value()?;
+        let result = self.visit_value(&value, visitor)?;
+        self.end_value(&value)?;
+        Ok(result)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, IonSerdeError> {
+        let value = self.next_value()?;
+
+        if value.header.is_len_null_value() {
+            self.consume_scalar(&value)?;
+            self.end_value(&value)?;
+            visitor.visit_none()
+        } else {
+            self.peeked = Some(value);
+            visitor.visit_some(self)
+        }
+    }
+
+    // Allows Vec<u8> to be read from blobs and clobs, not only from lists.
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, IonSerdeError> {
+        let value = self.next_value()?;
+
+        let result = match value.header.get_type() {
+            ValueType::Blob | ValueType::Clob if !value.header.is_len_null_value() => {
+                match self.consume_scalar(&value)? {
+                    IonValue::Blob(bytes) | IonValue::Clob(bytes) => visitor
+                        .visit_seq(SeqDeserializer::<_, IonSerdeError>::new(bytes.into_iter()))?,
+                    value => return Err(IonParserError::TypeNotSupported(value).into()),
+                }
+            }
+            _ => self.visit_value(&value, visitor)?,
+        };
+
+        self.end_value(&value)?;
+
+        Ok(result)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        if name != SYMBOL_NEWTYPE_NAME
+            && name != DECIMAL_NEWTYPE_NAME
+            && name != TIMESTAMP_NEWTYPE_NAME
+        {
+            return visitor.visit_newtype_struct(self);
+        }
+
+        let value = self.next_value()?;
+
+        let text = match self.consume_scalar(&value)? {
+            IonValue::String(text) => text,
+            IonValue::Symbol(text) if !text.is_unknown() => text.into_text().unwrap_or_default(),
+            IonValue::Decimal(value) if name == DECIMAL_NEWTYPE_NAME => value.to_string(),
+            IonValue::Integer(value) if name == DECIMAL_NEWTYPE_NAME => value.to_string(),
+            IonValue::BigInteger(value) if name == DECIMAL_NEWTYPE_NAME => value.to_string(),
+            IonValue::DateTime(value) if name == TIMESTAMP_NEWTYPE_NAME => value
+                .to_datetime()
+                .to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            value => return Err(IonParserError::TypeNotSupported(value).into()),
+        };
+
+        self.end_value(&value)?;
+
+        visitor.visit_newtype_struct(text.into_deserializer())
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        let mut value = self.next_value()?;
+
+        if !value.annotations.is_empty() {
+            let variant = value
+                .annotations
+                .remove(0)
+                .into_text()
+                .ok_or(IonSerdeError::ExpectedEnum)?;
+            self.peeked = Some(value);
+            return visitor.visit_enum(VariantAccess { de: self, variant });
+        }
+
+        match value.header.get_type() {
+            ValueType::Symbol | ValueType::String if !value.header.is_len_null_value() => {
+                let variant = match self.consume_scalar(&value)? {
+                    IonValue::String(text) => text,
+                    IonValue::Symbol(text) => {
+                        text.into_text().ok_or(IonSerdeError::ExpectedEnum)?
+                    }
+                    _ => return Err(IonSerdeError::ExpectedEnum),
+                };
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            _ => Err(IonSerdeError::ExpectedEnum),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        let value = self.next_value()?;
+        self.parser.skip_value_body(&value.header)?;
+        self.end_value(&value)?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier
+    }
+}
+
+struct ListAccess<'a, R: Read> {
+    de: &'a mut IonDeserializer<R>,
+    end: usize,
+}
+
+impl<'de, 'a, R: Read> de::SeqAccess<'de> for ListAccess<'a, R> {
+    type Error = IonSerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, IonSerdeError> {
+        match self.de.next_value_until(Some(self.end))? {
+            Some(value) => {
+                self.de.peeked = Some(value);
+                seed.deserialize(&mut *self.de).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+struct StructAccess<'a, R: Read> {
+    de: &'a mut IonDeserializer<R>,
+    end: usize,
+}
+
+impl<'de, 'a, R: Read> de::MapAccess<'de> for StructAccess<'a, R> {
+    type Error = IonSerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, IonSerdeError> {
+        loop {
+            if self.de.check_container_end(self.end)? {
+                return Ok(None);
+            }
+
+            let (key, _) = self.de.parser.consume_field_name()?;
+
+            let key = key
+                .into_text()
+                .ok_or(IonParserError::SymbolNotFoundInTable)?;
+
+            let header = self.de.parser.consume_value_header(self.de.nested_level)?;
+
+            if let Some(value) = self
+                .de
+                .parser
+                .resolve_value_header(header, self.de.nested_level)?
+            {
+                self.de.peeked = Some(value);
+                return seed.deserialize(key.into_deserializer()).map(Some);
+            }
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct VariantAccess<'a, R: Read> {
+    de: &'a mut IonDeserializer<R>,
+    variant: String,
+}
+
+impl<'de, 'a, R: Read> de::EnumAccess<'de> for VariantAccess<'a, R> {
+    type Error = IonSerdeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), IonSerdeError> {
+        let variant: de::value::StrDeserializer<IonSerdeError> =
+            self.variant.as_str().into_deserializer();
+        let variant = seed.deserialize(variant)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a, R: Read> de::VariantAccess<'de> for VariantAccess<'a, R> {
+    type Error = IonSerdeError;
+
+    fn unit_variant(self) -> Result<(), IonSerdeError> {
+        de::IgnoredAny::deserialize(&mut *self.de)?;
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, IonSerdeError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        de::Deserializer::deserialize_seq(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, IonSerdeError> {
+        de::Deserializer::deserialize_map(&mut *self.de, visitor)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{IonParserError, ParsingError};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use thiserror::Error;
+
+// Newtype names used in order to tell the Ion serializer and deserializer
+// that a value is not a plain string. Other serde formats will just see
+// the inner string.
+pub(crate) const SYMBOL_NEWTYPE_NAME: &str = "$__ion_binary_rs_symbol";
+pub(crate) const DECIMAL_NEWTYPE_NAME: &str = "$__ion_binary_rs_decimal";
+pub(crate) const TIMESTAMP_NEWTYPE_NAME: &str = "$__ion_binary_rs_timestamp";
+
+/// Errors returned by `to_vec`, `from_slice` and `from_reader`.
+#[derive(Debug, Error)]
+pub enum IonSerdeError {
+    #[error("{0}")]
+    Custom(String),
+    #[error("Error parsing the ion binary format")]
+    ParserError(#[from] IonParserError),
+    #[error("Struct field names can only be strings, chars or unit enum variants")]
+    KeyMustBeAString,
+    #[error("The decimal value is not valid")]
+    InvalidDecimal(String),
+    #[error("The timestamp is not a valid RFC 3339 date")]
+    InvalidTimestamp(String),
+    #[error("The integer doesn't fit in 128 bits")]
+    IntegerTooBig,
+    #[error("An enum needs to be a symbol, a string or an annotated value")]
+    ExpectedEnum,
+    #[error("The list has more elements than the destination type")]
+    TrailingElements,
+    #[error("There are more values in the ion stream after the deserialized one")]
+    TrailingValues,
+}
+
+impl From<ParsingError> for IonSerdeError {
+    fn from(err: ParsingError) -> Self {
+        IonSerdeError::ParserError(IonParserError::BinaryError(err))
+    }
+}
+
+impl ser::Error for IonSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        IonSerdeError::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for IonSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        IonSerdeError::Custom(msg.to_string())
+    }
+}
+
+/// Serializes the inner string as an Ion symbol instead of an Ion string.
+/// When deserializing it accepts both symbols and strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SerdeSymbol(pub String);
+
+/// Serializes the inner value as an Ion decimal, keeping all its precision.
+/// When deserializing it accepts decimals and integers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeDecimal(pub BigDecimal);
+
+/// Serializes the inner value as an Ion timestamp. When deserializing it
+/// accepts timestamps and RFC 3339 strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerdeTimestamp(pub DateTime<FixedOffset>);
+
+impl Serialize for SerdeSymbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(SYMBOL_NEWTYPE_NAME, &self.0)
+    }
+}
+
+impl Serialize for SerdeDecimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(DECIMAL_NEWTYPE_NAME, &self.0.to_string())
+    }
+}
+
+impl Serialize for SerdeTimestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let text = self.0.to_rfc3339_opts(SecondsFormat::AutoSi, false);
+        serializer.serialize_newtype_struct(TIMESTAMP_NEWTYPE_NAME, &text)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdeSymbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = deserializer.deserialize_newtype_struct(SYMBOL_NEWTYPE_NAME, TextVisitor)?;
+        Ok(SerdeSymbol(text))
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdeDecimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = deserializer.deserialize_newtype_struct(DECIMAL_NEWTYPE_NAME, TextVisitor)?;
+        parse_decimal(&text)
+            .map(SerdeDecimal)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdeTimestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = deserializer.deserialize_newtype_struct(TIMESTAMP_NEWTYPE_NAME, TextVisitor)?;
+        parse_timestamp(&text)
+            .map(SerdeTimestamp)
+            .map_err(de::Error::custom)
+    }
+}
+
+pub(crate) fn parse_decimal(text: &str) -> Result<BigDecimal, IonSerdeError> {
+    BigDecimal::from_str(text).map_err(|_| IonSerdeError::InvalidDecimal(text.to_string()))
+}
+
+pub(crate) fn parse_timestamp(text: &str) -> Result<DateTime<FixedOffset>, IonSerdeError> {
+    DateTime::parse_from_rfc3339(text)
+        .map_err(|_| IonSerdeError::InvalidTimestamp(text.to_string()))
+}
+
+// Accepts the text either wrapped in a newtype (Ion and most formats) or
+// directly (formats that don't keep newtypes, like serde_json).
+struct TextVisitor;
+
+impl<'de> Visitor<'de> for TextVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        String::deserialize(deserializer)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(value)
+    }
+}
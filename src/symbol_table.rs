@@ -1,6 +1,9 @@
 use crate::binary_parser_types::SYSTEM_SYMBOL_TABLE;
+use crate::catalog::Catalog;
+use crate::symbol_token::{ImportLocation, SymbolToken};
 use log::trace;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A table symbol. It can b used together with the "with_shared_table" method
 /// in order to define new shared tables.
@@ -10,26 +13,74 @@ pub enum Symbol {
     Dummy,
 }
 
+/// SIDs taken by an imported shared table in a local table.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct ImportedRange {
+    name: String,
+    version: u32,
+    first_id: usize,
+    len: usize,
+}
+
 #[derive(Eq, PartialEq, Debug)]
-pub struct LocalSymbolTable(Vec<Symbol>);
+pub struct LocalSymbolTable {
+    symbols: Vec<Symbol>,
+    // SID of every text in the table. When a text is repeated it keeps the
+    // lowest SID. Symbols are only added through `add_symbol`, which keeps
+    // it up to date.
+    ids_by_text: HashMap<String, usize>,
+    imports: Vec<ImportedRange>,
+}
 
 impl LocalSymbolTable {
     pub fn new() -> LocalSymbolTable {
-        LocalSymbolTable(
-            SYSTEM_SYMBOL_TABLE
-                .to_vec()
-                .iter()
-                .map(|s| Symbol::Symbol(s.to_string()))
-                .collect(),
-        )
+        let mut table = LocalSymbolTable {
+            symbols: Vec::with_capacity(SYSTEM_SYMBOL_TABLE.len()),
+            ids_by_text: HashMap::with_capacity(SYSTEM_SYMBOL_TABLE.len()),
+            imports: vec![],
+        };
+
+        // The symbol zero has no text, so "$0" is handled as any other text.
+        for (id, symbol) in SYSTEM_SYMBOL_TABLE.iter().enumerate() {
+            match id {
+                0 => table.add_symbol(Symbol::Dummy),
+                _ => table.add_symbol(Symbol::Symbol(symbol.to_string())),
+            };
+        }
+
+        table
     }
 
     pub fn add_symbol(&mut self, symbol: Symbol) -> usize {
-        let id = self.0.len();
-        self.0.push(symbol);
+        let id = self.symbols.len();
+
+        if let Symbol::Symbol(text) = &symbol {
+            self.ids_by_text.entry(text.clone()).or_insert(id);
+        }
+
+        self.symbols.push(symbol);
         id
     }
 
+    /// Adds the symbols of a shared table, filling with dummy symbols up
+    /// to `len` when the table has less symbols than declared.
+    pub fn add_imported_symbols(&mut self, name: &str, version: u32, slice: &[Symbol], len: usize) {
+        let first_id = self.symbols.len();
+
+        self.add_symbols(slice);
+
+        if len > slice.len() {
+            self.insert_dummy_symbols(len - slice.len());
+        }
+
+        self.imports.push(ImportedRange {
+            name: name.to_string(),
+            version,
+            first_id,
+            len: self.symbols.len() - first_id,
+        });
+    }
+
     pub fn add_symbols(&mut self, slice: &[Symbol]) {
         for symbol in slice {
             self.add_symbol(symbol.clone());
@@ -37,42 +88,90 @@ impl LocalSymbolTable {
     }
 
     pub fn get_symbol_by_id(&self, id: usize) -> Option<&Symbol> {
-        self.0.get(id)
+        self.symbols.get(id)
     }
 
-    pub fn get_id_by_symbol(&self, symbol: &str) -> Option<usize> {
-        self.0
+    pub fn get_import_location(&self, id: usize) -> Option<ImportLocation> {
+        self.imports
             .iter()
-            .enumerate()
-            .find(|(_, value)| {
-                if let Symbol::Symbol(value) = value {
-                    *value == symbol
-                } else {
-                    false
-                }
+            .find(|import| id >= import.first_id && id < import.first_id + import.len)
+            .map(|import| {
+                ImportLocation::new(import.name.clone(), id - import.first_id + 1)
+                    .with_import(import.version, import.len)
+            })
+    }
+
+    pub fn get_id_by_import_location(&self, location: &ImportLocation) -> Option<usize> {
+        self.imports
+            .iter()
+            .find(|import| {
+                import.name == location.import_name()
+                    && location.sid() > 0
+                    && location.sid() <= import.len
             })
-            .map(|value| value.0)
+            .map(|import| import.first_id + location.sid() - 1)
+    }
+
+    pub fn get_id_by_symbol(&self, symbol: &str) -> Option<usize> {
+        self.ids_by_text.get(symbol).copied()
     }
 
     pub fn insert_dummy_symbols(&mut self, max_len: usize) {
+        self.symbols.reserve(max_len);
+
         for _ in 0..max_len {
             self.add_symbol(Symbol::Dummy);
         }
     }
 
-    pub fn list_all_symbols(&self) -> &[Symbol] {
-        &self.0
+    /// Symbols declared by the table itself, after the system and the
+    /// imported ones.
+    pub fn list_local_symbols(&self) -> &[Symbol] {
+        let imported_len: usize = self.imports.iter().map(|import| import.len).sum();
+
+        &self.symbols[SYSTEM_SYMBOL_TABLE.len() + imported_len..]
+    }
+
+    pub fn list_imports(&self) -> Vec<Import> {
+        self.imports
+            .iter()
+            .map(|import| Import {
+                name: import.name.clone(),
+                version: Some(import.version),
+                max_len: Some(import.len),
+            })
+            .collect()
     }
 }
 
-#[derive(Debug)]
+/// A shared symbol table, as returned by a `Catalog`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SharedSymbolTable {
-    _name: String,
-    _version: u32,
+    name: String,
+    version: u32,
     symbols: Vec<Symbol>,
 }
 
 impl SharedSymbolTable {
+    pub fn new<S: Into<String>>(name: S, version: u32, symbols: &[String]) -> SharedSymbolTable {
+        SharedSymbolTable {
+            name: name.into(),
+            version,
+            symbols: symbols
+                .iter()
+                .map(|s| Symbol::Symbol(s.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     pub fn is_superset(&self, table: &SharedSymbolTable) -> bool {
         for (index, symbol) in table.symbols.iter().enumerate() {
             match self.symbols.get(index) {
@@ -99,7 +198,7 @@ impl SharedSymbolTable {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Import {
     pub(crate) name: String,
     pub(crate) version: Option<u32>,
@@ -114,12 +213,15 @@ pub enum SymbolContextError {
     MaxIdNeededWhenImportingANotFoundSharedTable,
     InternalParserErrorThisIsABug,
     NewTableIsNotSuperSetOfPrevious,
+    TableAlreadyImported,
+    SharedTableNotFound,
 }
 
 #[derive(Debug)]
 pub struct SymbolContext {
     current_table: LocalSymbolTable,
     shared_tables: HashMap<String, (u32, HashMap<u32, SharedSymbolTable>)>,
+    catalog: Option<Arc<dyn Catalog>>,
 }
 
 impl SymbolContext {
@@ -127,6 +229,63 @@ impl SymbolContext {
         SymbolContext {
             current_table: LocalSymbolTable::new(),
             shared_tables: HashMap::new(),
+            catalog: None,
+        }
+    }
+
+    /// Sets the catalog used to look for shared tables that have not been
+    /// added to the context.
+    pub fn set_catalog(&mut self, catalog: Arc<dyn Catalog>) {
+        self.catalog = Some(catalog);
+    }
+
+    /// Shared tables added to the context, both directly and from the
+    /// catalog, in no particular order.
+    pub fn get_shared_tables(&self) -> Vec<SharedSymbolTable> {
+        self.shared_tables
+            .values()
+            .flat_map(|(_, tables)| tables.values().cloned())
+            .collect()
+    }
+
+    /// Returns the shared table with the given version, looking for it in
+    /// the catalog if needed. When that version isn't available it returns
+    /// the highest one.
+    pub fn get_shared_table(&mut self, name: &str, version: u32) -> Option<&SharedSymbolTable> {
+        self.load_table_from_catalog(name, version);
+
+        let (highest_version, tables) = self.shared_tables.get(name)?;
+
+        tables.get(&version).or_else(|| tables.get(highest_version))
+    }
+
+    // Adds the table from the catalog unless the context has the version
+    // already. The catalog may return another version when it doesn't have
+    // the requested one.
+    fn load_table_from_catalog(&mut self, name: &str, version: u32) {
+        let has_version = self
+            .shared_tables
+            .get(name)
+            .is_some_and(|(_, tables)| tables.contains_key(&version));
+
+        if has_version {
+            return;
+        }
+
+        let table = match &self.catalog {
+            Some(catalog) => catalog.get_table(name, version),
+            None => None,
+        };
+
+        if let Some(table) = table {
+            let SharedSymbolTable {
+                name,
+                version,
+                symbols,
+            } = table;
+
+            // The version may be there already if it isn't the requested one
+            let _ = self.add_shared_table(name, version, &symbols);
         }
     }
 
@@ -143,8 +302,8 @@ impl SymbolContext {
         symbols: &[Symbol],
     ) -> Result<(), SymbolContextError> {
         let new_table = SharedSymbolTable {
-            _name: name.clone(),
-            _version: version,
+            name: name.clone(),
+            version,
             symbols: symbols.to_vec(),
         };
 
@@ -219,6 +378,8 @@ impl SymbolContext {
                 1
             };
 
+            self.load_table_from_catalog(&import.name, version);
+
             match self.shared_tables.get(&import.name) {
                 Some(table_collection) => match table_collection.1.get(&version) {
                     Some(table) => {
@@ -227,7 +388,9 @@ impl SymbolContext {
                             None => table.get_all_symbols(),
                         };
 
-                        new_table.add_symbols(symbols);
+                        let len = import.max_len.unwrap_or(symbols.len());
+
+                        new_table.add_imported_symbols(&import.name, version, symbols, len);
                     }
                     None => {
                         if let Some(max_len) = import.max_len {
@@ -238,8 +401,17 @@ impl SymbolContext {
                                 }
                             };
 
+                            // Versions are supersets of the previous ones, so
+                            // the import is the same as one of the version used.
+                            // It keeps the version declared, so it is written
+                            // again the same way.
                             let symbols = table.get_symbols_max_len(max_len);
-                            new_table.add_symbols(symbols);
+                            new_table.add_imported_symbols(
+                                &import.name,
+                                version,
+                                symbols,
+                                max_len,
+                            );
                         } else {
                             return Err(SymbolContextError::MaxIdNeededWhenImportingASharedTableWhereVersionIsNotAvailable);
                         }
@@ -247,7 +419,7 @@ impl SymbolContext {
                 },
                 None => {
                     if let Some(len) = import.max_len {
-                        new_table.insert_dummy_symbols(len);
+                        new_table.add_imported_symbols(&import.name, version, &[], len);
                     } else {
                         return Err(
                             SymbolContextError::MaxIdNeededWhenImportingANotFoundSharedTable,
@@ -270,8 +442,46 @@ impl SymbolContext {
         Ok(())
     }
 
-    pub fn get_symbol_by_id(&self, id: usize) -> Option<&Symbol> {
-        self.current_table.get_symbol_by_id(id)
+    /// Resolves a SID to a token. Symbols without text get their position
+    /// in the shared table they come from, if any. It returns None for SIDs
+    /// that are not defined.
+    pub fn get_symbol_token(&self, id: usize) -> Option<SymbolToken> {
+        let token = match self.current_table.get_symbol_by_id(id)? {
+            Symbol::Symbol(text) => SymbolToken::new(text.clone()),
+            Symbol::Dummy => match self.current_table.get_import_location(id) {
+                Some(location) => SymbolToken::from_import_location(location),
+                None => SymbolToken::unknown(),
+            },
+        };
+
+        Some(token.with_sid(id))
+    }
+
+    pub fn get_id_by_import_location(&self, location: &ImportLocation) -> Option<usize> {
+        self.current_table.get_id_by_import_location(location)
+    }
+
+    /// Amount of symbols in the current table, imported and local, without
+    /// the system ones.
+    pub fn get_symbols_len(&self) -> usize {
+        self.current_table.symbols.len() - SYSTEM_SYMBOL_TABLE.len()
+    }
+
+    /// Shared tables imported by the current table.
+    pub fn get_imports(&self) -> Vec<Import> {
+        self.current_table.list_imports()
+    }
+
+    /// Replaces the imports of the current table keeping its local symbols,
+    /// which get new SIDs after the imported ones.
+    pub fn set_imports(&mut self, imports: &[Import]) -> Result<(), SymbolContextError> {
+        let symbols = self.current_table.list_local_symbols().to_vec();
+
+        self.set_new_table(imports, &symbols)
+    }
+
+    pub fn get_id_by_symbol(&self, symbol: &str) -> Option<usize> {
+        self.current_table.get_id_by_symbol(symbol)
     }
 
     pub fn insert_symbol(&mut self, symbol: &str) -> usize {
@@ -283,8 +493,9 @@ impl SymbolContext {
         }
     }
 
-    pub fn dump_all_local_symbols(&self) -> Vec<String> {
-        self.current_table.list_all_symbols()[10..]
+    /// Local symbols, skipping the first `index` ones.
+    pub fn dump_local_symbols_from(&self, index: usize) -> Vec<String> {
+        self.current_table.list_local_symbols()[index..]
             .iter()
             .map(|s| match s {
                 Symbol::Symbol(name) => name.clone(),
@@ -0,0 +1,284 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Position of a symbol inside a shared symbol table. Symbols whose text is
+/// unknown because the shared table wasn't available when parsing keep it,
+/// so they can be encoded again with the same meaning.
+///
+/// Locations read from a stream also keep the version and max_id of the
+/// import they come from, so encoders declare the same import again. They
+/// are not taken into account when comparing locations.
+#[derive(Debug, Clone)]
+pub struct ImportLocation {
+    import_name: String,
+    sid: usize,
+    import_version: Option<u32>,
+    import_max_id: Option<usize>,
+}
+
+impl ImportLocation {
+    /// Creates a location for the symbol at the position `sid` (starting at
+    /// 1) of the shared table named `import_name`.
+    pub fn new<S: Into<String>>(import_name: S, sid: usize) -> ImportLocation {
+        ImportLocation {
+            import_name: import_name.into(),
+            sid,
+            import_version: None,
+            import_max_id: None,
+        }
+    }
+
+    /// Sets the version and max_id of the import declaring the shared
+    /// table.
+    pub fn with_import(mut self, version: u32, max_id: usize) -> ImportLocation {
+        self.import_version = Some(version);
+        self.import_max_id = Some(max_id);
+        self
+    }
+
+    pub fn import_name(&self) -> &str {
+        &self.import_name
+    }
+
+    /// Position of the symbol in the shared table, starting at 1.
+    pub fn sid(&self) -> usize {
+        self.sid
+    }
+
+    pub fn import_version(&self) -> Option<u32> {
+        self.import_version
+    }
+
+    pub fn import_max_id(&self) -> Option<usize> {
+        self.import_max_id
+    }
+}
+
+impl PartialEq for ImportLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.import_name == other.import_name && self.sid == other.sid
+    }
+}
+
+impl Eq for ImportLocation {}
+
+impl Ord for ImportLocation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.import_name, self.sid).cmp(&(&other.import_name, other.sid))
+    }
+}
+
+impl PartialOrd for ImportLocation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::hash::Hash for ImportLocation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.import_name.hash(state);
+        self.sid.hash(state);
+    }
+}
+
+/// An Ion symbol as used by symbol values, annotations and the field names
+/// of `IonValue::OrderedStruct`.
+///
+/// Most symbols have text, but the text may be unknown: `$0` has no text
+/// and symbols imported from shared tables that are not in the catalog
+/// only have their position in that table (the `ImportLocation`).
+///
+/// The SID is the one the symbol had in the stream it was read from. It is
+/// only informative and it isn't taken into account when comparing tokens,
+/// as it depends on the symbol table in use. Two tokens are equal if they
+/// have the same text or, when the text is unknown, the same import
+/// location.
+///
+/// Tokens are usually created from strings:
+///
+/// ```rust
+/// use ion_binary_rs::{IonValue, SymbolToken};
+///
+/// let value = IonValue::Symbol("sedan".into());
+///
+/// assert_eq!(value, IonValue::Symbol(SymbolToken::new("sedan")));
+/// assert_eq!(SymbolToken::from("sedan").text(), Some("sedan"));
+/// assert_eq!(SymbolToken::unknown().text(), None);
+/// ```
+#[derive(Clone, Eq)]
+pub struct SymbolToken {
+    text: Option<String>,
+    sid: Option<usize>,
+    import_location: Option<ImportLocation>,
+}
+
+impl SymbolToken {
+    /// A symbol with the given text.
+    pub fn new<S: Into<String>>(text: S) -> SymbolToken {
+        SymbolToken {
+            text: Some(text.into()),
+            sid: None,
+            import_location: None,
+        }
+    }
+
+    /// The symbol zero (`$0`), which has no text.
+    pub fn unknown() -> SymbolToken {
+        SymbolToken {
+            text: None,
+            sid: Some(0),
+            import_location: None,
+        }
+    }
+
+    /// A symbol without text coming from the given shared table position.
+    pub fn from_import_location(import_location: ImportLocation) -> SymbolToken {
+        SymbolToken {
+            text: None,
+            sid: None,
+            import_location: Some(import_location),
+        }
+    }
+
+    /// Sets the SID the symbol had in the stream it was read from.
+    pub fn with_sid(mut self, sid: usize) -> SymbolToken {
+        self.sid = Some(sid);
+        self
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    pub fn sid(&self) -> Option<usize> {
+        self.sid
+    }
+
+    pub fn import_location(&self) -> Option<&ImportLocation> {
+        self.import_location.as_ref()
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.text.is_none()
+    }
+
+    /// Returns the text, if any, consuming the token.
+    pub fn into_text(self) -> Option<String> {
+        self.text
+    }
+}
+
+impl PartialEq for SymbolToken {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.text, &other.text) {
+            (Some(text), Some(other_text)) => text == other_text,
+            (None, None) => self.import_location == other.import_location,
+            _ => false,
+        }
+    }
+}
+
+// Tokens with text go first, ordered by it, and then the ones with unknown
+// text, ordered by their import location.
+impl Ord for SymbolToken {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.text, &other.text) {
+            (Some(text), Some(other_text)) => text.cmp(other_text),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.import_location.cmp(&other.import_location),
+        }
+    }
+}
+
+impl PartialOrd for SymbolToken {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::hash::Hash for SymbolToken {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+
+        if self.text.is_none() {
+            self.import_location.hash(state);
+        }
+    }
+}
+
+impl PartialEq<str> for SymbolToken {
+    fn eq(&self, other: &str) -> bool {
+        self.text.as_deref() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for SymbolToken {
+    fn eq(&self, other: &&str) -> bool {
+        self.text.as_deref() == Some(*other)
+    }
+}
+
+impl PartialEq<String> for SymbolToken {
+    fn eq(&self, other: &String) -> bool {
+        self.text.as_deref() == Some(other.as_str())
+    }
+}
+
+impl From<String> for SymbolToken {
+    fn from(text: String) -> Self {
+        SymbolToken::new(text)
+    }
+}
+
+impl From<&String> for SymbolToken {
+    fn from(text: &String) -> Self {
+        SymbolToken::new(text.clone())
+    }
+}
+
+impl From<&str> for SymbolToken {
+    fn from(text: &str) -> Self {
+        SymbolToken::new(text)
+    }
+}
+
+// Tokens with text are printed as strings, so debug output of values stays
+// the same as when symbols were plain strings.
+impl fmt::Debug for SymbolToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.text, &self.import_location) {
+            (Some(text), _) => fmt::Debug::fmt(text, f),
+            (None, Some(location)) => write!(f, "{}#{}", location.import_name, location.sid),
+            (None, None) => write!(f, "$0"),
+        }
+    }
+}
+
+/// Struct field names are Strings in `IonValue::Struct` and SymbolTokens in
+/// `IonValue::OrderedStruct`, so encoders take both through this trait.
+pub(crate) trait FieldName: Ord {
+    fn text(&self) -> Option<&str>;
+
+    fn import_location(&self) -> Option<&ImportLocation>;
+}
+
+impl FieldName for String {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+
+    fn import_location(&self) -> Option<&ImportLocation> {
+        None
+    }
+}
+
+impl FieldName for SymbolToken {
+    fn text(&self) -> Option<&str> {
+        SymbolToken::text(self)
+    }
+
+    fn import_location(&self) -> Option<&ImportLocation> {
+        SymbolToken::import_location(self)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    AsyncIonParser, AsyncIonWriter, IonEncoder, IonParser, IonParserError, IonParserOptions,
+    IonValue, IonWriter,
+};
+use tokio::io::{duplex, AsyncWriteExt};
+
+fn test_values() -> Vec<IonValue> {
+    (0..100)
+        .map(|index| {
+            IonValue::OrderedStruct(vec![
+                (
+                    format!("field_{}", index % 7).into(),
+                    IonValue::Symbol(format!("symbol_{}", index).into()),
+                ),
+                (
+                    "id".into(),
+                    IonValue::Annotation(
+                        vec![format!("annotation_{}", index % 3).into()],
+                        Box::new(IonValue::Integer(index)),
+                    ),
+                ),
+            ])
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn values_go_through_a_duplex_stream() {
+    let values = test_values();
+    let (client, server) = duplex(64);
+
+    let sent = values.clone();
+
+    let writing = tokio::spawn(async move {
+        let mut writer = AsyncIonWriter::with_flush_threshold(client, 256);
+
+        for value in &sent {
+            writer.write(value).await.unwrap();
+        }
+
+        writer.finish().await.unwrap().shutdown().await.unwrap();
+    });
+
+    let mut parser = AsyncIonParser::new(server);
+    parser.with_ordered_structs();
+
+    let mut received = vec![];
+
+    while let Some(value) = parser.next_value().await.unwrap() {
+        received.push(value);
+    }
+
+    writing.await.unwrap();
+
+    assert!(received
+        .iter()
+        .all(|value| matches!(value, IonValue::OrderedStruct(_))));
+    assert_eq!(received, values);
+}
+
+#[tokio::test]
+async fn async_writer_writes_as_the_writer() {
+    let values = test_values();
+
+    let mut writer = IonWriter::with_flush_threshold(Vec::new(), 512);
+    let mut async_writer = AsyncIonWriter::with_flush_threshold(Vec::new(), 512);
+
+    for value in &values {
+        writer.write(value).unwrap();
+        async_writer.write(value).await.unwrap();
+    }
+
+    let bytes = writer.finish().unwrap();
+    let async_bytes = async_writer.finish().await.unwrap();
+
+    // Symbol table fields are not written in a fixed order, so only the
+    // length of the output can be compared.
+    assert_eq!(async_bytes.len(), bytes.len());
+
+    let mut parser = IonParser::new(&async_bytes[..]);
+    parser.with_ordered_structs();
+
+    let parsed = parser.consume_all().unwrap();
+
+    assert!(parsed
+        .iter()
+        .all(|value| matches!(value, IonValue::OrderedStruct(_))));
+    assert_eq!(parsed, values);
+}
+
+#[tokio::test]
+async fn async_parser_reports_errors_as_the_parser() {
+    let nested_non_utf8_string = [0xE0, 0x01, 0x00, 0xEA, 0xB5, 0xD4, 0x84, 0x82, 0xC3, 0x28];
+    let truncated_string = [0xE0, 0x01, 0x00, 0xEA, 0x21, 0x01, 0x85, b'a', b'b'];
+
+    for bytes in [&nested_non_utf8_string[..], &truncated_string] {
+        let mut parser = IonParser::new(bytes);
+        let mut async_parser = AsyncIonParser::new(bytes);
+
+        let expected = loop {
+            match parser.consume_value_located() {
+                Ok((value, _)) => assert_eq!(async_parser.next_value().await, Ok(Some(value))),
+                Err(error) => break error,
+            }
+        };
+
+        let error = async_parser.next_value().await.unwrap_err();
+
+        assert_eq!(error, expected);
+        assert_eq!(error.offset(), expected.offset());
+        assert_eq!(error.path(), expected.path());
+    }
+}
+
+#[tokio::test]
+async fn async_parser_checks_limits_before_reading_the_value() {
+    let (mut client, server) = duplex(64);
+
+    // A blob declaring 256 MiB, with the stream left open
+    client
+        .write_all(&[0xE0, 0x01, 0x00, 0xEA, 0xAE, 0x7F, 0x7F, 0x7F, 0xFF])
+        .await
+        .unwrap();
+
+    let mut parser = AsyncIonParser::new(server);
+    parser.with_options(IonParserOptions {
+        max_value_size: Some(1024),
+        ..IonParserOptions::default()
+    });
+
+    assert_eq!(
+        parser.next_value().await.unwrap_err(),
+        IonParserError::MaxValueSizeExceeded
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::String("a".repeat(100)));
+    let bytes = encoder.encode();
+
+    let mut parser = AsyncIonParser::new(&bytes[..]);
+    parser.with_options(IonParserOptions {
+        max_total_input: Some(bytes.len() - 1),
+        ..IonParserOptions::default()
+    });
+
+    assert_eq!(
+        parser.next_value().await.unwrap_err(),
+        IonParserError::MaxTotalInputExceeded
+    );
+}
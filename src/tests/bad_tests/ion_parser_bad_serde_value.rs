@@ -7,7 +7,7 @@ use crate::{IonParserError, IonValue};
 
 #[test]
 fn serde_from_ion_symbol() {
-    let bad_value = IonValue::Symbol("any".to_string());
+    let bad_value = IonValue::Symbol("any".into());
     let result: Result<Value, IonParserError> = bad_value.clone().try_into();
     let error = result.unwrap_err();
 
@@ -16,8 +16,11 @@ fn serde_from_ion_symbol() {
 
 #[test]
 fn serde_from_ion_datetime() {
-    let bad_value =
-        IonValue::DateTime(DateTime::parse_from_rfc3339("1997-12-11T16:39:27-00:00").unwrap());
+    let bad_value = IonValue::DateTime(
+        DateTime::parse_from_rfc3339("1997-12-11T16:39:27-00:00")
+            .unwrap()
+            .into(),
+    );
     let result: Result<Value, IonParserError> = bad_value.clone().try_into();
     let error = result.unwrap_err();
 
@@ -26,7 +29,7 @@ fn serde_from_ion_datetime() {
 
 #[test]
 fn serde_from_ion_annotation() {
-    let bad_value = IonValue::Annotation(vec!["one".to_string()], Box::new(IonValue::Bool(true)));
+    let bad_value = IonValue::Annotation(vec!["one".into()], Box::new(IonValue::Bool(true)));
     let result: Result<Value, IonParserError> = bad_value.clone().try_into();
     let error = result.unwrap_err();
 
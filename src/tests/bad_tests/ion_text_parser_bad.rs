@@ -0,0 +1,19 @@
+use crate::tests::test_utils::list_text_testsuite_files;
+use crate::IonTextParser;
+use std::fs::File;
+use std::io::BufReader;
+
+#[test]
+fn text_bad_files() {
+    let mut accepted = Vec::new();
+
+    for path in list_text_testsuite_files("bad") {
+        let file = BufReader::new(File::open(&path).unwrap());
+
+        if IonTextParser::new(file).consume_all().is_ok() {
+            accepted.push(path);
+        }
+    }
+
+    assert!(accepted.is_empty(), "{:#?}", accepted);
+}
@@ -3,3 +3,4 @@ mod ion_parser_bad_others;
 mod ion_parser_bad_serde_value;
 mod ion_parser_bad_timestamp;
 mod ion_parser_bad_typecodes;
+mod ion_text_parser_bad;
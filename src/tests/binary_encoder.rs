@@ -50,6 +50,7 @@ fn encode_integer_big() {
 
     let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
 
+    assert!(matches!(resulting_ion_value, IonValue::BigInteger(_)));
     assert_eq!(ion_value, resulting_ion_value);
 }
 
@@ -63,6 +64,7 @@ fn encode_integer_big_negative() {
 
     let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
 
+    assert!(matches!(resulting_ion_value, IonValue::BigInteger(_)));
     assert_eq!(ion_value, resulting_ion_value);
 }
 
@@ -170,6 +172,19 @@ fn encode_integer_decimal() {
     }
 }
 
+#[test]
+fn encode_decimal_positive_exponent() {
+    for text in ["0e5", "1e5", "-12e3"] {
+        let ion_value = IonValue::Decimal(BigDecimal::from_str(text).unwrap());
+
+        let bytes = encode_ion_value(&ion_value);
+
+        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+
+        assert!(ion_value.ion_eq(&resulting_ion_value), "{}", text);
+    }
+}
+
 #[test]
 fn encode_integer_string() {
     let values: Vec<String> = vec![
@@ -248,7 +263,7 @@ fn encode_integer_datetime() {
     ];
 
     for ion_value in values {
-        let ion_value = IonValue::DateTime(ion_value);
+        let ion_value = IonValue::DateTime(ion_value.into());
 
         let bytes = encode_ion_value(&ion_value);
 
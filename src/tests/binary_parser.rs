@@ -9,7 +9,7 @@ fn decode_value_null() {
     let mut lexer = IonBinaryParser::new(&ion_test[..]);
 
     assert_eq!(
-        lexer.consume_value_header(0),
+        lexer.consume_opcode(0).and_then(ValueHeader::new),
         Ok(ValueHeader::new(0/*Null Value*/ + 15 /*Len for null Value*/).unwrap())
     );
 }
@@ -349,7 +349,7 @@ fn decode_value_with_version_header() {
     let mut lexer = IonBinaryParser::new(&ion_test[..]);
 
     assert_eq!(
-        lexer.consume_value_header(0),
+        lexer.consume_opcode(0).and_then(ValueHeader::new),
         Ok(ValueHeader::new((0xe << 4) /*Annotation*/ + 14 /*Long len*/).unwrap())
     );
 }
@@ -0,0 +1,199 @@
+use crate::{
+    Catalog, FileCatalog, ImportLocation, InMemoryCatalog, IonEncoder, IonParser, IonParserError,
+    IonTextParser, IonValue, SharedSymbolTable, SymbolContextError, SymbolToken,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn imported(name: &str, sid: usize) -> SymbolToken {
+    SymbolToken::from_import_location(ImportLocation::new(name, sid))
+}
+
+fn cars_table(version: u32, symbols: &[&str]) -> SharedSymbolTable {
+    let symbols: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+
+    SharedSymbolTable::new("cars", version, &symbols)
+}
+
+fn encode(values: Vec<IonValue>) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+
+    for value in values {
+        encoder.add(value);
+    }
+
+    encoder.encode()
+}
+
+#[test]
+fn in_memory_catalog_versions() {
+    let mut catalog = InMemoryCatalog::new();
+
+    catalog.add_table(cars_table(1, &["brand"])).unwrap();
+    catalog
+        .add_table(cars_table(3, &["brand", "model"]))
+        .unwrap();
+
+    assert_eq!(
+        catalog.add_table(cars_table(1, &["brand"])),
+        Err(SymbolContextError::TableVersionAlreadyThere)
+    );
+
+    assert_eq!(
+        catalog.get_table("cars", 1),
+        Some(cars_table(1, &["brand"]))
+    );
+    assert_eq!(catalog.get_table("cars", 2).unwrap().version(), 3);
+    assert_eq!(catalog.get_table("cars", 3).unwrap().name(), "cars");
+    assert_eq!(catalog.get_table("trucks", 1), None);
+}
+
+#[test]
+fn parser_looks_up_imports_in_the_catalog() {
+    let bytes = encode(vec![
+        IonValue::Symbol(imported("cars", 2)),
+        IonValue::Annotation(vec![imported("cars", 1)], Box::new(IonValue::Integer(1))),
+    ]);
+
+    let mut catalog = InMemoryCatalog::new();
+    catalog
+        .add_table(cars_table(1, &["brand", "model"]))
+        .unwrap();
+    let catalog = Arc::new(catalog);
+
+    let expected = vec![
+        IonValue::Symbol("model".into()),
+        IonValue::Annotation(vec!["brand".into()], Box::new(IonValue::Integer(1))),
+    ];
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.with_catalog(catalog.clone());
+    assert_eq!(parser.consume_all().unwrap(), expected);
+
+    let text =
+        r#"$ion_symbol_table::{ imports: [{ name: "cars", version: 1, max_id: 2 }] } $11 $10::1"#;
+
+    let mut parser = IonTextParser::new(text.as_bytes());
+    parser.with_catalog(catalog);
+    assert_eq!(parser.consume_all().unwrap(), expected);
+}
+
+#[test]
+fn parser_uses_other_catalog_versions_with_max_id() {
+    let mut catalog = InMemoryCatalog::new();
+    catalog
+        .add_table(cars_table(2, &["brand", "model", "year"]))
+        .unwrap();
+
+    let text =
+        r#"$ion_symbol_table::{ imports: [{ name: "cars", version: 1, max_id: 2 }] } $10 $11 $12"#;
+
+    let mut parser = IonTextParser::new(text.as_bytes());
+    parser.with_catalog(catalog);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Symbol("brand".into())
+    );
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Symbol("model".into())
+    );
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::SymbolIdNotDefined)
+    );
+}
+
+#[test]
+fn encoder_imports_tables_from_the_catalog() {
+    let mut catalog = InMemoryCatalog::new();
+    catalog
+        .add_table(cars_table(1, &["brand", "model"]))
+        .unwrap();
+    let catalog = Arc::new(catalog);
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_catalog(catalog.clone());
+    encoder.add(IonValue::Symbol(imported("cars", 2)));
+    encoder.add(IonValue::Symbol("brand".into()));
+    let bytes = encoder.encode();
+
+    // Without the catalog, the text symbol is also read from the import
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![
+            IonValue::Symbol(imported("cars", 2)),
+            IonValue::Symbol(imported("cars", 1)),
+        ]
+    );
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.with_catalog(catalog);
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol("model".into()),
+            IonValue::Symbol("brand".into()),
+        ]
+    );
+}
+
+#[test]
+fn file_catalog_loads_text_and_binary_files() {
+    let directory = std::env::temp_dir().join(format!("ion_catalog_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    std::fs::write(
+        directory.join("cars.ion"),
+        r#"
+            $ion_shared_symbol_table::{ name: "cars", version: 1, symbols: ["brand", "model"] }
+            $ion_shared_symbol_table::{ name: "cars", version: 2, symbols: ["brand", "model", "year"] }
+        "#,
+    )
+    .unwrap();
+
+    let mut table = HashMap::new();
+    table.insert("name".to_string(), IonValue::String("trucks".to_string()));
+    table.insert("version".to_string(), IonValue::Integer(1));
+    table.insert(
+        "symbols".to_string(),
+        IonValue::List(vec![IonValue::String("axles".to_string())]),
+    );
+
+    std::fs::write(
+        directory.join("trucks.10n"),
+        encode(vec![IonValue::Annotation(
+            vec!["$ion_shared_symbol_table".into()],
+            Box::new(IonValue::Struct(table)),
+        )]),
+    )
+    .unwrap();
+
+    let mut catalog = FileCatalog::new();
+    let result = catalog.load_directory(&directory);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+    result.unwrap();
+
+    assert_eq!(
+        catalog.get_table("cars", 1),
+        Some(cars_table(1, &["brand", "model"]))
+    );
+    assert_eq!(catalog.get_table("cars", 2).unwrap().version(), 2);
+    assert_eq!(
+        catalog.get_table("trucks", 1),
+        Some(SharedSymbolTable::new("trucks", 1, &["axles".to_string()]))
+    );
+
+    let text = r#"$ion_symbol_table::{ imports: [{ name: "trucks", version: 1 }] } $10"#;
+
+    let mut parser = IonTextParser::new(text.as_bytes());
+    parser.with_catalog(catalog);
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![IonValue::Symbol("axles".into())]
+    );
+}
@@ -0,0 +1,119 @@
+use crate::{
+    InMemoryCatalog, IonEncoder, IonParser, IonValue, SharedSymbolTable, SymbolContextError,
+};
+use std::collections::HashMap;
+
+fn field_names() -> Vec<String> {
+    (0..200).map(|index| format!("field_{}", index)).collect()
+}
+
+fn message(index: i64) -> IonValue {
+    let mut fields: HashMap<String, IonValue> = field_names()
+        .into_iter()
+        .map(|name| (name, IonValue::Integer(index)))
+        .collect();
+
+    fields.insert(
+        "local_field".to_string(),
+        IonValue::Symbol("field_3".into()),
+    );
+
+    IonValue::Annotation(vec!["field_7".into()], Box::new(IonValue::Struct(fields)))
+}
+
+#[test]
+fn encoder_uses_shared_table_sids() {
+    let mut encoder = IonEncoder::new();
+    encoder
+        .with_shared_table("messages".to_string(), 1, &field_names())
+        .unwrap();
+    encoder.add(message(1));
+    let bytes = encoder.encode();
+
+    let mut plain_encoder = IonEncoder::new();
+    plain_encoder.add(message(1));
+    let plain_bytes = plain_encoder.encode();
+
+    assert!(bytes.len() * 3 < plain_bytes.len());
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser
+        .with_shared_table("messages".to_string(), 1, &field_names())
+        .unwrap();
+
+    assert_eq!(parser.consume_all().unwrap(), vec![message(1)]);
+
+    // Without the shared table the field names only have their location
+    let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    match &values[..] {
+        [IonValue::Annotation(_, value)] => match &**value {
+            IonValue::OrderedStruct(fields) => {
+                assert!(fields
+                    .iter()
+                    .filter(|(name, _)| *name != "local_field")
+                    .all(|(name, _)| name.import_location().is_some()));
+            }
+            value => panic!("Expected an ordered struct, got {:?}", value),
+        },
+        values => panic!("Expected an annotated struct, got {:?}", values),
+    }
+}
+
+#[test]
+fn encoder_declares_only_the_missing_symbols() {
+    let mut encoder = IonEncoder::new();
+    encoder
+        .with_shared_table("messages".to_string(), 1, &field_names())
+        .unwrap();
+    encoder.add(IonValue::Symbol("field_0".into()));
+    encoder.add(IonValue::Symbol("other".into()));
+
+    let bytes = encoder.encode();
+
+    let contains = |text: &str| {
+        bytes
+            .windows(text.len())
+            .any(|window| window == text.as_bytes())
+    };
+
+    assert!(contains("messages"));
+    assert!(contains("other"));
+    assert!(!contains("field_0"));
+
+    // $10 (field_0) and $210 (other), after the 200 imported symbols
+    assert_eq!(bytes[bytes.len() - 4..], [0x71, 0x0A, 0x71, 0xD2]);
+}
+
+#[test]
+fn encoder_imports_tables_from_the_catalog() {
+    let mut catalog = InMemoryCatalog::new();
+    catalog
+        .add_table(SharedSymbolTable::new("messages", 2, &field_names()))
+        .unwrap();
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_catalog(catalog);
+
+    assert_eq!(
+        encoder.with_import("unknown".to_string(), 1),
+        Err(SymbolContextError::SharedTableNotFound)
+    );
+
+    encoder.with_import("messages".to_string(), 1).unwrap();
+
+    assert_eq!(
+        encoder.with_import("messages".to_string(), 2),
+        Err(SymbolContextError::TableAlreadyImported)
+    );
+
+    encoder.add(message(2));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser
+        .with_shared_table("messages".to_string(), 2, &field_names())
+        .unwrap();
+
+    assert_eq!(parser.consume_all().unwrap(), vec![message(2)]);
+}
@@ -1,5 +1,7 @@
 use crate::{hashmap, read_file_testsuite};
-use crate::{ion_parser::IonParser, ion_parser_types::IonValue, IonParserError, ParsingError};
+use crate::{
+    ion_parser::IonParser, ion_parser_types::IonValue, IonParserError, ParsingError, SymbolToken,
+};
 use std::fs::File;
 use std::io::BufReader;
 
@@ -154,47 +156,47 @@ fn item1() {
     use IonValue::*;
 
     let expected = IonValue::Annotation(
-        vec!["iopg18".to_string()],
+        vec!["iopg18".into()],
         Box::new(IonValue::Struct(hashmap!(
             "iopg14".to_string() => String("BT00DCN9OK".to_string()),
             "iopg15".to_string() => Integer(1),
             "iopg17".to_string() => Struct(hashmap!(
-                "iopg20".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg108".to_string())))]),
+                "iopg20".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg108".into())))]),
                 "iopg26".to_string() => List(vec![Struct(hashmap!(
-                    "iopc10".to_string() => Symbol("iopc1".to_string()),
+                    "iopc10".to_string() => Symbol("iopc1".into()),
                     "iopc9".to_string() => String("unhappiest discordant droppers".to_string())
                 ))]),
                 "iopg51".to_string() => List(vec![Struct(hashmap!(
-                    "iopc10".to_string() => Symbol("iopc1".to_string()),
+                    "iopc10".to_string() => Symbol("iopc1".into()),
                     "iopc9".to_string() => String("Edna disgusts mascara".to_string())
                 ))]),
-                "iopg22".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg108".to_string())))]),
+                "iopg22".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg108".into())))]),
                 "iopg28".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Integer(2)))]),
                 "iopg60".to_string() => List(vec![Struct(hashmap!(
-                    "iopc10".to_string() => Symbol("iopc1".to_string()),
+                    "iopc10".to_string() => Symbol("iopc1".into()),
                     "iopc9".to_string() => String("his deployment microsystems".to_string())
                 ))]),
                 "iopg1123".to_string() => List(vec![Struct(hashmap!(
-                    "iopc10".to_string() => Symbol("iopc1".to_string()),
+                    "iopc10".to_string() => Symbol("iopc1".into()),
                     "iopc9".to_string() => String("unhappiest discordant droppers".to_string())
                 ))]),
                 "iopg5350".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Bool(true)))]),
-                "iopg23".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg150".to_string())))]),
+                "iopg23".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg150".into())))]),
                 "iopg1244".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => String("641251497029891251497028".to_string())))]),
                 "iopg95".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => String("skydiving-altimeters".to_string())))]),
-                "iopg25".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg27".to_string())))]),
-                "iopg7178".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg9880".to_string())))]),
-                "iopg7233".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg9886".to_string())))]),
+                "iopg25".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg27".into())))]),
+                "iopg7178".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg9880".into())))]),
+                "iopg7233".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg9886".into())))]),
                 "iopg39".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => String("9712514907027".to_string())))]),
                 "iopg103".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => String("641251497029891251497028".to_string())))]),
                 "iopg33".to_string() => List(vec![Struct(hashmap!(
                     "iopc9".to_string() => String("metaphysics Urquhart Cyclops".to_string()),
-                    "iopc10".to_string() => Symbol("iopc1".to_string())
+                    "iopc10".to_string() => Symbol("iopc1".into())
                 ))]),
-                "iopg30".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => DateTime(ChronoDateTime::parse_from_rfc3339("2010-09-10T19:59:51+00:00").unwrap())))]),
-                "iopg31".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg132".to_string())))]),
-                "iopg19".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg135".to_string())))]),
-                "iopg21".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg38".to_string())))])
+                "iopg30".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => DateTime(ChronoDateTime::parse_from_rfc3339("2010-09-10T19:59:51+00:00").unwrap().into())))]),
+                "iopg31".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg132".into())))]),
+                "iopg19".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg135".into())))]),
+                "iopg21".to_string() => List(vec![Struct(hashmap!("iopc9".to_string() => Symbol("iopg38".into())))])
             )),
             "version".to_string() => Integer(2)
         ))),
@@ -214,7 +216,7 @@ fn symbol_explicit_zero() {
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into())
+        IonValue::Symbol(SymbolToken::unknown())
     );
 }
 
@@ -226,7 +228,7 @@ fn symbol_implicit_zero() {
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into())
+        IonValue::Symbol(SymbolToken::unknown())
     );
 }
 
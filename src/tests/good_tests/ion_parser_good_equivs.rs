@@ -89,22 +89,16 @@ fn equivs_timestamp_fractions() {
     if let IonValue::SExpr(ref list) = value {
         assert_eq!(list.len(), 4);
         assert_eq!(
-            list[0],
-            IonValue::DateTime(
-                chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
-            )
+            chrono::DateTime::<chrono::FixedOffset>::try_from(&list[0]).unwrap(),
+            chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
         );
         assert_eq!(
-            list[1],
-            IonValue::DateTime(
-                chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
-            )
+            chrono::DateTime::<chrono::FixedOffset>::try_from(&list[1]).unwrap(),
+            chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
         );
         assert_eq!(
-            list[2],
-            IonValue::DateTime(
-                chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
-            )
+            chrono::DateTime::<chrono::FixedOffset>::try_from(&list[2]).unwrap(),
+            chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
         );
         assert_eq!(list[0], list[1]);
         assert_eq!(list[1], list[2]);
@@ -125,16 +119,12 @@ fn equivs_timestamp_superfluous_offset() {
     if let IonValue::SExpr(ref list) = value {
         assert_eq!(list.len(), 2);
         assert_eq!(
-            list[0],
-            IonValue::DateTime(
-                chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
-            )
+            chrono::DateTime::<chrono::FixedOffset>::try_from(&list[0]).unwrap(),
+            chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
         );
         assert_eq!(
-            list[1],
-            IonValue::DateTime(
-                chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
-            )
+            chrono::DateTime::<chrono::FixedOffset>::try_from(&list[1]).unwrap(),
+            chrono::DateTime::parse_from_rfc3339("0001-01-01T00:00:00+00:00").unwrap()
         );
         assert_eq!(list[0], list[1]);
     } else {
@@ -13,7 +13,7 @@ fn struct_annotated_empty() {
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Annotation(
-            ["max_id".to_string()].to_vec(),
+            vec!["max_id".into()],
             Box::new(IonValue::Struct(HashMap::new()))
         )
     );
@@ -28,7 +28,7 @@ fn struct_annotated_ordered() {
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Annotation(
-            ["symbols".to_string(), "max_id".to_string()].to_vec(),
+            vec!["symbols".into(), "max_id".into()],
             Box::new(IonValue::Struct(hashmap!(
                 "version".to_string() => IonValue::Bool(false),
                 "imports".to_string() => IonValue::Bool(true),
@@ -1,8 +1,10 @@
 use crate::ion_parser::IonParser;
 use crate::read_file_testsuite;
-use crate::IonValue;
+use crate::{IonTimestamp, TimestampPrecision};
+use bigdecimal::BigDecimal;
 use std::fs::File;
 use std::io::BufReader;
+use std::str::FromStr;
 
 #[test]
 fn timestamp_timestamp2011() {
@@ -11,13 +13,11 @@ fn timestamp_timestamp2011() {
     let mut parser = IonParser::new(ion_blob);
 
     let value = parser.consume_value().unwrap().0;
+    let timestamp = IonTimestamp::try_from(&value).unwrap();
 
-    assert_eq!(
-        value,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap()
-        )
-    );
+    assert_eq!(timestamp, IonTimestamp::from_year(2011).unwrap());
+    assert_eq!(timestamp.precision(), TimestampPrecision::Year);
+    assert_eq!(timestamp.offset(), None);
 }
 
 #[test]
@@ -27,13 +27,11 @@ fn timestamp_timestamp2011_02() {
     let mut parser = IonParser::new(ion_blob);
 
     let value = parser.consume_value().unwrap().0;
+    let timestamp = IonTimestamp::try_from(&value).unwrap();
 
-    assert_eq!(
-        value,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00").unwrap()
-        )
-    );
+    assert_eq!(timestamp, IonTimestamp::from_year_month(2011, 2).unwrap());
+    assert_eq!(timestamp.precision(), TimestampPrecision::Month);
+    assert_eq!(timestamp.offset(), None);
 }
 
 #[test]
@@ -43,13 +41,11 @@ fn timestamp_timestamp2011_02_20() {
     let mut parser = IonParser::new(ion_blob);
 
     let value = parser.consume_value().unwrap().0;
+    let timestamp = IonTimestamp::try_from(&value).unwrap();
 
-    assert_eq!(
-        value,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-20T00:00:00+00:00").unwrap()
-        )
-    );
+    assert_eq!(timestamp, IonTimestamp::from_ymd(2011, 2, 20).unwrap());
+    assert_eq!(timestamp.precision(), TimestampPrecision::Day);
+    assert_eq!(timestamp.offset(), None);
 }
 
 #[test]
@@ -61,16 +57,20 @@ fn timestamp_timestamp2011_02_20_t19_30_59_100_08_00() {
     let mut parser = IonParser::new(ion_blob);
 
     let value = parser.consume_value().unwrap().0;
-
-    assert_eq!(
-        value,
-        IonValue::DateTime(
-            // Note: In the binary the values are in UTC, but in the filename, the date has
-            // the same values as UTC but with a timezone, which is not correct. The binary
-            // content and the filename are two different dates. (I hope I'm right). That
-            // is why we changes from the hour 19 (file name) to the hour 11 in timezone -8
-            // as the binary contains a 19.
-            chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.100-08:00").unwrap()
-        )
-    );
+    let timestamp = IonTimestamp::try_from(&value).unwrap();
+
+    // Note: In the binary the values are in UTC, but in the filename, the date has
+    // the same values as UTC but with a timezone, which is not correct. The binary
+    // content and the filename are two different dates. (I hope I'm right). That
+    // is why we changes from the hour 19 (file name) to the hour 11 in timezone -8
+    // as the binary contains a 19.
+    let expected = IonTimestamp::from_ymd_hms(2011, 2, 20, 11, 30, 59, Some(-480))
+        .unwrap()
+        .with_fraction(BigDecimal::from_str("0.100").unwrap())
+        .unwrap();
+
+    assert_eq!(timestamp, expected);
+    assert_eq!(timestamp.precision(), TimestampPrecision::Second);
+    assert_eq!(timestamp.offset(), Some(-480));
+    assert_eq!(timestamp.to_string(), "2011-02-20T11:30:59.100-08:00");
 }
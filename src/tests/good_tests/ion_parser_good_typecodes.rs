@@ -2,6 +2,7 @@ use crate::hashmap;
 use crate::read_file_testsuite;
 use crate::{
     ion_parser::IonParser, ion_parser_types::IonValue, IonParserError, NullIonValue, ParsingError,
+    SymbolToken,
 };
 use bigdecimal::BigDecimal;
 use num_bigint::BigInt;
@@ -385,58 +386,51 @@ fn typecodes_t6_large() {
 
     // Seconds coefficient 1000000000000000000000000000000000
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     // Seconds coefficient 1000000000000000000000000000000018
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     // Seconds coefficient 1000000000000000000000000000004626
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     // Seconds coefficient 1000000000000000000000000001184274
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     // Seconds coefficient 1000000000000000000000000303174162
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     // Seconds coefficient 1000000000000000000000077612585490
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     // Seconds coefficient 1000000000000000000019868821885458
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 }
 
@@ -447,45 +441,39 @@ fn typecodes_t6_small() {
     let mut parser = IonParser::new(ion_blob);
 
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:00:00+00:00").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:00:00+00:00").unwrap()
     );
 
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:00:00+00:00").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:00:00+00:00").unwrap()
     );
 
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:00:00+00:00").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:00:00+00:00").unwrap()
     );
 
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2401-01-01T00:00:00+00:00").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("2401-01-01T00:00:00+00:00").unwrap()
     );
 
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:00-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:00-00:33").unwrap()
     );
 
     assert_eq!(
-        parser.consume_value().unwrap().0,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
-        ),
+        chrono::DateTime::<chrono::FixedOffset>::try_from(&parser.consume_value().unwrap().0)
+            .unwrap(),
+        chrono::DateTime::parse_from_rfc3339("0097-01-01T00:28:01-00:33").unwrap()
     );
 
     assert_eq!(
@@ -502,27 +490,27 @@ fn typecodes_t7_small() {
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
@@ -541,52 +529,52 @@ fn typecodes_t7_large() {
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Symbol("$0".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
     );
 }
 
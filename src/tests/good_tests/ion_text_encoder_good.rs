@@ -0,0 +1,81 @@
+use crate::tests::test_utils::{list_binary_testsuite_files, list_text_testsuite_files};
+use crate::{IonParser, IonTextEncoder, IonTextParser, IonValue};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+// The text spec only allows UTF-8, so the UTF-16 and UTF-32 files are not supported.
+const SKIPPED_FILES: &[&str] = &["utf16.ion", "utf32.ion"];
+
+// Values are compared through their compact text, as `nan` is never equal to itself.
+fn check_text_round_trip(path: &Path, values: Vec<IonValue>) -> Result<(), String> {
+    let mut encoder = IonTextEncoder::new();
+    values.into_iter().for_each(|value| encoder.add(value));
+    let compact = encoder.encode();
+
+    for text in [compact.clone(), pretty(&compact)] {
+        let values = IonTextParser::new(text.as_bytes())
+            .consume_all()
+            .map_err(|error| format!("{:?}: {:?} in {:?}", path, error, text))?;
+
+        let mut encoder = IonTextEncoder::new();
+        values.into_iter().for_each(|value| encoder.add(value));
+        let result = encoder.encode();
+
+        if result != compact {
+            return Err(format!("{:?}: {:?} != {:?}", path, result, compact));
+        }
+    }
+
+    Ok(())
+}
+
+fn pretty(compact: &str) -> String {
+    let values = IonTextParser::new(compact.as_bytes())
+        .consume_all()
+        .unwrap();
+    let mut encoder = IonTextEncoder::new_pretty();
+    values.into_iter().for_each(|value| encoder.add(value));
+    encoder.encode()
+}
+
+// Files the parsers cannot read are already reported by the parser tests.
+#[test]
+fn text_encoder_round_trip_binary_files() {
+    let mut failures = Vec::new();
+
+    for path in list_binary_testsuite_files("good") {
+        let file = BufReader::new(File::open(&path).unwrap());
+
+        if let Ok(values) = IonParser::new(file).consume_all() {
+            if let Err(failure) = check_text_round_trip(&path, values) {
+                failures.push(failure);
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+}
+
+#[test]
+fn text_encoder_round_trip_text_files() {
+    let mut failures = Vec::new();
+
+    for path in list_text_testsuite_files("good") {
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+
+        if SKIPPED_FILES.contains(&file_name.as_str()) {
+            continue;
+        }
+
+        let file = BufReader::new(File::open(&path).unwrap());
+
+        if let Ok(values) = IonTextParser::new(file).consume_all() {
+            if let Err(failure) = check_text_round_trip(&path, values) {
+                failures.push(failure);
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+}
@@ -0,0 +1,28 @@
+use crate::tests::test_utils::list_text_testsuite_files;
+use crate::IonTextParser;
+use std::fs::File;
+use std::io::BufReader;
+
+// The text spec only allows UTF-8, so the UTF-16 and UTF-32 files are not supported.
+const SKIPPED_FILES: &[&str] = &["utf16.ion", "utf32.ion"];
+
+#[test]
+fn text_good_files() {
+    let mut failures = Vec::new();
+
+    for path in list_text_testsuite_files("good") {
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+
+        if SKIPPED_FILES.contains(&file_name.as_str()) {
+            continue;
+        }
+
+        let file = BufReader::new(File::open(&path).unwrap());
+
+        if let Err(error) = IonTextParser::new(file).consume_all() {
+            failures.push(format!("{:?}: {:?}", path, error));
+        }
+    }
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+}
@@ -0,0 +1,89 @@
+use crate::tests::test_utils::{list_binary_testsuite_files, list_text_testsuite_files};
+use crate::{IonParser, IonTextParser, IonValue};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+const EMBEDDED_DOCUMENTS: &str = "embedded_documents";
+
+fn read_groups(path: &PathBuf) -> Vec<IonValue> {
+    let file = BufReader::new(File::open(path).unwrap());
+
+    let values = if path.extension().is_some_and(|extension| extension == "10n") {
+        IonParser::new(file)
+            .consume_all()
+            .map_err(|error| format!("{:?}", error))
+    } else {
+        IonTextParser::new(file)
+            .consume_all()
+            .map_err(|error| format!("{:?}", error))
+    };
+
+    values.unwrap_or_else(|error| panic!("Failed to parse {:?}: {}", path, error))
+}
+
+// Each top-level value is a list or sexp with the values to compare. The
+// ones annotated with `embedded_documents` have Ion text documents.
+fn group_elements(group: &IonValue) -> Vec<IonValue> {
+    match group {
+        IonValue::List(elements) | IonValue::SExpr(elements) => elements.clone(),
+        IonValue::Annotation(annotations, group)
+            if annotations
+                .iter()
+                .any(|annotation| annotation == EMBEDDED_DOCUMENTS) =>
+        {
+            group_elements(group)
+                .into_iter()
+                .map(|document| match document {
+                    IonValue::String(text) => {
+                        IonValue::List(IonTextParser::new(text.as_bytes()).consume_all().unwrap())
+                    }
+                    document => panic!("Embedded document is not a string: {:?}", document),
+                })
+                .collect()
+        }
+        IonValue::Annotation(_, group) => group_elements(group),
+        group => panic!("Group is not a list or sexp: {:?}", group),
+    }
+}
+
+fn check_groups(section: &str, are_equivalent: bool) {
+    let mut failures = Vec::new();
+
+    let files = list_text_testsuite_files(section)
+        .into_iter()
+        .chain(list_binary_testsuite_files(section));
+
+    for path in files {
+        for group in read_groups(&path) {
+            let elements = group_elements(&group);
+
+            for (index, value) in elements.iter().enumerate() {
+                for (other_index, other) in elements.iter().enumerate() {
+                    if index == other_index {
+                        continue;
+                    }
+
+                    let is_equivalent = value.ion_eq(other);
+                    let is_consistent = is_equivalent == (value.cmp(other).is_eq());
+
+                    if is_equivalent != are_equivalent || !is_consistent {
+                        failures.push(format!("{:?}: {:?} and {:?}", path, value, other));
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+}
+
+#[test]
+fn equivs_files_are_equivalent() {
+    check_groups("good/equivs", true);
+}
+
+#[test]
+fn non_equivs_files_are_not_equivalent() {
+    check_groups("good/non-equivs", false);
+}
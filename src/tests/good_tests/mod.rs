@@ -9,3 +9,6 @@ mod ion_parser_good_serde_value;
 mod ion_parser_good_struct;
 mod ion_parser_good_timestamp;
 mod ion_parser_good_typecodes;
+mod ion_text_encoder_good;
+mod ion_text_parser_good;
+mod ion_value_equivs;
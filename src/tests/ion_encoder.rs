@@ -1,7 +1,8 @@
 use crate::hashmap;
-use crate::{IonEncoder, IonParser, IonValue};
+use crate::{ImportLocation, IonEncoder, IonParser, IonValue, SymbolToken};
 use bigdecimal::BigDecimal;
 use chrono::DateTime;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 #[test]
@@ -10,12 +11,20 @@ fn encode_list() {
 
     let list = vec![
         IonValue::Integer(2523623),
-        IonValue::DateTime(DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00").unwrap()),
+        IonValue::DateTime(
+            DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00")
+                .unwrap()
+                .into(),
+        ),
         IonValue::String("Hola :D".to_string()),
         IonValue::String("Hello :D".to_string()),
         IonValue::Decimal(BigDecimal::from_str("329710294.574576239652439876523876").unwrap()),
         IonValue::String("Test 1".to_string()),
-        IonValue::DateTime(DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00").unwrap()),
+        IonValue::DateTime(
+            DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00")
+                .unwrap()
+                .into(),
+        ),
         IonValue::String(" ʳ ʴ ʵ ʶ ʷ ʸ ʹ ʺ ʻ ʼ ʽ".to_string()),
         IonValue::String("ഇഈഉഊഋഌഎഏഐഒഓഔകഖഗഘങച".to_string()),
     ];
@@ -26,13 +35,21 @@ fn encode_list() {
 
     let list = vec![
         IonValue::Integer(2523623),
-        IonValue::DateTime(DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00").unwrap()),
+        IonValue::DateTime(
+            DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00")
+                .unwrap()
+                .into(),
+        ),
         IonValue::String("Hola :D".to_string()),
         IonValue::String("Hello :D".to_string()),
         IonValue::Blob(bytes),
         IonValue::Decimal(BigDecimal::from_str("329710294.574576239652439876523876").unwrap()),
         IonValue::String("Test 1".to_string()),
-        IonValue::DateTime(DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00").unwrap()),
+        IonValue::DateTime(
+            DateTime::parse_from_rfc3339("1996-12-19T16:39:57-00:00")
+                .unwrap()
+                .into(),
+        ),
         IonValue::String(" ʳ ʴ ʵ ʶ ʷ ʸ ʹ ʺ ʻ ʼ ʽ".to_string()),
         IonValue::String("ഇഈഉഊഋഌഎഏഐഒഓഔകഖഗഘങച".to_string()),
     ];
@@ -81,3 +98,279 @@ fn encode_struct() {
 
     assert_eq!(ion_value, resulting_ion_value);
 }
+
+#[test]
+fn encode_into_appends_to_the_buffer() {
+    let mut value = IonValue::String("deep".to_string());
+
+    for index in 0..50 {
+        value = IonValue::OrderedStruct(vec![
+            (format!("field_{}", index).into(), value),
+            ("blob".into(), IonValue::Blob(vec![7; index * 100])),
+        ]);
+        value = IonValue::Annotation(
+            vec![format!("level_{}", index % 3).into()],
+            Box::new(IonValue::List(vec![value, IonValue::Integer(index as i64)])),
+        );
+    }
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let expected = encoder.encode();
+
+    let mut buffer = vec![0xFF];
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    encoder.encode_into(&mut buffer);
+
+    assert_eq!(buffer[0], 0xFF);
+    assert_eq!(buffer[1..], expected[..]);
+
+    let mut parser = IonParser::new(&buffer[1..]);
+    parser.with_ordered_structs();
+
+    let values = parser.consume_all().unwrap();
+
+    assert!(matches!(
+        &values[..],
+        [IonValue::Annotation(_, list)] if matches!(&**list, IonValue::List(items)
+            if matches!(items[0], IonValue::OrderedStruct(_)))
+    ));
+    assert_eq!(values, vec![value]);
+}
+
+fn canonical_bytes(value: IonValue) -> Vec<u8> {
+    let mut encoder = IonEncoder::new_canonical();
+    encoder.add(value);
+    encoder.encode()
+}
+
+#[test]
+fn canonical_encoding_is_deterministic() {
+    let names: Vec<String> = (0..100).map(|index| format!("field_{}", index)).collect();
+
+    let build = |names: &mut dyn Iterator<Item = &String>| -> IonValue {
+        let mut fields = HashMap::new();
+
+        for name in names {
+            let value = IonValue::Struct(hashmap!(
+                "imported".to_string() => IonValue::Symbol(SymbolToken::from_import_location(
+                    ImportLocation::new(format!("table_{}", name.len() % 3), 2),
+                )),
+                name.clone() => IonValue::Symbol(name.as_str().into())
+            ));
+
+            fields.insert(name.clone(), value);
+        }
+
+        IonValue::Struct(fields)
+    };
+
+    let value = build(&mut names.iter());
+    let bytes = canonical_bytes(value.clone());
+
+    assert_eq!(canonical_bytes(build(&mut names.iter().rev())), bytes);
+
+    let ordered = match &value {
+        IonValue::Struct(fields) => IonValue::OrderedStruct(
+            fields
+                .iter()
+                .map(|(name, value)| (name.into(), value.clone()))
+                .collect(),
+        ),
+        _ => unreachable!(),
+    };
+
+    assert_eq!(canonical_bytes(ordered), bytes);
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![value]
+    );
+}
+
+#[test]
+fn canonical_encoding_keeps_scalars() {
+    let decimal = |text: &str| IonValue::Decimal(BigDecimal::from_str(text).unwrap());
+
+    assert_ne!(
+        canonical_bytes(decimal("1.00")),
+        canonical_bytes(decimal("1"))
+    );
+    assert_ne!(
+        canonical_bytes(IonValue::Float(-0.0)),
+        canonical_bytes(IonValue::Float(0.0))
+    );
+
+    for value in [decimal("1.00"), IonValue::Float(-0.0)] {
+        let bytes = canonical_bytes(value.clone());
+
+        assert!(IonParser::new(&bytes[..]).consume_all().unwrap()[0].ion_eq(&value));
+    }
+
+    let bytes = canonical_bytes(IonValue::Float(1.5));
+
+    assert_eq!(bytes[bytes.len() - 5..], [0x44, 0x3F, 0xC0, 0x00, 0x00]);
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![IonValue::Float(1.5)]
+    );
+
+    let bytes = canonical_bytes(IonValue::Float(0.1));
+
+    assert_eq!(bytes[bytes.len() - 9], 0x48);
+}
+
+#[test]
+fn canonical_encoding_ignores_previous_values() {
+    let first = IonValue::List(vec![
+        IonValue::Symbol("first".into()),
+        IonValue::Annotation(
+            vec![SymbolToken::from_import_location(ImportLocation::new(
+                "cars", 1,
+            ))],
+            Box::new(IonValue::Integer(1)),
+        ),
+    ]);
+    let second = IonValue::List(vec![
+        IonValue::Symbol("second".into()),
+        IonValue::Symbol("first".into()),
+    ]);
+
+    let mut encoder = IonEncoder::new_canonical();
+    encoder.with_appended_symbol_tables();
+
+    encoder.add(first);
+    encoder.encode();
+
+    encoder.add(second.clone());
+
+    assert_eq!(encoder.encode(), canonical_bytes(second));
+}
+
+#[test]
+fn reset_starts_a_new_stream() {
+    // Canonical, as the symbol table fields would be in any order
+    let mut encoder = IonEncoder::new_canonical();
+    encoder
+        .with_shared_table("cars".to_string(), 1, &["brand".to_string()])
+        .unwrap();
+
+    encoder.add(IonValue::Symbol("first".into()));
+    encoder.encode();
+
+    encoder.reset();
+    encoder.add(IonValue::Symbol("brand".into()));
+    encoder.add(IonValue::Symbol("second".into()));
+    let bytes = encoder.encode();
+
+    let mut fresh_encoder = IonEncoder::new_canonical();
+    fresh_encoder
+        .with_shared_table("cars".to_string(), 1, &["brand".to_string()])
+        .unwrap();
+    fresh_encoder.add(IonValue::Symbol("brand".into()));
+    fresh_encoder.add(IonValue::Symbol("second".into()));
+
+    assert_eq!(bytes, fresh_encoder.encode());
+}
+
+#[test]
+fn appended_symbol_tables_can_be_concatenated() {
+    let imported = IonValue::Symbol(SymbolToken::from_import_location(ImportLocation::new(
+        "cars", 2,
+    )));
+
+    let chunks = vec![
+        vec![IonValue::Symbol("sedan".into())],
+        vec![
+            IonValue::Symbol("coupe".into()),
+            IonValue::Symbol("sedan".into()),
+        ],
+        vec![imported, IonValue::Symbol("coupe".into())],
+        vec![IonValue::Symbol("sedan".into())],
+    ];
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_appended_symbol_tables();
+
+    let mut encoded_chunks = vec![];
+
+    for chunk in &chunks {
+        for value in chunk {
+            encoder.add(value.clone());
+        }
+
+        encoded_chunks.push(encoder.encode());
+    }
+
+    let contains = |bytes: &[u8], text: &str| {
+        bytes
+            .windows(text.len())
+            .any(|window| window == text.as_bytes())
+    };
+
+    assert_eq!(encoded_chunks[0][..4], [0xE0, 0x01, 0x00, 0xEA]);
+    assert_ne!(encoded_chunks[1][..4], [0xE0, 0x01, 0x00, 0xEA]);
+
+    // Only the new symbol is declared, appended to the previous table
+    assert!(contains(&encoded_chunks[1], "coupe"));
+    assert!(!contains(&encoded_chunks[1], "sedan"));
+
+    // The new import needs a new table with all the symbols
+    assert!(contains(&encoded_chunks[2], "cars"));
+    assert!(contains(&encoded_chunks[2], "sedan"));
+
+    // Nothing to declare, sedan is $12 after the two imported symbols
+    assert_eq!(encoded_chunks[3], [0x71, 0x0C]);
+
+    let stream = encoded_chunks.concat();
+
+    assert_eq!(
+        IonParser::new(&stream[..]).consume_all().unwrap(),
+        chunks.concat()
+    );
+
+    encoder.reset();
+    encoder.add(IonValue::Symbol("sedan".into()));
+
+    let bytes = encoder.encode();
+
+    assert_eq!(bytes[..4], [0xE0, 0x01, 0x00, 0xEA]);
+    assert!(contains(&bytes, "sedan"));
+    assert!(!contains(&bytes, "coupe"));
+}
+
+#[test]
+fn compact_floats_keep_their_width() {
+    let bytes = [
+        0xE0, 0x01, 0x00, 0xEA, 0xBA, 0x44, 0x3F, 0xC0, 0x00, 0x00, 0x44, 0x80, 0x00, 0x00, 0x00,
+    ];
+
+    let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_compact_floats();
+
+    for value in &values {
+        encoder.add(value.clone());
+    }
+
+    let encoded = encoder.encode();
+
+    assert_eq!(encoded[encoded.len() - 11..], bytes[4..]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_compact_floats();
+    encoder.add(IonValue::Float(0.1));
+    encoder.add(IonValue::Float(0.0));
+
+    let encoded = encoder.encode();
+
+    assert_eq!(encoded[encoded.len() - 10], 0x48);
+    assert_eq!(encoded[encoded.len() - 1], 0x40);
+    assert_eq!(
+        IonParser::new(&encoded[..]).consume_all().unwrap(),
+        vec![IonValue::Float(0.1), IonValue::Float(0.0)]
+    );
+}
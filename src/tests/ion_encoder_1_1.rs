@@ -0,0 +1,297 @@
+use crate::hashmap;
+use crate::{
+    IonEncoder, IonParser, IonTimestamp, IonValue, IonVersion, NullIonValue, SymbolToken,
+    TimestampPrecision,
+};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use std::str::FromStr;
+
+const ION_1_1: [u8; 4] = [0xE0, 0x01, 0x01, 0xEA];
+
+fn encode(values: &[IonValue], version: IonVersion) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+    encoder.with_target_version(version);
+
+    for value in values {
+        encoder.add(value.clone());
+    }
+
+    encoder.encode()
+}
+
+fn assert_round_trip(values: Vec<IonValue>) -> Vec<u8> {
+    let bytes = encode(&values, IonVersion::Ion1_1);
+
+    assert_eq!(bytes[..4], ION_1_1);
+    assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), values);
+
+    bytes
+}
+
+// The struct used by the benchmarks
+fn car() -> IonValue {
+    IonValue::Struct(hashmap!(
+        "Model".to_string() => IonValue::String("CLK 350".to_string()),
+        "Type".to_string() => IonValue::String("Sedan".to_string()),
+        "Color".to_string() => IonValue::String("White".to_string()),
+        "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Year".to_string() => IonValue::Integer(2019)
+    ))
+}
+
+#[test]
+fn ion_1_1_scalars_round_trip() {
+    let values = vec![
+        IonValue::Integer(0),
+        IonValue::Integer(-2),
+        IonValue::Integer(127),
+        IonValue::Integer(128),
+        IonValue::Integer(i64::MIN),
+        IonValue::Integer(i64::MAX),
+        IonValue::BigInteger(BigInt::from(u64::MAX) + 1),
+        IonValue::BigInteger(BigInt::from(i64::MIN) * 1000),
+        IonValue::Bool(true),
+        IonValue::Bool(false),
+        IonValue::Float(0.0),
+        IonValue::Float(-1.5),
+        IonValue::Float(0.1),
+        IonValue::Float(f64::INFINITY),
+        IonValue::Decimal(BigDecimal::from_str("0").unwrap()),
+        IonValue::Decimal(BigDecimal::from_str("0.00").unwrap()),
+        IonValue::Decimal(BigDecimal::from_str("-1.5").unwrap()),
+        IonValue::Decimal(BigDecimal::from_str("15e10").unwrap()),
+        IonValue::Decimal(BigDecimal::from_str("329710294.574576239652439876523876").unwrap()),
+        IonValue::String("".to_string()),
+        IonValue::String("ഇഈഉഊഋഌഎഏഐഒഓഔകഖഗഘങച".to_string()),
+        IonValue::Symbol("".into()),
+        IonValue::Symbol("a symbol longer than fifteen bytes".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
+        IonValue::Blob(vec![]),
+        IonValue::Blob(vec![1; 300]),
+        IonValue::Clob(b"clob".to_vec()),
+        IonValue::Null(NullIonValue::Null),
+        IonValue::Null(NullIonValue::Bool),
+        IonValue::Null(NullIonValue::Integer),
+        IonValue::Null(NullIonValue::Float),
+        IonValue::Null(NullIonValue::Decimal),
+        IonValue::Null(NullIonValue::DateTime),
+        IonValue::Null(NullIonValue::String),
+        IonValue::Null(NullIonValue::Symbol),
+        IonValue::Null(NullIonValue::Blob),
+        IonValue::Null(NullIonValue::Clob),
+        IonValue::Null(NullIonValue::List),
+        IonValue::Null(NullIonValue::SExpr),
+        IonValue::Null(NullIonValue::Struct),
+    ];
+
+    let bytes = assert_round_trip(values);
+    let parsed = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert!(parsed[..6]
+        .iter()
+        .all(|value| matches!(value, IonValue::Integer(_))));
+    assert!(matches!(
+        parsed[6..8],
+        [IonValue::BigInteger(_), IonValue::BigInteger(_)]
+    ));
+}
+
+#[test]
+fn ion_1_1_timestamps_round_trip() {
+    let second = |offset| IonTimestamp::from_ymd_hms(2024, 3, 15, 10, 30, 45, offset).unwrap();
+    let minute = |offset| second(offset).with_precision(TimestampPrecision::Minute);
+    let fraction = |fraction| {
+        second(Some(-300))
+            .with_fraction(BigDecimal::from_str(fraction).unwrap())
+            .unwrap()
+    };
+
+    let timestamps = vec![
+        // Short forms
+        IonTimestamp::from_year(2024).unwrap(),
+        IonTimestamp::from_year_month(2024, 3).unwrap(),
+        IonTimestamp::from_ymd(2024, 3, 15).unwrap(),
+        minute(None),
+        minute(Some(0)),
+        minute(Some(345)),
+        second(Some(0)),
+        second(Some(-840)),
+        fraction("0.500"),
+        fraction("0.000001"),
+        fraction("0.999999999"),
+        // Long forms
+        IonTimestamp::from_year(1969).unwrap(),
+        IonTimestamp::from_ymd(2100, 12, 31).unwrap(),
+        minute(Some(347)),
+        second(None)
+            .with_fraction(BigDecimal::from_str("0.5").unwrap())
+            .unwrap(),
+        IonTimestamp::from_ymd_hms(1, 1, 1, 0, 0, 0, Some(-1439)).unwrap(),
+        fraction("0.1234"),
+        fraction("0.000000000001"),
+    ];
+
+    let values: Vec<IonValue> = timestamps.into_iter().map(IonValue::DateTime).collect();
+
+    let bytes = assert_round_trip(values);
+
+    // 2024T and 2024-03T
+    assert_eq!(bytes[4..9], [0x80, 0x36, 0x81, 0xB6, 0x01]);
+}
+
+#[test]
+fn ion_1_1_containers_round_trip() {
+    let long_list = IonValue::List((0..100).map(IonValue::Integer).collect());
+
+    // Its length would take two bytes, and the delimiters take one each
+    let bytes = encode(std::slice::from_ref(&long_list), IonVersion::Ion1_1);
+
+    assert_eq!(bytes[4], 0xF1);
+    assert_eq!(bytes.last(), Some(&0xF0));
+
+    let values = vec![
+        IonValue::List(vec![]),
+        IonValue::SExpr(vec![IonValue::Symbol("+".into()), IonValue::Integer(1)]),
+        long_list.clone(),
+        IonValue::Struct(Default::default()),
+        IonValue::Struct(hashmap!(
+            "".to_string() => IonValue::Integer(1),
+            "name".to_string() => long_list.clone()
+        )),
+        IonValue::OrderedStruct(
+            (0..50)
+                .map(|index| (format!("field_{}", index).into(), IonValue::Integer(index)))
+                .collect(),
+        ),
+        IonValue::Annotation(vec!["a".into()], Box::new(IonValue::Integer(1))),
+        IonValue::Annotation(
+            vec!["name".into(), SymbolToken::unknown()],
+            Box::new(IonValue::Integer(1)),
+        ),
+        IonValue::Annotation(
+            vec!["name".into(), "symbols".into(), "imports".into()],
+            Box::new(long_list),
+        ),
+    ];
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_target_version(IonVersion::Ion1_1);
+
+    for value in &values {
+        encoder.add(value.clone());
+    }
+
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.with_ordered_structs();
+
+    let parsed = parser.consume_all().unwrap();
+
+    assert!(matches!(parsed[5], IonValue::OrderedStruct(_)));
+    assert_eq!(parsed[5], values[5]);
+
+    let unordered: Vec<IonValue> = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert!(matches!(unordered[5], IonValue::Struct(_)));
+
+    assert_eq!(unordered[..5], values[..5]);
+    assert_eq!(unordered[6..], values[6..]);
+}
+
+#[test]
+fn ion_1_1_writes_symbols_inline_unless_the_table_is_cheaper() {
+    let once = vec![IonValue::Symbol("sedan".into())];
+
+    // Without a symbol table
+    assert_eq!(
+        assert_round_trip(once),
+        [0xE0, 0x01, 0x01, 0xEA, 0xA5, b's', b'e', b'd', b'a', b'n']
+    );
+
+    // Declared once and written as $10 twice
+    let twice = vec![
+        IonValue::Symbol("sedan".into()),
+        IonValue::Symbol("sedan".into()),
+    ];
+
+    let bytes = assert_round_trip(twice);
+
+    assert_eq!(bytes[bytes.len() - 4..], [0xE1, 0x0A, 0xE1, 0x0A]);
+
+    // Too short to be worth it
+    let short = vec![IonValue::Symbol("a".into()), IonValue::Symbol("a".into())];
+
+    assert_eq!(
+        assert_round_trip(short),
+        [0xE0, 0x01, 0x01, 0xEA, 0xA1, b'a', 0xA1, b'a']
+    );
+}
+
+#[test]
+fn ion_1_1_continues_streams_and_shared_tables() {
+    let mut encoder = IonEncoder::new();
+    encoder.with_target_version(IonVersion::Ion1_1);
+    encoder.with_appended_symbol_tables();
+    encoder
+        .with_shared_table("cars".into(), 1, &["sedan".into()])
+        .unwrap();
+
+    let values = vec![
+        IonValue::Symbol("sedan".into()),
+        IonValue::Symbol("convertible".into()),
+        IonValue::Symbol("convertible".into()),
+    ];
+
+    let mut stream = vec![];
+
+    for value in &values {
+        encoder.add(value.clone());
+        encoder.add(value.clone());
+        encoder.encode_into(&mut stream);
+    }
+
+    let mut parser = IonParser::new(&stream[..]);
+    parser
+        .with_shared_table("cars".into(), 1, &["sedan".into()])
+        .unwrap();
+
+    let expected: Vec<IonValue> = values
+        .iter()
+        .flat_map(|value| vec![value.clone(), value.clone()])
+        .collect();
+
+    assert_eq!(parser.consume_all().unwrap(), expected);
+}
+
+#[test]
+fn ion_1_1_is_smaller_on_the_benchmark_values() {
+    let distinct_fields = IonValue::OrderedStruct(
+        (0..1000)
+            .map(|index| (format!("field_{}", index).into(), IonValue::Integer(index)))
+            .collect(),
+    );
+
+    for value in [car(), distinct_fields] {
+        let ion_1_0 = encode(std::slice::from_ref(&value), IonVersion::Ion1_0);
+        let ion_1_1 = encode(std::slice::from_ref(&value), IonVersion::Ion1_1);
+
+        let mut parser = IonParser::new(&ion_1_1[..]);
+
+        if let IonValue::OrderedStruct(_) = value {
+            parser.with_ordered_structs();
+        }
+
+        let parsed = parser.consume_all().unwrap();
+
+        assert!(matches!(
+            (&parsed[0], &value),
+            (IonValue::Struct(_), IonValue::Struct(_))
+                | (IonValue::OrderedStruct(_), IonValue::OrderedStruct(_))
+        ));
+        assert_eq!(parsed, vec![value]);
+        assert!(ion_1_1.len() < ion_1_0.len());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{IonHash, IonValue};
+use crate::{IonHash, IonParser, IonValue};
 use sha2::Sha256;
 
 // FLOAT 64
@@ -140,3 +140,22 @@ fn ion_hash_float32_min() {
     assert_eq!(b"\xed\x88\xa4\x35\x64\xcd\xed\xea\xcf\xa6\x55\xeb\xaf\x66\x1c\xcf\xfb\x8b\x03\xb2\x56\x26\x97\x30\xe7\xa6\x95\xe3\x22\xf9\xe7\x49", &hash[..]);
 }
 */
+
+// FLOAT 32
+
+#[test]
+fn ion_hash_float32_same_as_float64() {
+    let float32 = [0xE0, 0x01, 0x00, 0xEA, 0x44, 0x3F, 0xC0, 0x00, 0x00];
+    let float64 = [
+        0xE0, 0x01, 0x00, 0xEA, 0x48, 0x3F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let float32 = IonParser::new(&float32[..]).consume_value().unwrap().0;
+    let float64 = IonParser::new(&float64[..]).consume_value().unwrap().0;
+
+    assert_eq!(float32, IonValue::Float(1.5));
+    assert_eq!(
+        IonHash::digest::<Sha256>(&float32),
+        IonHash::digest::<Sha256>(&float64)
+    );
+}
@@ -5,7 +5,9 @@ use std::collections::HashMap;
 #[test]
 fn ion_hash_datetime_1() {
     let value = IonValue::DateTime(
-        chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.1-08:00").unwrap(),
+        chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.1-08:00")
+            .unwrap()
+            .into(),
     );
 
     let hash = IonHash::digest::<Sha256>(&value);
@@ -18,7 +20,9 @@ fn ion_hash_datetime_1() {
 #[test]
 fn ion_hash_datetime_2() {
     let value = IonValue::DateTime(
-        chrono::DateTime::parse_from_rfc3339("2234-11-01T23:59:59.999+03:45").unwrap(),
+        chrono::DateTime::parse_from_rfc3339("2234-11-01T23:59:59.999+03:45")
+            .unwrap()
+            .into(),
     );
 
     let hash = IonHash::digest::<Sha256>(&value);
@@ -31,7 +35,9 @@ fn ion_hash_datetime_2() {
 #[test]
 fn ion_hash_datetime_3() {
     let value = IonValue::DateTime(
-        chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap(),
+        chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00")
+            .unwrap()
+            .into(),
     );
 
     let hash = IonHash::digest::<Sha256>(&value);
@@ -52,7 +58,9 @@ fn ion_hash_datetime_4() {
         // many following zeros has the decimal. This implementation
         // removes them all and assumes the minimum precision for the
         // number to be represented.
-        chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.100-08:00").unwrap(),
+        chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.100-08:00")
+            .unwrap()
+            .into(),
     );
 
     let hash = IonHash::digest::<Sha256>(&value);
@@ -68,7 +76,9 @@ fn ion_hash_datetimes_in_struct() {
     map.insert(
         "2011-01-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
 
@@ -87,13 +97,17 @@ fn ion_hash_datetimes_in_struct_2() {
     map.insert(
         "2011-01-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
     map.insert(
         "2011-02-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
 
@@ -112,19 +126,25 @@ fn ion_hash_datetimes_in_struct_3() {
     map.insert(
         "2011-01-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
     map.insert(
         "2011-02-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
     map.insert(
         "2011-02-20T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-20T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-02-20T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
 
@@ -143,25 +163,33 @@ fn ion_hash_datetimes_in_struct_4() {
     map.insert(
         "2011-01-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
     map.insert(
         "2011-02-01T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
     map.insert(
         "2011-02-20T00:00:00+00:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-20T00:00:00+00:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-02-20T00:00:00+00:00")
+                .unwrap()
+                .into(),
         ),
     );
     map.insert(
         "2011-02-20T11:30:59.100-08:00".into(),
         IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.100-08:00").unwrap(),
+            chrono::DateTime::parse_from_rfc3339("2011-02-20T11:30:59.100-08:00")
+                .unwrap()
+                .into(),
         ),
     );
     let value = IonValue::Struct(map);
@@ -0,0 +1,219 @@
+use crate::hashmap;
+use crate::{ion, IonTimestamp, IonValue, NullIonValue, SymbolToken};
+use bigdecimal::BigDecimal;
+use std::str::FromStr;
+
+fn symbol(text: &str) -> IonValue {
+    IonValue::Symbol(text.into())
+}
+
+fn annotated(annotations: &[&str], value: IonValue) -> IonValue {
+    let annotations = annotations
+        .iter()
+        .map(|annotation| (*annotation).into())
+        .collect();
+
+    IonValue::Annotation(annotations, Box::new(value))
+}
+
+#[test]
+fn ion_macro_builds_scalars() {
+    assert_eq!(ion!(null), IonValue::Null(NullIonValue::Null));
+    assert_eq!(ion!(null.null), IonValue::Null(NullIonValue::Null));
+    assert_eq!(ion!(null.bool), IonValue::Null(NullIonValue::Bool));
+    assert_eq!(ion!(null.int), IonValue::Null(NullIonValue::Integer));
+    assert_eq!(ion!(null.float), IonValue::Null(NullIonValue::Float));
+    assert_eq!(ion!(null.decimal), IonValue::Null(NullIonValue::Decimal));
+    assert_eq!(ion!(null.timestamp), IonValue::Null(NullIonValue::DateTime));
+    assert_eq!(ion!(null.string), IonValue::Null(NullIonValue::String));
+    assert_eq!(ion!(null.symbol), IonValue::Null(NullIonValue::Symbol));
+    assert_eq!(ion!(null.blob), IonValue::Null(NullIonValue::Blob));
+    assert_eq!(ion!(null.clob), IonValue::Null(NullIonValue::Clob));
+    assert_eq!(ion!(null.list), IonValue::Null(NullIonValue::List));
+    assert_eq!(ion!(null.sexp), IonValue::Null(NullIonValue::SExpr));
+    assert_eq!(ion!(null.struct), IonValue::Null(NullIonValue::Struct));
+
+    assert_eq!(ion!(true), IonValue::Bool(true));
+    assert_eq!(ion!(false), IonValue::Bool(false));
+    assert_eq!(ion!(42), IonValue::Integer(42));
+    assert_eq!(ion!(-42), IonValue::Integer(-42));
+    assert_eq!(ion!(1.5), IonValue::Float(1.5));
+    assert_eq!(ion!(+inf), IonValue::Float(f64::INFINITY));
+    assert_eq!(ion!(-inf), IonValue::Float(f64::NEG_INFINITY));
+    assert!(matches!(ion!(nan), IonValue::Float(value) if value.is_nan()));
+
+    assert_eq!(
+        ion!(decimal("1.50")),
+        IonValue::Decimal(BigDecimal::from_str("1.50").unwrap())
+    );
+    assert_eq!(
+        ion!(timestamp("2024-03-15T")),
+        IonValue::DateTime(IonTimestamp::from_ymd(2024, 3, 15).unwrap())
+    );
+    assert_eq!(
+        ion!(timestamp("2024-03-15T10:30:45Z")),
+        IonValue::DateTime(IonTimestamp::from_ymd_hms(2024, 3, 15, 10, 30, 45, Some(0)).unwrap())
+    );
+
+    assert_eq!(ion!("sedan"), IonValue::String("sedan".to_string()));
+    assert_eq!(ion!(sedan), symbol("sedan"));
+    assert_eq!(ion!(symbol("two words")), symbol("two words"));
+    assert_eq!(ion!(blob(b"\x01\x02")), IonValue::Blob(vec![1, 2]));
+    assert_eq!(ion!(clob("text")), IonValue::Clob(b"text".to_vec()));
+}
+
+#[test]
+#[should_panic(expected = "Invalid decimal")]
+fn ion_macro_panics_on_invalid_decimals() {
+    ion!(decimal("1.5.0"));
+}
+
+#[test]
+#[should_panic(expected = "Invalid timestamp")]
+fn ion_macro_panics_on_invalid_timestamps() {
+    ion!(timestamp("2024-13-01T"));
+}
+
+#[test]
+fn ion_macro_builds_containers() {
+    assert_eq!(ion!([]), IonValue::List(vec![]));
+    assert_eq!(ion!(()), IonValue::SExpr(vec![]));
+    assert_eq!(ion!({}), IonValue::Struct(Default::default()));
+
+    assert_eq!(
+        ion!([1, -2, "three", four, [null.int], {},]),
+        IonValue::List(vec![
+            IonValue::Integer(1),
+            IonValue::Integer(-2),
+            IonValue::String("three".to_string()),
+            symbol("four"),
+            IonValue::List(vec![IonValue::Null(NullIonValue::Integer)]),
+            IonValue::Struct(Default::default()),
+        ])
+    );
+
+    assert_eq!(
+        ion!((+ x -1 +2 - a <= null.int -inf "b" [c] (d) { e: 1 } decimal("1") ann::f)),
+        IonValue::SExpr(vec![
+            symbol("+"),
+            symbol("x"),
+            IonValue::Integer(-1),
+            IonValue::Integer(2),
+            symbol("-"),
+            symbol("a"),
+            symbol("<="),
+            IonValue::Null(NullIonValue::Integer),
+            IonValue::Float(f64::NEG_INFINITY),
+            IonValue::String("b".to_string()),
+            IonValue::List(vec![symbol("c")]),
+            IonValue::SExpr(vec![symbol("d")]),
+            IonValue::Struct(hashmap!("e".to_string() => IonValue::Integer(1))),
+            IonValue::Decimal(BigDecimal::from(1)),
+            annotated(&["ann"], symbol("f")),
+        ])
+    );
+
+    let name = "computed";
+
+    assert_eq!(
+        ion!({
+            type: sedan,
+            "two words": [1, 2],
+            { name }: { year: 2019 },
+            repeated: 1,
+            repeated: 2
+        }),
+        IonValue::Struct(hashmap!(
+            "type".to_string() => symbol("sedan"),
+            "two words".to_string() => IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+            "computed".to_string() => IonValue::Struct(hashmap!(
+                "year".to_string() => IonValue::Integer(2019)
+            )),
+            "repeated".to_string() => IonValue::Integer(2)
+        ))
+    );
+}
+
+#[test]
+fn ion_macro_builds_long_containers() {
+    assert_eq!(
+        ion!([
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+            24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45,
+            46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67,
+            68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89,
+            90, 91, 92, 93, 94, 95, 96, 97, 98, 99
+        ]),
+        IonValue::List((0..100).map(IonValue::Integer).collect())
+    );
+
+    assert_eq!(
+        ion!({
+            f0: 0, f1: 1, f2: 2, f3: 3, f4: 4, f5: 5, f6: 6, f7: 7, f8: 8, f9: 9,
+            f10: 10, f11: 11, f12: 12, f13: 13, f14: 14, f15: 15, f16: 16, f17: 17, f18: 18, f19: 19,
+            f20: 20, f21: 21, f22: 22, f23: 23, f24: 24, f25: 25, f26: 26, f27: 27, f28: 28, f29: 29,
+            f30: 30, f31: 31, f32: 32, f33: 33, f34: 34, f35: 35, f36: 36, f37: 37, f38: 38, f39: 39,
+            f40: 40, f41: 41, f42: 42, f43: 43, f44: 44, f45: 45, f46: 46, f47: 47, f48: 48, f49: 49,
+            f50: 50, f51: 51, f52: 52, f53: 53, f54: 54, f55: 55, f56: 56, f57: 57, f58: 58, f59: 59,
+            f60: 60, f61: 61, f62: 62, f63: 63, f64: 64, f65: 65, f66: 66, f67: 67, f68: 68, f69: 69,
+            f70: 70, f71: 71, f72: 72, f73: 73, f74: 74, f75: 75, f76: 76, f77: 77, f78: 78, f79: 79,
+            f80: 80, f81: 81, f82: 82, f83: 83, f84: 84, f85: 85, f86: 86, f87: 87, f88: 88, f89: 89,
+            f90: 90, f91: 91, f92: 92, f93: 93, f94: 94, f95: 95, f96: 96, f97: 97, f98: 98, f99: 99
+        }),
+        IonValue::Struct(
+            (0..100)
+                .map(|i| (format!("f{i}"), IonValue::Integer(i)))
+                .collect()
+        )
+    );
+}
+
+#[test]
+fn ion_macro_builds_annotations() {
+    assert_eq!(ion!(a::1), annotated(&["a"], IonValue::Integer(1)));
+    assert_eq!(
+        ion!(a::"two words"::b::[c::d]),
+        annotated(
+            &["a", "two words", "b"],
+            IonValue::List(vec![annotated(&["c"], symbol("d"))])
+        )
+    );
+    assert_eq!(
+        ion!({ price: usd::decimal("9.99") }),
+        IonValue::Struct(hashmap!(
+            "price".to_string() => annotated(
+                &["usd"],
+                IonValue::Decimal(BigDecimal::from_str("9.99").unwrap())
+            )
+        ))
+    );
+    assert_eq!(
+        ion!((a::b::null.int c)),
+        IonValue::SExpr(vec![
+            annotated(&["a", "b"], IonValue::Null(NullIonValue::Integer)),
+            symbol("c"),
+        ])
+    );
+}
+
+#[test]
+fn ion_macro_interpolates_rust_values() {
+    let year = 2019;
+    let model = String::from("CLK 350");
+    let owners = ion!(["Alice", "Bob"]);
+
+    assert_eq!(
+        ion!([{ year }, { model.clone() }, { owners.clone() }, tag::{ year + 1 }]),
+        IonValue::List(vec![
+            IonValue::Integer(2019),
+            IonValue::String("CLK 350".to_string()),
+            owners,
+            annotated(&["tag"], IonValue::Integer(2020)),
+        ])
+    );
+
+    assert_eq!(
+        ion!({ IonValue::Symbol(SymbolToken::unknown()) }),
+        IonValue::Symbol(SymbolToken::unknown())
+    );
+}
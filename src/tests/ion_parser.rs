@@ -1,5 +1,11 @@
-use crate::{ion_parser::IonParser, ion_parser_types::IonValue};
+use crate::{
+    ion_parser::IonParser, ion_parser_types::IonValue, IonEncoder, IonParserError, IonValuePath,
+    ParsingError, PathComponent,
+};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::rc::Rc;
 
 #[test]
 fn decode_full_ion() {
@@ -24,3 +30,169 @@ fn decode_full_ion() {
         IonValue::Struct(expected)
     );
 }
+
+// Reader that counts how many bytes are actually read from it.
+struct CountingReader {
+    reader: Cursor<Vec<u8>>,
+    read_bytes: Rc<Cell<usize>>,
+}
+
+impl Read for CountingReader {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let read_bytes = self.reader.read(buffer)?;
+        self.read_bytes.set(self.read_bytes.get() + read_bytes);
+        Ok(read_bytes)
+    }
+}
+
+impl Seek for CountingReader {
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+        self.reader.seek(position)
+    }
+}
+
+fn encode_skip_test_values() -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+
+    encoder.add(IonValue::Annotation(
+        vec!["skipped".into()],
+        Box::new(IonValue::Blob(vec![7; 10_000])),
+    ));
+    encoder.add(IonValue::List(vec![
+        IonValue::String("big list".to_string());
+        1000
+    ]));
+    encoder.add(IonValue::Symbol("kept".into()));
+
+    encoder.encode()
+}
+
+#[test]
+fn skip_value_reads_past_values() {
+    let bytes = encode_skip_test_values();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    parser.skip_value().unwrap();
+    parser.skip_value().unwrap();
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Symbol("kept".into())
+    );
+    assert_eq!(
+        parser.skip_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::NoDataToRead)
+    );
+}
+
+#[test]
+fn skip_value_seeks_when_possible() {
+    let bytes = encode_skip_test_values();
+
+    let read_bytes = Rc::new(Cell::new(0));
+
+    let mut parser = IonParser::new_seekable(CountingReader {
+        reader: Cursor::new(bytes.clone()),
+        read_bytes: read_bytes.clone(),
+    });
+
+    let first_skipped = parser.skip_value().unwrap();
+    let second_skipped = parser.skip_value().unwrap();
+
+    assert!(first_skipped > 10_000);
+    assert!(second_skipped > 1000);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Symbol("kept".into())
+    );
+    assert_eq!(parser.position(), bytes.len());
+
+    // Only the headers and the symbol table are read, not the skipped bodies.
+    assert!(read_bytes.get() < 200, "Read {} bytes", read_bytes.get());
+}
+
+#[test]
+fn skip_value_returns_the_same_length_as_consume_value() {
+    let bytes = encode_skip_test_values();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    let mut skipped = vec![];
+
+    while let Ok(consumed) = parser.skip_value() {
+        skipped.push(consumed);
+    }
+
+    let mut parser = IonParser::new(&bytes[..]);
+    let mut consumed = vec![];
+    let mut position = 0;
+
+    while parser.consume_value().is_ok() {
+        consumed.push(parser.position() - position);
+        position = parser.position();
+    }
+
+    assert_eq!(skipped, consumed);
+}
+
+#[test]
+fn errors_carry_their_location() {
+    let order = IonValue::Struct(
+        vec![("price".to_string(), IonValue::String("x\u{e9}".into()))]
+            .into_iter()
+            .collect(),
+    );
+    let orders = IonValue::List(vec![IonValue::String("ok".into()), order]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::Integer(2));
+    encoder.add(IonValue::Integer(3));
+    encoder.add(IonValue::Struct(
+        vec![("orders".to_string(), orders)].into_iter().collect(),
+    ));
+    let mut bytes = encoder.encode();
+
+    let text_end = bytes.len();
+    bytes[text_end - 2] = 0xFF;
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    for _ in 0..3 {
+        parser.consume_value().unwrap();
+    }
+
+    let error = parser.consume_value_located().unwrap_err();
+
+    assert_eq!(error, IonParserError::NonUtf8String);
+    assert_eq!(error.offset(), text_end);
+    assert_eq!(error.value_index(), 3);
+    assert_eq!(error.path().to_string(), "[3].orders[1].price");
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Ion String is not valid UTF-8 at byte {} ([3].orders[1].price)",
+            text_end
+        )
+    );
+
+    let error = IonParser::new(&bytes[..])
+        .consume_all_located()
+        .unwrap_err();
+
+    assert_eq!(error.path().to_string(), "[3].orders[1].price");
+}
+
+#[test]
+fn value_paths_quote_field_names() {
+    let path = IonValuePath::new(vec![
+        PathComponent::Index(0),
+        PathComponent::Field("$id_2".to_string()),
+        PathComponent::Field("first name".to_string()),
+        PathComponent::Field("it's".to_string()),
+        PathComponent::Index(4),
+    ]);
+
+    assert_eq!(path.to_string(), "[0].$id_2.'first name'.'it\\'s'[4]");
+}
@@ -0,0 +1,199 @@
+use crate::{IonParser, IonParserError, IonTimestamp, IonValue, NullIonValue, TimestampPrecision};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use std::str::FromStr;
+
+const ION_1_1: [u8; 4] = [0xE0, 0x01, 0x01, 0xEA];
+
+fn parse(bytes: &[u8]) -> Result<Vec<IonValue>, IonParserError> {
+    let mut stream = ION_1_1.to_vec();
+    stream.extend_from_slice(bytes);
+
+    IonParser::new(&stream[..]).consume_all()
+}
+
+fn int_struct(field: &str, value: i64) -> IonValue {
+    IonValue::Struct(
+        vec![(field.to_string(), IonValue::Integer(value))]
+            .into_iter()
+            .collect(),
+    )
+}
+
+#[test]
+fn ion_1_1_scalars_are_decoded() {
+    let mut bytes = vec![
+        0x60, // 0
+        0x61, 0x05, // 5
+        0x62, 0xFE, 0xFF, // -2
+        0xF6, 0x13, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, // 2^64
+        0x6E, // true
+        0x6F, // false
+        0x6A, // 0e0
+        0x6B, 0x00, 0x3C, // 1e0 as half float
+        0x6C, 0x00, 0x00, 0xC0, 0x3F, // 1.5e0 as float
+        0x72, 0xFF, 0x0F, // 1.5
+        0x93, b'a', b'b', b'c', // "abc"
+        0xF9, 0x01, // ""
+        0xA2, b'h', b'i', // hi
+        0xEE, 0x04, // name
+        0xFE, 0x07, 1, 2, 3, // {{ AQID }}
+        0xFF, 0x05, b'h', b'i', // {{ "hi" }}
+        0xEA, // null
+        0xEB, 0x05, // null.string
+        0x6D,
+    ];
+    bytes.extend_from_slice(&2.5f64.to_le_bytes());
+
+    let values = parse(&bytes).unwrap();
+
+    assert!(matches!(
+        values[..],
+        [
+            IonValue::Integer(_),
+            IonValue::Integer(_),
+            IonValue::Integer(_),
+            IonValue::BigInteger(_),
+            ..
+        ]
+    ));
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Integer(0),
+            IonValue::Integer(5),
+            IonValue::Integer(-2),
+            IonValue::BigInteger(BigInt::from(u64::MAX) + 1),
+            IonValue::Bool(true),
+            IonValue::Bool(false),
+            IonValue::Float(0.0),
+            IonValue::Float(1.0),
+            IonValue::Float(1.5),
+            IonValue::Decimal(BigDecimal::from_str("1.5").unwrap()),
+            IonValue::String("abc".into()),
+            IonValue::String("".into()),
+            IonValue::Symbol("hi".into()),
+            IonValue::Symbol("name".into()),
+            IonValue::Blob(vec![1, 2, 3]),
+            IonValue::Clob(b"hi".to_vec()),
+            IonValue::Null(NullIonValue::Null),
+            IonValue::Null(NullIonValue::String),
+            IonValue::Float(2.5),
+        ]
+    );
+}
+
+#[test]
+fn ion_1_1_timestamps_are_decoded() {
+    let bytes = [
+        0x80, 0x36, // 2024T
+        0xF8, 0x05, 0xE8, 0x07, // 2024T in the long form
+        0x82, 0xB6, 0x79, // 2024-03-15
+        0x83, 0xB6, 0x79, 0xCA, 0x0B, // 2024-03-15T10:30Z
+        0x88, 0xB6, 0x79, 0xC9, 0xE3, 0x01, // 2024-03-15T10:30+01:00
+        0xF8, 0x13, 0xE8, 0xC7, 0x3C, 0xE5, 0x81, 0x56, 0x0B, 0x03, 0x05, // ...T10:30:45.5Z
+    ];
+
+    let minute = |offset| {
+        IonTimestamp::from_ymd_hms(2024, 3, 15, 10, 30, 0, Some(offset))
+            .unwrap()
+            .with_precision(TimestampPrecision::Minute)
+    };
+
+    let second = IonTimestamp::from_ymd_hms(2024, 3, 15, 10, 30, 45, Some(0))
+        .unwrap()
+        .with_fraction(BigDecimal::from_str("0.5").unwrap())
+        .unwrap();
+
+    assert_eq!(
+        parse(&bytes).unwrap(),
+        vec![
+            IonValue::DateTime(IonTimestamp::from_year(2024).unwrap()),
+            IonValue::DateTime(IonTimestamp::from_year(2024).unwrap()),
+            IonValue::DateTime(IonTimestamp::from_ymd(2024, 3, 15).unwrap()),
+            IonValue::DateTime(minute(0)),
+            IonValue::DateTime(minute(60)),
+            IonValue::DateTime(second),
+        ]
+    );
+}
+
+#[test]
+fn ion_1_1_containers_and_annotations_are_decoded() {
+    let bytes = [
+        0xB4, 0x61, 0x01, 0x91, b'a', // [1, "a"]
+        0xF2, 0x61, 0x02, 0xA1, b'+', 0xF0, // (2 +)
+        0xD6, 0x01, 0xFD, b'i', b'd', 0x61, 0x07, // {id: 7}
+        0xF3, 0xFD, b'i', b'd', 0x61, 0x07, 0x01, 0xF0, // {id: 7}
+        0xD2, 0x09, 0xEC, // {name: <nop>}
+        0xE7, 0xFF, b'a', 0x61, 0x01, // a::1
+        0xE4, 0x09, 0x60, // name::0
+    ];
+
+    assert_eq!(
+        parse(&bytes).unwrap(),
+        vec![
+            IonValue::List(vec![IonValue::Integer(1), IonValue::String("a".into())]),
+            IonValue::SExpr(vec![IonValue::Integer(2), IonValue::Symbol("+".into())]),
+            int_struct("id", 7),
+            int_struct("id", 7),
+            IonValue::Struct(Default::default()),
+            IonValue::Annotation(vec!["a".into()], Box::new(IonValue::Integer(1))),
+            IonValue::Annotation(vec!["name".into()], Box::new(IonValue::Integer(0))),
+        ]
+    );
+}
+
+#[test]
+fn ion_1_1_symbol_tables_are_loaded() {
+    let bytes = [
+        // $ion_symbol_table::{symbols: ["sym"]}
+        0xE4, 0x07, 0xD6, 0x0F, 0xB4, 0x93, b's', b'y', b'm', //
+        0xE1, 0x0A, // $10
+        // An Ion 1.0 stream after the Ion 1.1 one
+        0xE0, 0x01, 0x00, 0xEA, 0x21, 0x02,
+    ];
+
+    assert_eq!(
+        parse(&bytes).unwrap(),
+        vec![IonValue::Symbol("sym".into()), IonValue::Integer(2)]
+    );
+}
+
+#[test]
+fn ion_1_1_unsupported_features_return_errors() {
+    assert_eq!(parse(&[0x05]), Err(IonParserError::UnsupportedMacro(5)));
+    assert_eq!(
+        parse(&[0xF4, 0x03]),
+        Err(IonParserError::UnsupportedMacro(1))
+    );
+    assert_eq!(
+        parse(&[0xEF, 0x02]),
+        Err(IonParserError::UnsupportedSystemMacro(2))
+    );
+    assert_eq!(parse(&[0x69]), Err(IonParserError::InvalidOpcode(0x69)));
+
+    let mut directive = vec![0xE7, 0xE7];
+    directive.extend_from_slice(b"$ion_encoding");
+    directive.push(0xC0);
+
+    assert_eq!(
+        parse(&directive),
+        Err(IonParserError::UnsupportedEncodingDirective)
+    );
+
+    let mut stream = ION_1_1.to_vec();
+    stream.extend_from_slice(&[0xB3, 0x92, 0xC3, 0x28]);
+
+    let error = IonParser::new(&stream[..])
+        .consume_value_located()
+        .unwrap_err();
+
+    assert_eq!(error, IonParserError::NonUtf8String);
+    assert_eq!(error.path().to_string(), "[0][0]");
+
+    assert_eq!(
+        IonParser::new(&stream[..]).skip_value().unwrap_err(),
+        IonParserError::Ion11NotSupported
+    );
+}
@@ -0,0 +1,199 @@
+use crate::{IonEncoder, IonParser, IonParserError, IonParserOptions, IonSliceParser, IonValue};
+
+fn encode(values: Vec<IonValue>) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+    values.into_iter().for_each(|value| encoder.add(value));
+    encoder.encode()
+}
+
+fn parse_with(bytes: &[u8], options: IonParserOptions) -> Result<Vec<IonValue>, IonParserError> {
+    let mut parser = IonParser::new(bytes);
+    parser.with_options(options);
+    parser.consume_all()
+}
+
+fn local_table(imports: Vec<IonValue>, symbols: &[&str]) -> IonValue {
+    IonValue::Annotation(
+        vec!["$ion_symbol_table".into()],
+        Box::new(IonValue::Struct(
+            vec![
+                ("imports".to_string(), IonValue::List(imports)),
+                (
+                    "symbols".to_string(),
+                    IonValue::List(
+                        symbols
+                            .iter()
+                            .map(|symbol| IonValue::String(symbol.to_string()))
+                            .collect(),
+                    ),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )),
+    )
+}
+
+fn import(name: &str, max_id: i64) -> IonValue {
+    IonValue::Struct(
+        vec![
+            ("name".to_string(), IonValue::String(name.to_string())),
+            ("max_id".to_string(), IonValue::Integer(max_id)),
+        ]
+        .into_iter()
+        .collect(),
+    )
+}
+
+#[test]
+fn max_depth_limits_nested_containers() {
+    let nested = IonValue::List(vec![IonValue::SExpr(vec![IonValue::Struct(
+        vec![("field".to_string(), IonValue::Integer(1))]
+            .into_iter()
+            .collect(),
+    )])]);
+    let bytes = encode(vec![nested.clone()]);
+
+    let options = |max_depth| IonParserOptions {
+        max_depth: Some(max_depth),
+        ..IonParserOptions::default()
+    };
+
+    assert_eq!(parse_with(&bytes, options(3)), Ok(vec![nested]));
+    assert_eq!(
+        parse_with(&bytes, options(2)),
+        Err(IonParserError::MaxDepthExceeded)
+    );
+}
+
+#[test]
+fn max_depth_is_unbounded_by_default() {
+    let nested =
+        |depth| (0..depth).fold(IonValue::Integer(1), |value, _| IonValue::List(vec![value]));
+
+    let bytes = encode(vec![nested(200)]);
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all(),
+        Ok(vec![nested(200)])
+    );
+}
+
+#[test]
+fn max_depth_limits_annotated_containers() {
+    let mut nested = IonValue::Integer(1);
+
+    for _ in 0..10 {
+        nested = IonValue::Annotation(vec!["a".into()], Box::new(IonValue::List(vec![nested])));
+    }
+
+    let bytes = encode(vec![nested.clone()]);
+
+    let options = |max_depth| IonParserOptions {
+        max_depth: Some(max_depth),
+        ..IonParserOptions::default()
+    };
+
+    assert_eq!(parse_with(&bytes, options(10)), Ok(vec![nested.clone()]));
+    assert_eq!(
+        parse_with(&bytes, options(3)),
+        Err(IonParserError::MaxDepthExceeded)
+    );
+
+    let mut parser = IonSliceParser::new(&bytes);
+    parser.with_options(options(10));
+
+    assert_eq!(parser.consume_value().unwrap().0.into_owned(), nested);
+
+    let mut parser = IonSliceParser::new(&bytes);
+    parser.with_options(options(3));
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::MaxDepthExceeded
+    );
+}
+
+#[test]
+fn max_value_size_is_checked_before_reading_the_value() {
+    let options = IonParserOptions {
+        max_value_size: Some(16),
+        ..IonParserOptions::default()
+    };
+
+    let short = encode(vec![IonValue::String("a".repeat(16))]);
+    let long = encode(vec![IonValue::String("a".repeat(17))]);
+
+    assert!(parse_with(&short, options.clone()).is_ok());
+    assert_eq!(
+        parse_with(&long, options.clone()),
+        Err(IonParserError::MaxValueSizeExceeded)
+    );
+
+    // A blob declaring 256 MiB that are not there
+    let hostile = [0xE0, 0x01, 0x00, 0xEA, 0xAE, 0x7F, 0x7F, 0x7F, 0xFF];
+
+    assert_eq!(
+        parse_with(&hostile, options),
+        Err(IonParserError::MaxValueSizeExceeded)
+    );
+}
+
+#[test]
+fn max_symbols_and_imports_limit_symbol_tables() {
+    let options = IonParserOptions {
+        max_symbols: Some(100),
+        max_imports: Some(1),
+        ..IonParserOptions::default()
+    };
+
+    let small_table = encode(vec![local_table(vec![import("table", 10)], &["a", "b"])]);
+    let huge_import = encode(vec![local_table(vec![import("table", 1 << 40)], &[])]);
+    let many_symbols = encode(vec![local_table(vec![], &["symbol"; 101])]);
+    let many_imports = encode(vec![local_table(
+        vec![import("table", 1), import("other", 1)],
+        &[],
+    )]);
+
+    assert_eq!(parse_with(&small_table, options.clone()), Ok(vec![]));
+    assert_eq!(
+        parse_with(&huge_import, options.clone()),
+        Err(IonParserError::MaxSymbolsExceeded)
+    );
+    assert_eq!(
+        parse_with(&many_symbols, options.clone()),
+        Err(IonParserError::MaxSymbolsExceeded)
+    );
+    assert_eq!(
+        parse_with(&many_imports, options),
+        Err(IonParserError::MaxImportsExceeded)
+    );
+}
+
+#[test]
+fn max_total_input_limits_the_bytes_read() {
+    let bytes = encode(vec![
+        IonValue::String("first".into()),
+        IonValue::String("second".into()),
+    ]);
+
+    let options = |max_total_input| IonParserOptions {
+        max_total_input: Some(max_total_input),
+        ..IonParserOptions::default()
+    };
+
+    assert_eq!(parse_with(&bytes, options(bytes.len())).unwrap().len(), 2);
+    assert_eq!(
+        parse_with(&bytes, options(bytes.len() - 1)),
+        Err(IonParserError::MaxTotalInputExceeded)
+    );
+
+    let mut parser = IonSliceParser::new(&bytes);
+    parser.with_options(options(bytes.len() - 1));
+
+    assert!(parser.consume_value().is_ok());
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::MaxTotalInputExceeded
+    );
+}
@@ -0,0 +1,205 @@
+use crate::hashmap;
+use crate::{IonEncoder, IonPath, IonPathError, IonPathStep, IonReader, IonValue};
+
+fn string(value: &str) -> IonValue {
+    IonValue::String(value.to_string())
+}
+
+fn path(path: &str) -> IonPath {
+    path.parse().unwrap()
+}
+
+fn orders() -> IonValue {
+    let order = |id: i64, price: i64| {
+        IonValue::Struct(hashmap!(
+            "id".to_string() => IonValue::Integer(id),
+            "price".to_string() => IonValue::Annotation(
+                vec!["usd".into()],
+                Box::new(IonValue::Integer(price))
+            )
+        ))
+    };
+
+    IonValue::Struct(hashmap!(
+        "customer".to_string() => string("Alice"),
+        "orders".to_string() => IonValue::List(vec![order(1, 10), order(2, 20), order(3, 30)]),
+        "tags".to_string() => IonValue::Annotation(
+            vec!["set".into()],
+            Box::new(IonValue::SExpr(vec![string("new"), string("vip")]))
+        ),
+        "first name".to_string() => string("Alice")
+    ))
+}
+
+#[test]
+fn ion_path_parses_and_displays() {
+    let parsed = path("orders[3].price::usd.*[*].'first name'");
+
+    assert_eq!(
+        parsed.steps(),
+        [
+            IonPathStep::Field("orders".to_string()),
+            IonPathStep::Index(3),
+            IonPathStep::Field("price".to_string()),
+            IonPathStep::Annotation("usd".to_string()),
+            IonPathStep::AnyField,
+            IonPathStep::AnyIndex,
+            IonPathStep::Field("first name".to_string()),
+        ]
+    );
+
+    assert_eq!(
+        parsed.to_string(),
+        ".orders[3].price::usd.*[*].'first name'"
+    );
+    assert_eq!(path(&parsed.to_string()), parsed);
+    assert_eq!(
+        path(".'it\\'s'").steps(),
+        [IonPathStep::Field("it's".to_string())]
+    );
+    assert_eq!(path(""), IonPath::default());
+}
+
+#[test]
+fn ion_path_reports_invalid_paths() {
+    assert_eq!("a.".parse::<IonPath>(), Err(IonPathError::UnexpectedEnd));
+    assert_eq!(
+        "a[b]".parse::<IonPath>(),
+        Err(IonPathError::InvalidIndex(2))
+    );
+    assert_eq!("a[1".parse::<IonPath>(), Err(IonPathError::UnexpectedEnd));
+    assert_eq!(
+        "a:b".parse::<IonPath>(),
+        Err(IonPathError::UnexpectedCharacter(2, 'b'))
+    );
+    assert_eq!("a.'b".parse::<IonPath>(), Err(IonPathError::UnexpectedEnd));
+    assert_eq!(
+        "a b".parse::<IonPath>(),
+        Err(IonPathError::UnexpectedCharacter(1, ' '))
+    );
+}
+
+#[test]
+fn ion_path_selects_references_and_copies() {
+    let value = orders();
+
+    assert_eq!(path("").get(&value), Some(&value));
+    assert_eq!(path("customer").get(&value), Some(&string("Alice")));
+    assert_eq!(path(".'first name'").get(&value), Some(&string("Alice")));
+    assert_eq!(
+        path("orders[1].id").get(&value),
+        Some(&IonValue::Integer(2))
+    );
+
+    // Annotations are kept in the result and looked through by later steps
+    assert_eq!(
+        path("orders[*].price").select_owned(&value),
+        [10, 20, 30]
+            .iter()
+            .map(|price| IonValue::Annotation(
+                vec!["usd".into()],
+                Box::new(IonValue::Integer(*price))
+            ))
+            .collect::<Vec<IonValue>>()
+    );
+    assert_eq!(
+        path("tags[*]").select(&value),
+        vec![&string("new"), &string("vip")]
+    );
+
+    // Annotation filters
+    assert_eq!(path("tags::set[0]").get(&value), Some(&string("new")));
+    assert_eq!(path("tags::list[0]").get(&value), None);
+    assert_eq!(path("orders[*].price::eur").select(&value).len(), 0);
+    assert_eq!(path("orders[*].price::usd").select(&value).len(), 3);
+
+    // Steps that don't apply select nothing
+    assert_eq!(path("orders[3]").get(&value), None);
+    assert_eq!(path("orders.id").get(&value), None);
+    assert_eq!(path("customer[0]").get(&value), None);
+    assert_eq!(path("missing").get(&value), None);
+
+    let mut fields = path(".*").select_owned(&value);
+    fields.retain(|field| field == &string("Alice"));
+
+    assert_eq!(fields.len(), 2);
+}
+
+#[test]
+fn ion_path_selects_repeated_fields_of_ordered_structs() {
+    let value = IonValue::OrderedStruct(vec![
+        ("a".into(), IonValue::Integer(1)),
+        ("b".into(), IonValue::Integer(2)),
+        ("a".into(), IonValue::Integer(3)),
+    ]);
+
+    assert_eq!(
+        path("a").select_owned(&value),
+        vec![IonValue::Integer(1), IonValue::Integer(3)]
+    );
+    assert_eq!(path(".*").select(&value).len(), 3);
+}
+
+#[test]
+fn ion_path_edits_in_place() {
+    let mut value = orders();
+
+    for price in path("orders[*].price").select_mut(&mut value) {
+        *price = IonValue::Integer(0);
+    }
+
+    *path("customer").get_mut(&mut value).unwrap() = string("Bob");
+
+    assert_eq!(
+        path("orders[*].price").select_owned(&value),
+        vec![IonValue::Integer(0); 3]
+    );
+    assert_eq!(path("customer").get(&value), Some(&string("Bob")));
+    assert_eq!(
+        path("orders[0].id").get(&value),
+        Some(&IonValue::Integer(1))
+    );
+    assert!(path("missing").get_mut(&mut value).is_none());
+}
+
+#[test]
+fn ion_path_reads_from_the_stream() {
+    let values = vec![orders(), string("not a struct"), orders()];
+
+    let mut encoder = IonEncoder::new();
+
+    for value in &values {
+        encoder.add(value.clone());
+    }
+
+    let bytes = encoder.encode();
+
+    for query in [
+        "",
+        "orders[1].id",
+        "orders[*].price",
+        "orders[*].price::usd",
+        "tags::set[1]",
+        "tags::list",
+        ".*",
+        "missing[0]",
+    ] {
+        let query = path(query);
+
+        let mut expected: Vec<IonValue> = values
+            .iter()
+            .flat_map(|value| query.select_owned(value))
+            .collect();
+
+        let mut reader = IonReader::new(&bytes[..]);
+        let mut read = query.read(&mut reader).unwrap();
+
+        // Struct fields can be stored in a different order than the HashMap
+        let key = |value: &IonValue| format!("{:?}", value);
+        read.sort_by_key(key);
+        expected.sort_by_key(key);
+
+        assert_eq!(read, expected, "{}", query);
+        assert!(reader.next().unwrap().is_none());
+    }
+}
@@ -0,0 +1,296 @@
+use crate::hashmap;
+use crate::{
+    IonEncoder, IonParser, IonParserError, IonReader, IonValue, IonVersion, NullIonValue, ValueType,
+};
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use num_bigint::BigInt;
+use std::io::Cursor;
+use std::str::FromStr;
+
+const QLDB_EXAMPLE: &[u8] = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+
+fn encode(values: Vec<IonValue>) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+    values.into_iter().for_each(|value| encoder.add(value));
+    encoder.encode()
+}
+
+#[test]
+fn reader_walks_struct_fields() {
+    let mut reader = IonReader::new(QLDB_EXAMPLE);
+
+    let header = reader.next().unwrap().unwrap();
+    assert_eq!(header.get_type(), ValueType::Struct);
+    assert_eq!(reader.depth(), 0);
+
+    reader.step_in().unwrap();
+    assert_eq!(reader.depth(), 1);
+
+    let mut fields = vec![];
+
+    while let Some(header) = reader.next().unwrap() {
+        let name = reader.field_name().unwrap().to_string();
+
+        match header.get_type() {
+            ValueType::String => fields.push((name, reader.read_str().unwrap().to_string())),
+            ValueType::PositiveInt => fields.push((name, reader.read_i64().unwrap().to_string())),
+            other => panic!("Unexpected type {:?}", other),
+        }
+    }
+
+    reader.step_out().unwrap();
+    assert_eq!(reader.depth(), 0);
+    assert_eq!(reader.next().unwrap(), None);
+
+    let expected: Vec<(String, String)> = vec![
+        ("VIN", "1C4RJFAG0FC625797"),
+        ("Type", "Sedan"),
+        ("Year", "2019"),
+        ("Make", "Mercedes"),
+        ("Model", "CLK 350"),
+        ("Color", "White"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+
+    assert_eq!(fields, expected);
+}
+
+#[test]
+fn reader_reads_scalars_lazily() {
+    let timestamp = DateTime::parse_from_rfc3339("2020-03-04T10:20:30.123+01:00").unwrap();
+    let big_int = BigInt::from_str("123456789012345678901234567890").unwrap();
+
+    let bytes = encode(vec![
+        IonValue::Bool(true),
+        IonValue::Integer(-7),
+        IonValue::BigInteger(big_int.clone()),
+        IonValue::Float(1.5),
+        IonValue::Decimal(BigDecimal::from_str("12.34").unwrap()),
+        IonValue::DateTime(timestamp.into()),
+        IonValue::String("text".to_string()),
+        IonValue::Symbol("symbol".into()),
+        IonValue::Blob(vec![1, 2, 3]),
+        IonValue::Clob(vec![4, 5]),
+        IonValue::Null(NullIonValue::Integer),
+    ]);
+
+    let mut reader = IonReader::new(&bytes[..]);
+
+    reader.next().unwrap();
+    assert!(reader.read_bool().unwrap());
+    reader.next().unwrap();
+    assert_eq!(reader.read_i64().unwrap(), -7);
+    reader.next().unwrap();
+    assert_eq!(reader.read_big_int().unwrap(), big_int);
+    reader.next().unwrap();
+    assert_eq!(reader.read_f64().unwrap(), 1.5);
+    reader.next().unwrap();
+    assert_eq!(
+        reader.read_decimal().unwrap(),
+        BigDecimal::from_str("12.34").unwrap()
+    );
+    reader.next().unwrap();
+    assert_eq!(reader.read_timestamp().unwrap(), timestamp.into());
+    reader.next().unwrap();
+    assert_eq!(reader.read_str().unwrap(), "text");
+    reader.next().unwrap();
+    assert_eq!(reader.read_symbol().unwrap(), "symbol");
+    reader.next().unwrap();
+    assert_eq!(reader.read_blob().unwrap(), vec![1, 2, 3]);
+    reader.next().unwrap();
+    assert_eq!(reader.read_clob().unwrap(), vec![4, 5]);
+
+    let header = reader.next().unwrap().unwrap();
+    assert_eq!(header.get_type(), ValueType::PositiveInt);
+    assert!(reader.is_null());
+
+    assert_eq!(reader.next().unwrap(), None);
+}
+
+#[test]
+fn reader_skips_unread_values() {
+    let bytes = encode(vec![
+        IonValue::List((0..1000).map(IonValue::Integer).collect()),
+        IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::String("skipped".to_string()),
+            "b".to_string() => IonValue::List(vec![IonValue::Integer(1)])
+        )),
+        IonValue::Integer(42),
+    ]);
+
+    let mut reader = IonReader::new(&bytes[..]);
+
+    assert_eq!(reader.next().unwrap().unwrap().get_type(), ValueType::List);
+    assert_eq!(
+        reader.next().unwrap().unwrap().get_type(),
+        ValueType::Struct
+    );
+
+    reader.step_in().unwrap();
+    reader.next().unwrap();
+    reader.step_out().unwrap();
+
+    reader.next().unwrap();
+    assert_eq!(reader.read_i64().unwrap(), 42);
+    assert_eq!(reader.next().unwrap(), None);
+    assert_eq!(reader.position(), bytes.len());
+}
+
+#[test]
+fn reader_exposes_annotations() {
+    let bytes = encode(vec![IonValue::List(vec![
+        IonValue::Annotation(
+            vec!["first".into(), "second".into()],
+            Box::new(IonValue::Integer(1)),
+        ),
+        IonValue::Annotation(
+            vec!["container".into()],
+            Box::new(IonValue::SExpr(vec![IonValue::Symbol("a".into())])),
+        ),
+        IonValue::Integer(3),
+    ])]);
+
+    let mut reader = IonReader::new(&bytes[..]);
+
+    reader.next().unwrap();
+    assert!(reader.annotations().is_empty());
+    reader.step_in().unwrap();
+
+    reader.next().unwrap();
+    assert_eq!(reader.annotations(), ["first", "second"]);
+
+    assert_eq!(reader.next().unwrap().unwrap().get_type(), ValueType::SExpr);
+    assert_eq!(reader.annotations(), ["container"]);
+    reader.step_in().unwrap();
+    reader.step_out().unwrap();
+
+    reader.next().unwrap();
+    assert!(reader.annotations().is_empty());
+    assert_eq!(reader.read_i64().unwrap(), 3);
+
+    assert_eq!(reader.next().unwrap(), None);
+    reader.step_out().unwrap();
+    assert_eq!(reader.next().unwrap(), None);
+}
+
+#[test]
+fn reader_read_value_matches_parser() {
+    let values = vec![
+        IonValue::Annotation(
+            vec!["entry".into()],
+            Box::new(IonValue::Struct(hashmap!(
+                "id".to_string() => IonValue::Integer(1),
+                "tags".to_string() => IonValue::List(vec![IonValue::Symbol("x".into())])
+            ))),
+        ),
+        IonValue::SExpr(vec![IonValue::Null(NullIonValue::Null)]),
+    ];
+
+    let bytes = encode(values.clone());
+
+    let mut reader = IonReader::new(&bytes[..]);
+    let mut read_values = vec![];
+
+    while reader.next().unwrap().is_some() {
+        read_values.push(reader.read_value().unwrap());
+    }
+
+    assert_eq!(read_values, values);
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        read_values
+    );
+}
+
+#[test]
+fn reader_skips_nop_padding() {
+    // Struct with a NOP padding field followed by `name: 1`, then a NOP padding
+    // and the int 2 in the top level.
+    let ion_binary = b"\xe0\x01\0\xea\xd6\x84\x01\x00\x84\x21\x01\x01\x00\x21\x02";
+
+    let mut reader = IonReader::new(&ion_binary[..]);
+
+    reader.next().unwrap();
+    reader.step_in().unwrap();
+    reader.next().unwrap();
+    assert_eq!(reader.field_name(), Some("name"));
+    assert_eq!(reader.read_i64().unwrap(), 1);
+    assert_eq!(reader.next().unwrap(), None);
+    reader.step_out().unwrap();
+
+    reader.next().unwrap();
+    assert_eq!(reader.read_i64().unwrap(), 2);
+    assert_eq!(reader.next().unwrap(), None);
+}
+
+#[test]
+fn reader_errors() {
+    let bytes = encode(vec![
+        IonValue::Integer(1),
+        IonValue::Null(NullIonValue::List),
+    ]);
+
+    let mut reader = IonReader::new(&bytes[..]);
+
+    assert_eq!(reader.read_i64(), Err(IonParserError::NoCurrentValue));
+    assert_eq!(reader.step_out(), Err(IonParserError::NotInsideContainer));
+
+    reader.next().unwrap();
+    assert_eq!(reader.step_in(), Err(IonParserError::NotAContainer));
+    assert_eq!(
+        reader.read_str(),
+        Err(IonParserError::TypeNotSupported(IonValue::Integer(1)))
+    );
+    assert_eq!(reader.read_i64(), Err(IonParserError::NoCurrentValue));
+
+    reader.next().unwrap();
+    assert!(reader.is_null());
+    assert_eq!(reader.step_in(), Err(IonParserError::NotAContainer));
+    assert_eq!(reader.next().unwrap(), None);
+}
+
+#[test]
+fn reader_seeks_over_unread_values() {
+    let bytes = encode(vec![
+        IonValue::Struct(hashmap!(
+            "blob".to_string() => IonValue::Blob(vec![0; 5000]),
+            "id".to_string() => IonValue::Integer(7)
+        )),
+        IonValue::String("last".to_string()),
+    ]);
+
+    let mut reader = IonReader::new_seekable(Cursor::new(&bytes));
+    let mut id = None;
+
+    reader.next().unwrap();
+    reader.step_in().unwrap();
+
+    while reader.next().unwrap().is_some() {
+        if reader.field_name() == Some("id") {
+            id = Some(reader.read_i64().unwrap());
+        }
+    }
+
+    reader.step_out().unwrap();
+
+    assert_eq!(id, Some(7));
+
+    reader.next().unwrap();
+    assert_eq!(reader.read_str().unwrap(), "last");
+    assert_eq!(reader.next().unwrap(), None);
+}
+
+#[test]
+fn reader_does_not_read_ion_1_1() {
+    let mut encoder = IonEncoder::new();
+    encoder.with_target_version(IonVersion::Ion1_1);
+    encoder.add(IonValue::Integer(1));
+    let bytes = encoder.encode();
+
+    let mut reader = IonReader::new(&bytes[..]);
+
+    assert_eq!(reader.next(), Err(IonParserError::Ion11NotSupported));
+}
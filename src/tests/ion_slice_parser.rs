@@ -0,0 +1,173 @@
+use crate::{
+    IonEncoder, IonParser, IonParserError, IonSliceParser, IonValue, IonValueRef, IonVersion,
+    NullIonValue, ParsingError,
+};
+use bigdecimal::BigDecimal;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+const QLDB_EXAMPLE: &[u8] = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+
+fn is_borrowed_from(bytes: &[u8], input: &[u8]) -> bool {
+    let range = input.as_ptr_range();
+    range.contains(&bytes.as_ptr()) && bytes.len() <= input.len()
+}
+
+#[test]
+fn slice_parser_returns_the_same_values_as_the_parser() {
+    let values = vec![
+        IonValue::Null(NullIonValue::String),
+        IonValue::Bool(true),
+        IonValue::Integer(-42),
+        IonValue::Float(1.5),
+        IonValue::Decimal(BigDecimal::from_str("3.14").unwrap()),
+        IonValue::String("".into()),
+        IonValue::Symbol("symbol".into()),
+        IonValue::Clob(b"clob".to_vec()),
+        IonValue::Blob(vec![]),
+        IonValue::SExpr(vec![IonValue::Symbol("+".into()), IonValue::Integer(1)]),
+        IonValue::Annotation(
+            vec!["annotation".into()],
+            Box::new(IonValue::List(vec![
+                IonValue::String("a longer string than fourteen bytes".into()),
+                IonValue::Blob(vec![1, 2, 3]),
+            ])),
+        ),
+    ];
+
+    let mut encoder = IonEncoder::new();
+    values.iter().cloned().for_each(|value| encoder.add(value));
+    let bytes = encoder.encode();
+
+    let parsed = IonSliceParser::new(&bytes).consume_all().unwrap();
+    let owned: Vec<IonValue> = parsed.iter().map(IonValueRef::to_owned).collect();
+
+    assert_eq!(owned, values);
+    assert_eq!(owned, IonParser::new(&bytes[..]).consume_all().unwrap());
+}
+
+#[test]
+fn slice_parser_reads_ion_1_1() {
+    let values = vec![
+        IonValue::Symbol("sedan".into()),
+        IonValue::Annotation(
+            vec!["cars".into()],
+            Box::new(IonValue::List(vec![
+                IonValue::String("a longer string than fifteen bytes".into()),
+                IonValue::Integer(2019),
+            ])),
+        ),
+    ];
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_target_version(IonVersion::Ion1_1);
+    values.iter().cloned().for_each(|value| encoder.add(value));
+    let bytes = encoder.encode();
+
+    let parsed = IonSliceParser::new(&bytes).consume_all().unwrap();
+    let owned: Vec<IonValue> = parsed.iter().map(IonValueRef::to_owned).collect();
+
+    assert_eq!(owned, values);
+}
+
+#[test]
+fn slice_parser_borrows_strings_and_blobs() {
+    let mut parser = IonSliceParser::new(QLDB_EXAMPLE);
+    parser.with_ordered_structs();
+
+    let (value, consumed) = parser.consume_value().unwrap();
+
+    assert_eq!(consumed, QLDB_EXAMPLE.len() - 4);
+    assert_eq!(parser.position(), QLDB_EXAMPLE.len());
+
+    let fields = match value {
+        IonValueRef::OrderedStruct(fields) => fields,
+        other => panic!("Unexpected value {:?}", other),
+    };
+
+    assert_eq!(fields[0].0, "VIN");
+
+    match &fields[0].1 {
+        IonValueRef::String(Cow::Borrowed(text)) => {
+            assert_eq!(*text, "1C4RJFAG0FC625797");
+            assert!(is_borrowed_from(text.as_bytes(), QLDB_EXAMPLE));
+        }
+        other => panic!("Unexpected value {:?}", other),
+    }
+
+    let bytes = [0xE0, 0x01, 0x00, 0xEA, 0xA3, 1, 2, 3, 0x92, b'h', b'i'];
+    let values = IonSliceParser::new(&bytes).consume_all().unwrap();
+
+    match &values[..] {
+        [IonValueRef::Blob(Cow::Borrowed(blob)), IonValueRef::Clob(Cow::Borrowed(clob))] => {
+            assert_eq!(*blob, &[1, 2, 3]);
+            assert_eq!(*clob, b"hi");
+            assert!(is_borrowed_from(blob, &bytes));
+            assert!(is_borrowed_from(clob, &bytes));
+        }
+        other => panic!("Unexpected values {:?}", other),
+    }
+}
+
+#[test]
+fn slice_parser_validates_as_the_parser() {
+    let truncated_string = [0xE0, 0x01, 0x00, 0xEA, 0x85, b'a', b'b'];
+    let non_utf8_string = [0xE0, 0x01, 0x00, 0xEA, 0x82, 0xC3, 0x28];
+    let truncated_blob = [0xE0, 0x01, 0x00, 0xEA, 0xA3];
+    let nested_non_utf8_string = [0xE0, 0x01, 0x00, 0xEA, 0xB5, 0xD4, 0x84, 0x82, 0xC3, 0x28];
+
+    for bytes in [
+        &truncated_string[..],
+        &non_utf8_string,
+        &truncated_blob,
+        &nested_non_utf8_string,
+    ] {
+        let expected = IonParser::new(bytes).consume_value().unwrap_err();
+
+        assert_eq!(
+            IonSliceParser::new(bytes).consume_value().unwrap_err(),
+            expected
+        );
+    }
+
+    assert_eq!(
+        IonSliceParser::new(&truncated_string)
+            .consume_value()
+            .unwrap_err(),
+        IonParserError::BinaryError(ParsingError::NotEnoughtDataToRead(2))
+    );
+    assert_eq!(
+        IonSliceParser::new(&non_utf8_string)
+            .consume_value()
+            .unwrap_err(),
+        IonParserError::NonUtf8String
+    );
+    assert_eq!(
+        IonSliceParser::new(&nested_non_utf8_string)
+            .consume_value_located()
+            .unwrap_err()
+            .path()
+            .to_string(),
+        "[0][0].name"
+    );
+}
+
+#[test]
+fn value_ref_converts_from_and_into_owned_values() {
+    let value = IonValue::Struct(
+        vec![(
+            "field".to_string(),
+            IonValue::Annotation(
+                vec!["annotation".into()],
+                Box::new(IonValue::Clob(b"clob".to_vec())),
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    );
+
+    let value_ref = IonValueRef::from(value.clone());
+
+    assert_eq!(value_ref.to_owned(), value);
+    assert_eq!(IonValue::from(value_ref), value);
+}
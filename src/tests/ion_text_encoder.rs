@@ -0,0 +1,210 @@
+use crate::hashmap;
+use crate::{IonEncoder, IonParser, IonTextEncoder, IonTextParser, IonValue, NullIonValue};
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn encode(value: IonValue) -> String {
+    let mut encoder = IonTextEncoder::new();
+    encoder.add(value);
+    encoder.encode()
+}
+
+fn encode_pretty(value: IonValue) -> String {
+    let mut encoder = IonTextEncoder::new_pretty();
+    encoder.add(value);
+    encoder.encode()
+}
+
+fn parse_text(text: &str) -> IonValue {
+    IonTextParser::new(text.as_bytes())
+        .consume_value()
+        .unwrap()
+        .0
+}
+
+fn check_round_trip(value: IonValue) {
+    let compact = encode(value.clone());
+    assert_eq!(parse_text(&compact), value, "Compact text: {:?}", compact);
+
+    let pretty = encode_pretty(value.clone());
+    assert_eq!(parse_text(&pretty), value, "Pretty text: {:?}", pretty);
+}
+
+#[test]
+fn text_encode_scalars() {
+    assert_eq!(encode(IonValue::Null(NullIonValue::Null)), "null");
+    assert_eq!(
+        encode(IonValue::Null(NullIonValue::DateTime)),
+        "null.timestamp"
+    );
+    assert_eq!(encode(IonValue::Bool(true)), "true");
+    assert_eq!(encode(IonValue::Integer(-42)), "-42");
+    assert_eq!(
+        encode(IonValue::BigInteger(
+            BigInt::from_str("123456789012345678901234567890").unwrap()
+        )),
+        "123456789012345678901234567890"
+    );
+    assert_eq!(encode(IonValue::Float(1.5)), "1.5e0");
+    assert_eq!(encode(IonValue::Float(-0.0)), "-0e0");
+    assert_eq!(encode(IonValue::Float(f64::NAN)), "nan");
+    assert_eq!(encode(IonValue::Float(f64::INFINITY)), "+inf");
+    assert_eq!(encode(IonValue::Float(f64::NEG_INFINITY)), "-inf");
+}
+
+#[test]
+fn text_encode_decimals() {
+    let decimal = |text: &str| IonValue::Decimal(BigDecimal::from_str(text).unwrap());
+
+    assert_eq!(encode(decimal("1.50")), "1.50");
+    assert_eq!(encode(decimal("-0.005")), "-0.005");
+    assert_eq!(encode(decimal("12")), "12.");
+    assert_eq!(encode(decimal("1.5E+3")), "15d2");
+    assert_eq!(encode(decimal("-0")), "0.");
+}
+
+#[test]
+fn text_encode_timestamps() {
+    let timestamp =
+        |text: &str| IonValue::DateTime(DateTime::parse_from_rfc3339(text).unwrap().into());
+
+    assert_eq!(
+        encode(timestamp("2011-02-20T00:00:00Z")),
+        "2011-02-20T00:00:00Z"
+    );
+    assert_eq!(
+        encode(timestamp("2011-02-20T19:30:59.100+01:00")),
+        "2011-02-20T19:30:59.1+01:00"
+    );
+    assert_eq!(
+        encode(timestamp("2011-02-20T00:00:00-08:30")),
+        "2011-02-20T00:00:00-08:30"
+    );
+    assert_eq!(
+        encode(timestamp("2011-02-20T10:00:00.000000001Z")),
+        "2011-02-20T10:00:00.000000001Z"
+    );
+}
+
+#[test]
+fn text_encode_strings_and_symbols() {
+    assert_eq!(
+        encode(IonValue::String("a \"quoted\"\n\\ é\u{1}".to_string())),
+        r#""a \"quoted\"\n\\ é\x01""#
+    );
+    assert_eq!(encode(IonValue::Symbol("abc_$1".into())), "abc_$1");
+    assert_eq!(encode(IonValue::Symbol("two words".into())), "'two words'");
+    assert_eq!(encode(IonValue::Symbol("null".into())), "'null'");
+    assert_eq!(encode(IonValue::Symbol("$10".into())), "'$10'");
+    assert_eq!(encode(IonValue::Symbol("1a".into())), "'1a'");
+    assert_eq!(encode(IonValue::Symbol("it's".into())), r"'it\'s'");
+    assert_eq!(encode(IonValue::Symbol("".into())), "''");
+}
+
+#[test]
+fn text_encode_lobs() {
+    assert_eq!(encode(IonValue::Blob(b"".to_vec())), "{{}}");
+    assert_eq!(encode(IonValue::Blob(b"a".to_vec())), "{{YQ==}}");
+    assert_eq!(encode(IonValue::Blob(b"ab".to_vec())), "{{YWI=}}");
+    assert_eq!(encode(IonValue::Blob(b"abc".to_vec())), "{{YWJj}}");
+    assert_eq!(
+        encode(IonValue::Clob(b"a\"b\\\n\xff".to_vec())),
+        r#"{{"a\"b\\\x0a\xff"}}"#
+    );
+}
+
+#[test]
+fn text_encode_containers() {
+    let value = IonValue::Annotation(
+        vec!["ann".into(), "two words".into()],
+        Box::new(IonValue::Struct(hashmap!(
+            "b".to_string() => IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+            "a".to_string() => IonValue::SExpr(vec![
+                IonValue::Symbol("+".into()),
+                IonValue::Symbol("x".into()),
+            ]),
+            "c".to_string() => IonValue::Struct(HashMap::new())
+        ))),
+    );
+
+    assert_eq!(
+        encode(value.clone()),
+        "ann::'two words'::{a:('+' x),b:[1,2],c:{}}"
+    );
+
+    assert_eq!(
+        encode_pretty(value),
+        "ann::'two words'::{\n  a: (\n    '+'\n    x\n  ),\n  b: [\n    1,\n    2\n  ],\n  c: {}\n}"
+    );
+}
+
+#[test]
+fn text_encode_multiple_values() {
+    let mut encoder = IonTextEncoder::new();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::Symbol("a".into()));
+    assert_eq!(encoder.encode(), "1 a");
+
+    let mut encoder = IonTextEncoder::new_pretty();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::List(vec![]));
+    assert_eq!(encoder.encode(), "1\n[]");
+
+    assert_eq!(encoder.encode(), "");
+}
+
+#[test]
+fn text_encode_display() {
+    let value = IonValue::List(vec![IonValue::Integer(1), IonValue::Bool(false)]);
+
+    assert_eq!(format!("{}", value), "[1,false]");
+    assert_eq!(format!("{:#}", value), "[\n  1,\n  false\n]");
+}
+
+#[test]
+fn text_encode_round_trip() {
+    check_round_trip(IonValue::Struct(hashmap!(
+        "Model".to_string() => IonValue::String("CLK 350".to_string()),
+        "Year".to_string() => IonValue::Integer(2019),
+        "Price".to_string() => IonValue::Decimal(BigDecimal::from_str("-12345.678").unwrap()),
+        "Weight".to_string() => IonValue::Float(1234.5e-10),
+        "Sold".to_string() => IonValue::DateTime(
+            DateTime::parse_from_rfc3339("2020-03-04T10:20:30.123456-05:00").unwrap().into()
+        ),
+        "Photo".to_string() => IonValue::Blob(vec![0, 1, 2, 250, 251, 252, 253]),
+        "Raw".to_string() => IonValue::Clob(b"\0\r\t\x7f".to_vec()),
+        "tags".to_string() => IonValue::SExpr(vec![
+            IonValue::Symbol("sedan".into()),
+            IonValue::Symbol("'quoted'".into()),
+            IonValue::Null(NullIonValue::Symbol),
+        ]),
+        "nested list".to_string() => IonValue::List(vec![
+            IonValue::List(vec![]),
+            IonValue::Annotation(
+                vec!["$ion_symbol_table".into()],
+                Box::new(IonValue::Integer(0)),
+            ),
+        ])
+    )));
+}
+
+#[test]
+fn text_encode_round_trip_from_binary() {
+    let value = IonValue::List(vec![
+        IonValue::String("Ünïcödé \u{1F600}".to_string()),
+        IonValue::Decimal(BigDecimal::from_str("1e-20").unwrap()),
+        IonValue::Decimal(BigDecimal::from_str("-987.6543").unwrap()),
+        IonValue::Null(NullIonValue::Struct),
+    ]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    let parsed = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+
+    assert_eq!(parse_text(&encode(parsed)), value);
+}
@@ -0,0 +1,440 @@
+use crate::hashmap;
+use crate::{
+    IonParser, IonParserError, IonTextParser, IonValue, NullIonValue, ParsingError,
+    TextParsingError,
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset};
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn parse_all(text: &str) -> Result<Vec<IonValue>, IonParserError> {
+    IonTextParser::new(text.as_bytes()).consume_all()
+}
+
+fn parse_one(text: &str) -> IonValue {
+    let mut values = parse_all(text).unwrap();
+    assert_eq!(values.len(), 1, "Expected one value in {:?}", text);
+    values.pop().unwrap()
+}
+
+#[test]
+fn text_decode_qldb_example() {
+    let ion_text = r#"
+        {
+            VIN: "1C4RJFAG0FC625797",
+            Type: "Sedan",
+            Year: 2019,
+            Make: "Mercedes",
+            Model: "CLK 350",
+            Color: "White",
+        }
+    "#;
+
+    let ion_binary = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+
+    let expected = IonParser::new(&ion_binary[..]).consume_value().unwrap().0;
+
+    assert_eq!(parse_one(ion_text), expected);
+}
+
+#[test]
+fn text_nulls() {
+    let values = parse_all(
+        "null null.null null.bool null.int null.float null.decimal null.timestamp
+         null.string null.symbol null.blob null.clob null.struct null.list null.sexp",
+    )
+    .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Null(NullIonValue::Null),
+            IonValue::Null(NullIonValue::Null),
+            IonValue::Null(NullIonValue::Bool),
+            IonValue::Null(NullIonValue::Integer),
+            IonValue::Null(NullIonValue::Float),
+            IonValue::Null(NullIonValue::Decimal),
+            IonValue::Null(NullIonValue::DateTime),
+            IonValue::Null(NullIonValue::String),
+            IonValue::Null(NullIonValue::Symbol),
+            IonValue::Null(NullIonValue::Blob),
+            IonValue::Null(NullIonValue::Clob),
+            IonValue::Null(NullIonValue::Struct),
+            IonValue::Null(NullIonValue::List),
+            IonValue::Null(NullIonValue::SExpr),
+        ]
+    );
+
+    assert_eq!(
+        parse_all("null.integer"),
+        Err(IonParserError::TextError(
+            TextParsingError::InvalidNullType("integer".to_string())
+        ))
+    );
+}
+
+#[test]
+fn text_integers() {
+    let values = parse_all("0 -0 123 -1_000 0x1F -0Xff 0b101 9223372036854775808").unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Integer(0),
+            IonValue::Integer(0),
+            IonValue::Integer(123),
+            IonValue::Integer(-1000),
+            IonValue::Integer(31),
+            IonValue::Integer(-255),
+            IonValue::Integer(5),
+            IonValue::BigInteger(BigInt::from_str("9223372036854775808").unwrap()),
+        ]
+    );
+    assert!(values[..7]
+        .iter()
+        .all(|value| matches!(value, IonValue::Integer(_))));
+    assert!(matches!(values[7], IonValue::BigInteger(_)));
+
+    for invalid in ["007", "1__0", "1_", "0x_1", "+1", "12a", "0b102"] {
+        assert!(
+            matches!(
+                parse_all(invalid),
+                Err(IonParserError::TextError(
+                    TextParsingError::InvalidNumber(_) | TextParsingError::UnexpectedCharacter(_)
+                ))
+            ),
+            "{} should fail",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn text_floats_and_decimals() {
+    let values = parse_all("1.5e0 -2E-2 1e3 +inf -inf 1.5 -0.25 1. 12d-1 1_2.3_4").unwrap();
+
+    assert_eq!(values[0], IonValue::Float(1.5));
+    assert_eq!(values[1], IonValue::Float(-0.02));
+    assert_eq!(values[2], IonValue::Float(1000.0));
+    assert_eq!(values[3], IonValue::Float(f64::INFINITY));
+    assert_eq!(values[4], IonValue::Float(f64::NEG_INFINITY));
+    assert_eq!(
+        values[5],
+        IonValue::Decimal(BigDecimal::from_str("1.5").unwrap())
+    );
+    assert_eq!(
+        values[6],
+        IonValue::Decimal(BigDecimal::from_str("-0.25").unwrap())
+    );
+    assert_eq!(values[7], IonValue::Decimal(BigDecimal::from(1)));
+    assert_eq!(
+        values[8],
+        IonValue::Decimal(BigDecimal::from_str("1.2").unwrap())
+    );
+    assert_eq!(
+        values[9],
+        IonValue::Decimal(BigDecimal::from_str("12.34").unwrap())
+    );
+
+    match parse_one("nan") {
+        IonValue::Float(value) => assert!(value.is_nan()),
+        _ => panic!("Expected nan"),
+    }
+}
+
+#[test]
+fn text_timestamps() {
+    let values = parse_all(
+        "2007T 2007-02T 2007-02-23 2007-02-23T 2007-02-23T12:14Z
+         2007-02-23T12:14:33.079-08:00 2007-02-23T20:14:33.079+00:00 2007-02-23T12:14:33-00:00",
+    )
+    .unwrap();
+
+    let expected = [
+        "2007-01-01T00:00:00+00:00",
+        "2007-02-01T00:00:00+00:00",
+        "2007-02-23T00:00:00+00:00",
+        "2007-02-23T00:00:00+00:00",
+        "2007-02-23T12:14:00+00:00",
+        "2007-02-23T12:14:33.079-08:00",
+        "2007-02-23T20:14:33.079+00:00",
+        "2007-02-23T12:14:33+00:00",
+    ];
+
+    for (value, expected) in values.iter().zip(expected.iter()) {
+        assert_eq!(
+            DateTime::<FixedOffset>::try_from(value).unwrap(),
+            DateTime::parse_from_rfc3339(expected).unwrap()
+        );
+    }
+
+    for invalid in [
+        "2007-",
+        "2007-02",
+        "2007-02-30",
+        "2007-02-23T12Z",
+        "2007-02-23T12:14",
+        "2007-02-23T12:14:33.Z",
+        "2007-02-23T12:14+24:00",
+        "0000T",
+    ] {
+        assert!(parse_all(invalid).is_err(), "{} should fail", invalid);
+    }
+}
+
+#[test]
+fn text_strings() {
+    let values = parse_all(
+        r#""hello" "tab\there" "é\U0001F600\xe9" "😀" "line \
+continuation" '''long ''' /* comment */ '''string''' "ഇഈഉ""#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::String("hello".to_string()),
+            IonValue::String("tab\there".to_string()),
+            IonValue::String("é😀é".to_string()),
+            IonValue::String("😀".to_string()),
+            IonValue::String("line continuation".to_string()),
+            IonValue::String("long string".to_string()),
+            IonValue::String("ഇഈഉ".to_string()),
+        ]
+    );
+
+    assert_eq!(
+        parse_all("\"new\nline\""),
+        Err(IonParserError::TextError(
+            TextParsingError::InvalidStringCharacter('\n')
+        ))
+    );
+    assert_eq!(
+        parse_all(r#""\q""#),
+        Err(IonParserError::TextError(
+            TextParsingError::InvalidEscapeSequence
+        ))
+    );
+    assert_eq!(
+        parse_all(r#""\uD83D""#),
+        Err(IonParserError::TextError(
+            TextParsingError::InvalidEscapeSequence
+        ))
+    );
+    assert_eq!(
+        parse_all("\"open"),
+        Err(IonParserError::TextError(
+            TextParsingError::UnexpectedEndOfStream
+        ))
+    );
+}
+
+#[test]
+fn text_symbols_and_annotations() {
+    let values = parse_all(
+        "abc 'with space' '' $4 a::b::1 'quoted' :: /* c */ annot :: \"text\" $ion_symbol_table",
+    )
+    .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Symbol("abc".into()),
+            IonValue::Symbol("with space".into()),
+            IonValue::Symbol("".into()),
+            IonValue::Symbol("name".into()),
+            IonValue::Annotation(vec!["a".into(), "b".into()], Box::new(IonValue::Integer(1))),
+            IonValue::Annotation(
+                vec!["quoted".into(), "annot".into()],
+                Box::new(IonValue::String("text".to_string()))
+            ),
+            IonValue::Symbol("$ion_symbol_table".into()),
+        ]
+    );
+
+    assert_eq!(
+        parse_all("null::1"),
+        Err(IonParserError::TextError(
+            TextParsingError::KeywordAsAnnotation("null".to_string())
+        ))
+    );
+    assert_eq!(parse_all("$99"), Err(IonParserError::SymbolIdNotDefined));
+}
+
+#[test]
+fn text_containers() {
+    let value = parse_one(
+        r#"{
+            a: [1, two, "three",],
+            'b c': (+ 1 -2 a.b <= !== -inf),
+            "d": { nested: null.int },
+            '''e''': (),
+            // comment
+            f: [],
+        }"#,
+    );
+
+    assert_eq!(
+        value,
+        IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::List(vec![
+                IonValue::Integer(1),
+                IonValue::Symbol("two".into()),
+                IonValue::String("three".to_string()),
+            ]),
+            "b c".to_string() => IonValue::SExpr(vec![
+                IonValue::Symbol("+".into()),
+                IonValue::Integer(1),
+                IonValue::Integer(-2),
+                IonValue::Symbol("a".into()),
+                IonValue::Symbol(".".into()),
+                IonValue::Symbol("b".into()),
+                IonValue::Symbol("<=".into()),
+                IonValue::Symbol("!==".into()),
+                IonValue::Float(f64::NEG_INFINITY),
+            ]),
+            "d".to_string() => IonValue::Struct(hashmap!(
+                "nested".to_string() => IonValue::Null(NullIonValue::Integer)
+            )),
+            "e".to_string() => IonValue::SExpr(vec![]),
+            "f".to_string() => IonValue::List(vec![])
+        ))
+    );
+
+    for invalid in [
+        "[1 2]",
+        "[,]",
+        "{a 1}",
+        "{a::b: 1}",
+        "[+]",
+        "{a: 1",
+        "(a, b)",
+    ] {
+        assert!(parse_all(invalid).is_err(), "{} should fail", invalid);
+    }
+}
+
+#[test]
+fn text_lobs() {
+    let values = parse_all(
+        r#"{{ aGVsbG8= }} {{}} {{ aG Vs bG8h }} {{ "clob\x00\xff" }} {{ '''multi''' '''clob''' }}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Blob(b"hello".to_vec()),
+            IonValue::Blob(vec![]),
+            IonValue::Blob(b"hello!".to_vec()),
+            IonValue::Clob(vec![b'c', b'l', b'o', b'b', 0, 0xff]),
+            IonValue::Clob(b"multiclob".to_vec()),
+        ]
+    );
+
+    assert_eq!(
+        parse_all("{{ aGVsbG8 }}"),
+        Err(IonParserError::TextError(TextParsingError::InvalidBase64))
+    );
+    assert_eq!(
+        parse_all(r#"{{ "\u00e9" }}"#),
+        Err(IonParserError::TextError(
+            TextParsingError::InvalidEscapeSequence
+        ))
+    );
+    assert_eq!(
+        parse_all("{{ \"é\" }}"),
+        Err(IonParserError::TextError(
+            TextParsingError::InvalidClobCharacter('\u{c3}')
+        ))
+    );
+}
+
+#[test]
+fn text_local_symbol_table() {
+    let values = parse_all(
+        r#"
+        $ion_symbol_table::{ symbols: ["first", "second"] }
+        $10 $11
+        $ion_symbol_table::{ imports: $ion_symbol_table, symbols: ["third"] }
+        $12
+        $ion_1_0
+        '$ion_1_0'
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Symbol("first".into()),
+            IonValue::Symbol("second".into()),
+            IonValue::Symbol("third".into()),
+            IonValue::Symbol("$ion_1_0".into()),
+        ]
+    );
+
+    assert_eq!(
+        parse_all("$ion_symbol_table::{ symbols: [\"a\"] } $ion_1_0 $10"),
+        Err(IonParserError::SymbolIdNotDefined)
+    );
+    assert_eq!(
+        parse_all("$ion_2_0"),
+        Err(IonParserError::TextError(
+            TextParsingError::UnsupportedVersion("$ion_2_0".to_string())
+        ))
+    );
+}
+
+#[test]
+fn text_shared_table_import() {
+    let mut parser = IonTextParser::new(
+        &b"$ion_symbol_table::{ imports: [{ name: \"table\", version: 1, max_id: 2 }] } $10 $11"[..],
+    );
+
+    parser
+        .with_shared_table(
+            "table".to_string(),
+            1,
+            &["Model".to_string(), "Make".to_string()],
+        )
+        .unwrap();
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol("Model".into()),
+            IonValue::Symbol("Make".into()),
+        ]
+    );
+}
+
+#[test]
+fn text_consume_value_until_end() {
+    let mut parser = IonTextParser::new(&b"1 // trailing comment\n"[..]);
+
+    assert_eq!(parser.consume_value().unwrap(), (IonValue::Integer(1), 1));
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::BinaryError(ParsingError::NoDataToRead))
+    );
+    assert_eq!(parse_all("").unwrap(), vec![]);
+    assert_eq!(
+        parse_all("/* open comment"),
+        Err(IonParserError::TextError(
+            TextParsingError::UnterminatedComment
+        ))
+    );
+}
+
+#[test]
+fn text_duplicated_keys_keep_last_value() {
+    let value = parse_one("{ a: 1, a: 2 }");
+
+    let mut expected = HashMap::new();
+    expected.insert("a".to_string(), IonValue::Integer(2));
+
+    assert_eq!(value, IonValue::Struct(expected));
+}
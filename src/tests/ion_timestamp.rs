@@ -0,0 +1,193 @@
+use crate::binary_encoder::encode_ion_value;
+use crate::{
+    IonEncoder, IonHash, IonParser, IonParserError, IonTextParser, IonTimestamp, IonValue,
+    TimestampPrecision,
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+fn parse_text(text: &str) -> IonTimestamp {
+    match IonTextParser::new(text.as_bytes())
+        .consume_value()
+        .unwrap()
+        .0
+    {
+        IonValue::DateTime(value) => value,
+        other => panic!("Expected a timestamp, got {:?}", other),
+    }
+}
+
+fn encode(value: IonTimestamp) -> Vec<u8> {
+    encode_ion_value(&IonValue::DateTime(value))
+}
+
+#[test]
+fn timestamp_keeps_text_precision() {
+    let timestamps = [
+        ("2007T", TimestampPrecision::Year),
+        ("2007-02T", TimestampPrecision::Month),
+        ("2007-02-23", TimestampPrecision::Day),
+        ("2007-02-23T12:14Z", TimestampPrecision::Minute),
+        ("2007-02-23T12:14:33-00:00", TimestampPrecision::Second),
+        ("2007-02-23T12:14:33.100+05:30", TimestampPrecision::Second),
+        (
+            "2007-02-23T12:14:33.123456789012345-08:00",
+            TimestampPrecision::Second,
+        ),
+    ];
+
+    for (text, precision) in timestamps {
+        let timestamp = parse_text(text);
+
+        assert_eq!(timestamp.precision(), precision);
+        assert_eq!(timestamp.to_string(), text);
+    }
+
+    assert_eq!(parse_text("2007-02-23T").to_string(), "2007-02-23");
+}
+
+#[test]
+fn timestamp_binary_round_trip() {
+    let timestamps = [
+        "2007T",
+        "2007-02T",
+        "2007-02-23",
+        "2007-02-23T12:14Z",
+        "2007-02-23T12:14-00:00",
+        "2007-02-23T00:14+02:00",
+        "2007-02-23T12:14:33.100-00:00",
+        "2007-02-23T12:14:33.000Z",
+        "2007-02-23T23:59:59.123456789012345-08:00",
+    ];
+
+    for text in timestamps {
+        let timestamp = parse_text(text);
+
+        let mut encoder = IonEncoder::new();
+        encoder.add(IonValue::DateTime(timestamp.clone()));
+        let bytes = encoder.encode();
+
+        let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+        assert_eq!(values, vec![IonValue::DateTime(timestamp)]);
+
+        let mut encoder = IonEncoder::new();
+        encoder.add(values[0].clone());
+
+        assert_eq!(encoder.encode(), bytes, "{} changed when re-encoded", text);
+    }
+}
+
+#[test]
+fn timestamp_binary_representation() {
+    // Year precision with unknown offset: offset -0, year 2007
+    assert_eq!(encode(parse_text("2007T")), [0x63, 0xC0, 0x0F, 0xD7]);
+
+    // Minute precision in UTC, stored in UTC: 2007-02-23T10:14 for +02:00
+    assert_eq!(
+        encode(parse_text("2007-02-23T12:14+02:00")),
+        [0x68, 0x00, 0xF8, 0x0F, 0xD7, 0x82, 0x97, 0x8A, 0x8E]
+    );
+
+    // Fraction with three digits: exponent -3, coefficient 100
+    assert_eq!(
+        encode(parse_text("2007-02-23T12:14:33.100Z")),
+        [0x6A, 0x80, 0x0F, 0xD7, 0x82, 0x97, 0x8C, 0x8E, 0xA1, 0xC3, 0x64]
+    );
+}
+
+#[test]
+fn timestamp_equality_and_hash_keep_fidelity() {
+    let short_fraction = parse_text("2007-02-23T12:14:33.1Z");
+    let long_fraction = parse_text("2007-02-23T12:14:33.100Z");
+    let unknown_offset = parse_text("2007-02-23T12:14:33.1-00:00");
+
+    assert_ne!(short_fraction, long_fraction);
+    assert_ne!(short_fraction, unknown_offset);
+    assert_ne!(parse_text("2007-02-23"), parse_text("2007-02-23T00:00Z"));
+
+    let hash = |value: &IonTimestamp| IonHash::default_digest(&IonValue::DateTime(value.clone()));
+
+    assert_ne!(hash(&short_fraction), hash(&long_fraction));
+    assert_ne!(hash(&short_fraction), hash(&unknown_offset));
+    assert_eq!(
+        hash(&short_fraction),
+        hash(
+            &DateTime::parse_from_rfc3339("2007-02-23T12:14:33.1Z")
+                .unwrap()
+                .into()
+        )
+    );
+}
+
+#[test]
+fn timestamp_chrono_conversions() {
+    let datetime = DateTime::parse_from_rfc3339("2020-03-04T10:20:30.123+01:00").unwrap();
+
+    let timestamp = IonTimestamp::from(datetime);
+
+    assert_eq!(timestamp.precision(), TimestampPrecision::Second);
+    assert_eq!(timestamp.offset(), Some(60));
+    assert_eq!(
+        timestamp.fraction(),
+        Some(&BigDecimal::from_str("0.123").unwrap())
+    );
+    assert_eq!(timestamp.to_datetime(), datetime);
+    assert_eq!(timestamp.to_string(), "2020-03-04T10:20:30.123+01:00");
+
+    let utc: DateTime<Utc> = timestamp.into();
+    assert_eq!(utc.to_rfc3339(), "2020-03-04T09:20:30.123+00:00");
+
+    let truncated = parse_text("2007-02-23T12:14:33.123456789999-00:00").to_datetime();
+    assert_eq!(
+        truncated,
+        DateTime::parse_from_rfc3339("2007-02-23T12:14:33.123456789Z").unwrap()
+    );
+
+    let year = IonTimestamp::from_year(2007).unwrap();
+    assert_eq!(year.to_datetime().to_rfc3339(), "2007-01-01T00:00:00+00:00");
+}
+
+#[test]
+fn timestamp_constructors() {
+    let timestamp = IonTimestamp::from_ymd_hms(2007, 2, 23, 12, 14, 33, Some(-480))
+        .unwrap()
+        .with_fraction(BigDecimal::from_str("0.5").unwrap())
+        .unwrap();
+
+    assert_eq!(timestamp.to_string(), "2007-02-23T12:14:33.5-08:00");
+    assert_eq!(
+        timestamp
+            .clone()
+            .with_precision(TimestampPrecision::Minute)
+            .to_string(),
+        "2007-02-23T12:14-08:00"
+    );
+    assert_eq!(
+        timestamp
+            .with_precision(TimestampPrecision::Month)
+            .to_string(),
+        "2007-02T"
+    );
+    assert_eq!(
+        IonTimestamp::from_year_month(2007, 2).unwrap(),
+        parse_text("2007-02T")
+    );
+
+    assert!(IonTimestamp::from_ymd(2007, 2, 30).is_err());
+    assert!(IonTimestamp::from_ymd_hms(2007, 2, 23, 24, 0, 0, None).is_err());
+    assert!(IonTimestamp::from_ymd_hms(2007, 2, 23, 12, 0, 0, Some(24 * 60)).is_err());
+    assert_eq!(
+        IonTimestamp::from_year(2007)
+            .unwrap()
+            .with_fraction(BigDecimal::from(1)),
+        Err(IonParserError::DateSecondFractionOverflow)
+    );
+    assert_eq!(
+        IonTimestamp::from_year(2007)
+            .unwrap()
+            .with_fraction(BigDecimal::from_str("-0.1").unwrap()),
+        Err(IonParserError::DateNegativeSecondFraction)
+    );
+}
@@ -0,0 +1,222 @@
+use crate::hashmap;
+use crate::{IonTimestamp, IonValue, NullIonValue, SymbolToken};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+fn decimal(text: &str) -> IonValue {
+    IonValue::Decimal(BigDecimal::from_str(text).unwrap())
+}
+
+fn timestamp(text: &str) -> IonValue {
+    crate::IonTextParser::new(text.as_bytes())
+        .consume_value()
+        .unwrap()
+        .0
+}
+
+fn annotated(annotations: &[&str], value: IonValue) -> IonValue {
+    let annotations = annotations
+        .iter()
+        .map(|annotation| (*annotation).into())
+        .collect();
+
+    IonValue::Annotation(annotations, Box::new(value))
+}
+
+#[test]
+fn ion_eq_follows_the_data_model() {
+    let equivalent = [
+        (IonValue::Integer(5), IonValue::BigInteger(BigInt::from(5))),
+        (IonValue::Float(f64::NAN), IonValue::Float(-f64::NAN)),
+        (decimal("1.0"), decimal("10e-1")),
+        (
+            timestamp("2024-03-15T10:30:00.5Z"),
+            IonValue::DateTime(
+                IonTimestamp::from_ymd_hms(2024, 3, 15, 10, 30, 0, Some(0))
+                    .unwrap()
+                    .with_fraction(BigDecimal::from_str("0.5").unwrap())
+                    .unwrap(),
+            ),
+        ),
+        (
+            IonValue::Struct(hashmap!(
+                "a".to_string() => IonValue::Integer(1),
+                "b".to_string() => IonValue::Integer(2)
+            )),
+            IonValue::OrderedStruct(vec![
+                ("b".into(), IonValue::Integer(2)),
+                ("a".into(), IonValue::Integer(1)),
+            ]),
+        ),
+        (
+            IonValue::OrderedStruct(vec![
+                ("a".into(), IonValue::Integer(1)),
+                ("b".into(), IonValue::Integer(2)),
+                ("a".into(), IonValue::Integer(3)),
+            ]),
+            IonValue::OrderedStruct(vec![
+                ("a".into(), IonValue::Integer(3)),
+                ("a".into(), IonValue::Integer(1)),
+                ("b".into(), IonValue::Integer(2)),
+            ]),
+        ),
+        (
+            annotated(&["a"], annotated(&["b"], IonValue::Integer(1))),
+            annotated(&["a", "b"], IonValue::Integer(1)),
+        ),
+        (annotated(&[], IonValue::Integer(1)), IonValue::Integer(1)),
+        (
+            IonValue::Null(NullIonValue::Null),
+            IonValue::Null(NullIonValue::Annotation),
+        ),
+    ];
+
+    for (value, other) in equivalent {
+        assert!(value.ion_eq(&other), "{:?} {:?}", value, other);
+        assert!(other.ion_eq(&value), "{:?} {:?}", other, value);
+        assert_eq!(value, other);
+    }
+
+    let different = [
+        (IonValue::Float(0.0), IonValue::Float(-0.0)),
+        (IonValue::Float(f64::NAN), IonValue::Float(0.0)),
+        (decimal("1.0"), decimal("1.00")),
+        (decimal("0"), decimal("0.0")),
+        (IonValue::Integer(1), decimal("1")),
+        (IonValue::Integer(1), IonValue::Float(1.0)),
+        (
+            timestamp("2024-03-15T10:30Z"),
+            timestamp("2024-03-15T10:30-00:00"),
+        ),
+        (
+            timestamp("2024-03-15T10:30Z"),
+            timestamp("2024-03-15T11:30+01:00"),
+        ),
+        (
+            timestamp("2024-03-15T10:30:00Z"),
+            timestamp("2024-03-15T10:30Z"),
+        ),
+        (
+            timestamp("2024-03-15T10:30:00.0Z"),
+            timestamp("2024-03-15T10:30:00.00Z"),
+        ),
+        (timestamp("2024T"), timestamp("2024-01-01")),
+        (
+            IonValue::Symbol("a".into()),
+            IonValue::String("a".to_string()),
+        ),
+        (
+            IonValue::Symbol("a".into()),
+            IonValue::Symbol(SymbolToken::unknown()),
+        ),
+        (IonValue::Blob(vec![1]), IonValue::Clob(vec![1])),
+        (IonValue::List(vec![]), IonValue::SExpr(vec![])),
+        (
+            IonValue::Null(NullIonValue::Integer),
+            IonValue::Null(NullIonValue::Null),
+        ),
+        (IonValue::Null(NullIonValue::Integer), IonValue::Integer(0)),
+        (
+            IonValue::OrderedStruct(vec![
+                ("a".into(), IonValue::Integer(1)),
+                ("a".into(), IonValue::Integer(1)),
+            ]),
+            IonValue::OrderedStruct(vec![("a".into(), IonValue::Integer(1))]),
+        ),
+        (
+            annotated(&["a", "b"], IonValue::Integer(1)),
+            annotated(&["b", "a"], IonValue::Integer(1)),
+        ),
+        (
+            annotated(&["a"], IonValue::Integer(1)),
+            IonValue::Integer(1),
+        ),
+    ];
+
+    for (value, other) in different {
+        assert!(!value.ion_eq(&other), "{:?} {:?}", value, other);
+        assert!(!other.ion_eq(&value), "{:?} {:?}", other, value);
+        assert_ne!(value, other);
+    }
+}
+
+#[test]
+fn ion_values_have_a_total_order() {
+    let sorted = vec![
+        IonValue::Null(NullIonValue::Null),
+        IonValue::Null(NullIonValue::Bool),
+        IonValue::Bool(false),
+        IonValue::Bool(true),
+        IonValue::Null(NullIonValue::Integer),
+        IonValue::BigInteger(BigInt::from(i64::MIN) * 2),
+        IonValue::Integer(-1),
+        IonValue::BigInteger(BigInt::from(0)),
+        IonValue::Integer(i64::MAX),
+        IonValue::Float(f64::NEG_INFINITY),
+        IonValue::Float(-0.0),
+        IonValue::Float(0.0),
+        IonValue::Float(f64::INFINITY),
+        IonValue::Float(f64::NAN),
+        decimal("-1"),
+        decimal("0"),
+        decimal("0.0"),
+        decimal("1.0"),
+        decimal("1.00"),
+        IonValue::DateTime(IonTimestamp::from_year(2023).unwrap()),
+        timestamp("2024T"),
+        timestamp("2024-01-01"),
+        timestamp("2024-01-01T00:00Z"),
+        timestamp("2024-01-01T00:00:00Z"),
+        timestamp("2024-01-01T00:00:00.0Z"),
+        timestamp("2024-01-01T00:00:00.00Z"),
+        timestamp("2024-01-01T00:00:00.001Z"),
+        IonValue::Symbol("a".into()),
+        IonValue::Symbol("b".into()),
+        IonValue::Symbol(SymbolToken::unknown()),
+        IonValue::String("a".to_string()),
+        IonValue::Clob(vec![]),
+        IonValue::Blob(vec![0]),
+        IonValue::List(vec![IonValue::Integer(1)]),
+        IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(0)]),
+        IonValue::SExpr(vec![]),
+        IonValue::Null(NullIonValue::Struct),
+        IonValue::Struct(hashmap!("a".to_string() => IonValue::Integer(1))),
+        IonValue::OrderedStruct(vec![
+            ("a".into(), IonValue::Integer(1)),
+            ("a".into(), IonValue::Integer(1)),
+        ]),
+        IonValue::Struct(hashmap!("b".to_string() => IonValue::Integer(0))),
+    ];
+
+    let mut shuffled: Vec<IonValue> = sorted.iter().rev().cloned().collect();
+    shuffled.rotate_left(7);
+    shuffled.sort();
+
+    assert_eq!(shuffled, sorted);
+
+    for pair in sorted.windows(2) {
+        assert!(pair[0] < pair[1], "{:?} {:?}", pair[0], pair[1]);
+    }
+
+    // Annotations go after the value
+    assert!(annotated(&["z"], IonValue::Integer(1)) < IonValue::Integer(2));
+    assert!(IonValue::Integer(1) < annotated(&["a"], IonValue::Integer(1)));
+}
+
+#[test]
+fn ion_values_can_be_map_keys() {
+    let mut map = BTreeMap::new();
+
+    map.insert(IonValue::Integer(5), "five");
+    map.insert(IonValue::BigInteger(BigInt::from(5)), "big five");
+    map.insert(IonValue::Float(f64::NAN), "nan");
+    map.insert(decimal("1.0"), "one");
+    map.insert(decimal("1.00"), "another one");
+
+    assert_eq!(map.len(), 4);
+    assert_eq!(map[&IonValue::Integer(5)], "big five");
+    assert_eq!(map[&IonValue::Float(f64::NAN)], "nan");
+    assert_eq!(map[&decimal("1.00")], "another one");
+}
@@ -0,0 +1,106 @@
+use crate::hashmap;
+use crate::{IonEncoder, IonParser, IonValue, IonWriter};
+
+fn test_values() -> Vec<IonValue> {
+    (0..100)
+        .map(|index| {
+            IonValue::Struct(hashmap!(
+                format!("field_{}", index % 7) => IonValue::Symbol(format!("symbol_{}", index).into()),
+                "id".to_string() => IonValue::Annotation(
+                    vec![format!("annotation_{}", index % 3).into()],
+                    Box::new(IonValue::Integer(index)),
+                )
+            ))
+        })
+        .collect()
+}
+
+#[test]
+fn writer_output_is_parsed_back() {
+    let values = test_values();
+
+    let mut writer = IonWriter::new(Vec::new());
+
+    for value in &values {
+        writer.write(value).unwrap();
+    }
+
+    let bytes = writer.finish().unwrap();
+
+    assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), values);
+}
+
+#[test]
+fn writer_output_matches_encoder_for_a_single_flush() {
+    let value = IonValue::List(vec![
+        IonValue::Symbol("a".into()),
+        IonValue::Symbol("b".into()),
+    ]);
+
+    let mut writer = IonWriter::new(Vec::new());
+    writer.write(&value).unwrap();
+    let bytes = writer.finish().unwrap();
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value);
+
+    assert_eq!(bytes, encoder.encode());
+}
+
+#[test]
+fn writer_appends_symbol_tables_between_flushes() {
+    let values = test_values();
+
+    let mut writer = IonWriter::with_flush_threshold(Vec::new(), 0);
+
+    for value in &values {
+        writer.write(value).unwrap();
+    }
+
+    let bytes = writer.finish().unwrap();
+
+    assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), values);
+
+    // Every symbol is declared once, so each of them appears once in the stream.
+    let occurrences = bytes
+        .windows(9)
+        .filter(|window| window == b"symbol_42")
+        .count();
+    assert_eq!(occurrences, 1);
+}
+
+#[test]
+fn writer_writes_when_reaching_the_threshold() {
+    let mut writer = IonWriter::with_flush_threshold(Vec::new(), 10);
+
+    writer.write(&IonValue::Integer(1)).unwrap();
+    assert!(writer.get_ref().is_empty());
+
+    writer
+        .write(&IonValue::String("more than ten bytes".to_string()))
+        .unwrap();
+    let written = writer.get_ref().len();
+    assert!(written > 0);
+
+    writer.write(&IonValue::Integer(2)).unwrap();
+    assert_eq!(writer.get_ref().len(), written);
+
+    let bytes = writer.finish().unwrap();
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![
+            IonValue::Integer(1),
+            IonValue::String("more than ten bytes".to_string()),
+            IonValue::Integer(2),
+        ]
+    );
+}
+
+#[test]
+fn writer_without_values_writes_the_header() {
+    let bytes = IonWriter::new(Vec::new()).finish().unwrap();
+
+    assert_eq!(bytes, vec![0xE0, 0x01, 0x00, 0xEA]);
+    assert!(IonParser::new(&bytes[..]).consume_all().unwrap().is_empty());
+}
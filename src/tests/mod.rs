@@ -1,10 +1,35 @@
+// Some of the vendored tests use the legacy numeric constants, and spell
+// out the parts of the values they build
+#![allow(clippy::legacy_numeric_constants, clippy::identity_op)]
+
+#[cfg(feature = "tokio")]
+mod async_ion;
 mod bad_tests;
 mod binary_encoder;
 mod binary_parser;
+mod catalog;
+mod encoder_shared_tables;
 mod good_tests;
 mod ion_encoder;
+mod ion_encoder_1_1;
 mod ion_hash;
+mod ion_macro;
 mod ion_parser;
+mod ion_parser_1_1;
+mod ion_parser_options;
+mod ion_path;
+mod ion_reader;
+mod ion_slice_parser;
+mod ion_text_encoder;
+mod ion_text_parser;
+mod ion_timestamp;
+mod ion_value_cmp;
+mod ion_writer;
+mod ordered_struct;
+#[cfg(feature = "serde")]
+mod serde;
+mod symbol_table;
+mod symbol_token;
 
 #[macro_use]
 mod test_utils;
@@ -0,0 +1,146 @@
+use crate::hashmap;
+use crate::{IonEncoder, IonHash, IonParser, IonTextParser, IonValue};
+use std::collections::HashMap;
+
+fn duplicated_fields() -> IonValue {
+    IonValue::OrderedStruct(vec![
+        ("b".into(), IonValue::Integer(1)),
+        ("a".into(), IonValue::Integer(2)),
+        ("b".into(), IonValue::Integer(3)),
+    ])
+}
+
+fn encode(value: IonValue) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+    encoder.add(value);
+    encoder.encode()
+}
+
+#[test]
+fn ordered_struct_binary_round_trip() {
+    let bytes = encode(duplicated_fields());
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.with_ordered_structs();
+
+    let values = parser.consume_all().unwrap();
+
+    assert!(matches!(values[..], [IonValue::OrderedStruct(_)]));
+    assert_eq!(values, vec![duplicated_fields()]);
+}
+
+#[test]
+fn ordered_struct_binary_last_field_wins_by_default() {
+    let bytes = encode(duplicated_fields());
+    let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert!(matches!(values[..], [IonValue::Struct(_)]));
+    assert_eq!(
+        values,
+        vec![IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::Integer(2),
+            "b".to_string() => IonValue::Integer(3)
+        ))]
+    );
+}
+
+#[test]
+fn ordered_struct_text() {
+    let text = "{b: 1, a: 2, b: 3}";
+
+    let mut parser = IonTextParser::new(text.as_bytes());
+    parser.with_ordered_structs();
+
+    let values = parser.consume_all().unwrap();
+
+    assert!(matches!(values[..], [IonValue::OrderedStruct(_)]));
+    assert_eq!(values, vec![duplicated_fields()]);
+
+    assert_eq!(duplicated_fields().to_string(), text.replace(' ', ""));
+
+    let values = IonTextParser::new(text.as_bytes()).consume_all().unwrap();
+
+    assert!(matches!(values[..], [IonValue::Struct(_)]));
+    assert_eq!(
+        values,
+        vec![IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::Integer(2),
+            "b".to_string() => IonValue::Integer(3)
+        ))]
+    );
+}
+
+#[test]
+fn ordered_struct_nested_values_and_symbol_tables() {
+    let value = IonValue::List(vec![
+        IonValue::Symbol("symbol".into()),
+        IonValue::OrderedStruct(vec![(
+            "inner".into(),
+            IonValue::Annotation(vec!["note".into()], Box::new(duplicated_fields())),
+        )]),
+    ]);
+
+    let bytes = encode(value.clone());
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.with_ordered_structs();
+
+    let values = parser.consume_all().unwrap();
+
+    assert!(matches!(
+        &values[..],
+        [IonValue::List(list)] if matches!(&list[1], IonValue::OrderedStruct(fields)
+            if matches!(&fields[0].1, IonValue::Annotation(_, inner)
+                if matches!(**inner, IonValue::OrderedStruct(_))))
+    ));
+    assert_eq!(values, vec![value]);
+}
+
+#[test]
+fn ordered_struct_encoding_is_stable() {
+    assert_eq!(encode(duplicated_fields()), encode(duplicated_fields()));
+}
+
+#[test]
+fn ordered_struct_hash_matches_struct() {
+    let ordered = IonValue::OrderedStruct(vec![
+        ("b".into(), IonValue::Integer(1)),
+        ("a".into(), IonValue::String("text".to_string())),
+    ]);
+
+    let unordered = IonValue::Struct(hashmap!(
+        "a".to_string() => IonValue::String("text".to_string()),
+        "b".to_string() => IonValue::Integer(1)
+    ));
+
+    assert_eq!(
+        IonHash::default_digest(&ordered),
+        IonHash::default_digest(&unordered)
+    );
+
+    let reordered = IonValue::OrderedStruct(vec![
+        ("b".into(), IonValue::Integer(3)),
+        ("a".into(), IonValue::Integer(2)),
+        ("b".into(), IonValue::Integer(1)),
+    ]);
+
+    assert_eq!(
+        IonHash::default_digest(&duplicated_fields()),
+        IonHash::default_digest(&reordered)
+    );
+    assert_ne!(
+        IonHash::default_digest(&duplicated_fields()),
+        IonHash::default_digest(&IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::Integer(2),
+            "b".to_string() => IonValue::Integer(3)
+        )))
+    );
+}
+
+#[test]
+fn ordered_struct_into_hashmap() {
+    let map: HashMap<String, IonValue> = duplicated_fields().try_into().unwrap();
+
+    assert_eq!(map.get("b"), Some(&IonValue::Integer(3)));
+    assert_eq!(map.len(), 2);
+}
@@ -0,0 +1,251 @@
+use crate::hashmap;
+use crate::{
+    from_slice, to_vec, IonEncoder, IonParser, IonSerdeError, IonValue, SerdeDecimal, SerdeSymbol,
+    SerdeTimestamp,
+};
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Car {
+    #[serde(rename = "Model")]
+    model: String,
+    #[serde(rename = "Type")]
+    car_type: String,
+    #[serde(rename = "Color")]
+    color: String,
+    #[serde(rename = "VIN")]
+    vin: String,
+    #[serde(rename = "Make")]
+    make: String,
+    #[serde(rename = "Year")]
+    year: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Point(i32, i32),
+    Rectangle { width: u64, height: u64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Everything {
+    boolean: bool,
+    small: i8,
+    big: u64,
+    huge: i128,
+    float: f32,
+    text: String,
+    character: char,
+    optional: Option<String>,
+    missing: Option<String>,
+    list: Vec<u16>,
+    tuple: (String, bool),
+    map: HashMap<String, i64>,
+    #[serde(with = "serde_bytes")]
+    blob: Vec<u8>,
+    symbol: SerdeSymbol,
+    decimal: SerdeDecimal,
+    timestamp: SerdeTimestamp,
+    shapes: Vec<Shape>,
+    unit: (),
+}
+
+fn everything() -> Everything {
+    Everything {
+        boolean: true,
+        small: -3,
+        big: u64::MAX,
+        huge: i128::MIN,
+        float: 1.5,
+        text: "ഇഈഉഊഋഌഎഏഐഒഓഔകഖഗഘങച".to_string(),
+        character: 'ʳ',
+        optional: Some("present".to_string()),
+        missing: None,
+        list: vec![1, 2, 3, 500],
+        tuple: ("a".to_string(), false),
+        map: hashmap!("one".to_string() => 1, "two".to_string() => -2),
+        blob: vec![0, 1, 2, 255],
+        symbol: SerdeSymbol("CLK".to_string()),
+        decimal: SerdeDecimal(BigDecimal::from_str("329710294.574576239652439876523876").unwrap()),
+        timestamp: SerdeTimestamp(
+            DateTime::parse_from_rfc3339("1996-12-19T16:39:57.123-03:00").unwrap(),
+        ),
+        shapes: vec![
+            Shape::Empty,
+            Shape::Circle(2.5),
+            Shape::Point(-1, 7),
+            Shape::Rectangle {
+                width: 3,
+                height: 4,
+            },
+        ],
+        unit: (),
+    }
+}
+
+#[test]
+fn serde_deserialize_qldb_example() {
+    let ion_test = b"\xe0\x01\0\xea\xee\xa6\x81\x83\xde\xa2\x87\xbe\x9f\x83VIN\x84Type\x84Year\x84Make\x85Model\x85Color\xde\xb9\x8a\x8e\x911C4RJFAG0FC625797\x8b\x85Sedan\x8c\"\x07\xe3\x8d\x88Mercedes\x8e\x87CLK 350\x8f\x85White";
+
+    let car: Car = from_slice(&ion_test[..]).unwrap();
+
+    assert_eq!(
+        car,
+        Car {
+            model: "CLK 350".to_string(),
+            car_type: "Sedan".to_string(),
+            color: "White".to_string(),
+            vin: "1C4RJFAG0FC625797".to_string(),
+            make: "Mercedes".to_string(),
+            year: 2019,
+        }
+    );
+}
+
+#[test]
+fn serde_round_trip() {
+    let value = everything();
+
+    let bytes = to_vec(&value).unwrap();
+
+    let result: Everything = from_slice(&bytes).unwrap();
+
+    assert_eq!(value, result);
+}
+
+#[test]
+fn serde_serialize_is_readable_by_ion_parser() {
+    let bytes = to_vec(&everything()).unwrap();
+
+    let value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+
+    let value = match value {
+        IonValue::Struct(value) => value,
+        _ => panic!("Expected a struct"),
+    };
+
+    assert!(matches!(value["big"], IonValue::BigInteger(_)));
+    assert_eq!(value["big"], IonValue::BigInteger(u64::MAX.into()));
+    assert_eq!(value["optional"], IonValue::String("present".to_string()));
+    assert_eq!(value["missing"], IonValue::Null(crate::NullIonValue::Null));
+    assert_eq!(value["blob"], IonValue::Blob(vec![0, 1, 2, 255]));
+    assert_eq!(value["symbol"], IonValue::Symbol("CLK".into()));
+    assert_eq!(
+        value["decimal"],
+        IonValue::Decimal(BigDecimal::from_str("329710294.574576239652439876523876").unwrap())
+    );
+    assert_eq!(
+        value["timestamp"],
+        IonValue::DateTime(
+            DateTime::parse_from_rfc3339("1996-12-19T16:39:57.123-03:00")
+                .unwrap()
+                .into()
+        )
+    );
+    assert_eq!(
+        value["shapes"],
+        IonValue::List(vec![
+            IonValue::Symbol("Empty".into()),
+            IonValue::Annotation(vec!["Circle".into()], Box::new(IonValue::Float(2.5))),
+            IonValue::Annotation(
+                vec!["Point".into()],
+                Box::new(IonValue::List(vec![
+                    IonValue::Integer(-1),
+                    IonValue::Integer(7)
+                ]))
+            ),
+            IonValue::Annotation(
+                vec!["Rectangle".into()],
+                Box::new(IonValue::Struct(hashmap!(
+                    "width".to_string() => IonValue::Integer(3),
+                    "height".to_string() => IonValue::Integer(4)
+                )))
+            ),
+        ])
+    );
+}
+
+#[test]
+fn serde_deserialize_ion_encoder_output() {
+    let value = IonValue::Struct(hashmap!(
+        "numbers".to_string() => IonValue::SExpr(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+        "name".to_string() => IonValue::Annotation(
+            vec!["ignored".into()],
+            Box::new(IonValue::Symbol("Sedan".into()))
+        ),
+        "price".to_string() => IonValue::Decimal(BigDecimal::from_str("12.50").unwrap()),
+        "extra".to_string() => IonValue::List(vec![IonValue::Bool(true)])
+    ));
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Partial {
+        numbers: Vec<i32>,
+        name: String,
+        price: f64,
+    }
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value);
+    let bytes = encoder.encode();
+
+    let result: Partial = from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        result,
+        Partial {
+            numbers: vec![1, 2],
+            name: "Sedan".to_string(),
+            price: 12.5,
+        }
+    );
+}
+
+#[test]
+fn serde_deserialize_vec_from_blob() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Blob(vec![4, 5, 6]));
+    let bytes = encoder.encode();
+
+    let result: Vec<u8> = from_slice(&bytes).unwrap();
+
+    assert_eq!(result, vec![4, 5, 6]);
+}
+
+#[test]
+fn serde_error_non_string_key() {
+    let value: HashMap<u32, bool> = hashmap!(1 => true);
+
+    assert!(matches!(
+        to_vec(&value),
+        Err(IonSerdeError::KeyMustBeAString)
+    ));
+}
+
+#[test]
+fn serde_error_trailing_values() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::Integer(2));
+    let bytes = encoder.encode();
+
+    assert!(matches!(
+        from_slice::<i64>(&bytes),
+        Err(IonSerdeError::TrailingValues)
+    ));
+}
+
+#[test]
+fn serde_error_trailing_elements() {
+    let bytes = to_vec(&vec![1, 2, 3]).unwrap();
+
+    assert!(matches!(
+        from_slice::<(i32, i32)>(&bytes),
+        Err(IonSerdeError::TrailingElements)
+    ));
+}
@@ -0,0 +1,67 @@
+use crate::symbol_table::{Import, Symbol, SymbolContext};
+
+fn symbols(texts: &[&str]) -> Vec<Symbol> {
+    texts
+        .iter()
+        .map(|text| Symbol::Symbol(text.to_string()))
+        .collect()
+}
+
+#[test]
+fn insert_symbol_reuses_sids() {
+    let mut context = SymbolContext::new();
+
+    assert_eq!(context.insert_symbol("name"), 4);
+    assert_eq!(context.insert_symbol("$0"), 10);
+    assert_eq!(context.insert_symbol("brand"), 11);
+    assert_eq!(context.insert_symbol("$0"), 10);
+    assert_eq!(context.insert_symbol("brand"), 11);
+}
+
+#[test]
+fn lookup_follows_new_tables() {
+    let mut context = SymbolContext::new();
+
+    context
+        .add_shared_table("cars".to_string(), 1, &symbols(&["brand", "model"]))
+        .unwrap();
+
+    context.insert_symbol("old");
+
+    let imports = [
+        Import {
+            name: "cars".to_string(),
+            version: Some(1),
+            max_len: Some(3),
+        },
+        Import {
+            name: "trucks".to_string(),
+            version: Some(1),
+            max_len: Some(2),
+        },
+    ];
+
+    context
+        .set_new_table(&imports, &symbols(&["model", "year"]))
+        .unwrap();
+
+    // $10 brand, $11 model, $12 to $14 dummies, $15 model, $16 year
+    assert_eq!(context.insert_symbol("brand"), 10);
+    assert_eq!(context.insert_symbol("model"), 11);
+    assert_eq!(context.insert_symbol("year"), 16);
+    assert_eq!(context.insert_symbol("old"), 17);
+
+    context.set_new_table_from_current(symbols(&["year", "color"]));
+
+    assert_eq!(context.insert_symbol("year"), 16);
+    assert_eq!(context.insert_symbol("color"), 19);
+
+    context.set_imports(&[]).unwrap();
+
+    // Local symbols keep their order after the system ones, repeated
+    // "year" included
+    assert_eq!(context.insert_symbol("model"), 10);
+    assert_eq!(context.insert_symbol("year"), 11);
+    assert_eq!(context.insert_symbol("color"), 14);
+    assert_eq!(context.insert_symbol("brand"), 15);
+}
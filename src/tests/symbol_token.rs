@@ -0,0 +1,343 @@
+use crate::{
+    ImportLocation, IonEncoder, IonHash, IonParser, IonParserError, IonReader, IonTextEncoder,
+    IonTextParser, IonValue, IonVersion, IonWriter, SymbolToken,
+};
+use sha2::{Digest, Sha256};
+
+fn imported(name: &str, sid: usize) -> SymbolToken {
+    SymbolToken::from_import_location(ImportLocation::new(name, sid))
+}
+
+fn encode(values: Vec<IonValue>) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+
+    for value in values {
+        encoder.add(value);
+    }
+
+    encoder.encode()
+}
+
+#[test]
+fn symbol_token_equality() {
+    assert_eq!(
+        SymbolToken::from("sedan"),
+        SymbolToken::new("sedan").with_sid(10)
+    );
+    assert_eq!(SymbolToken::from("sedan"), "sedan");
+    assert_ne!(SymbolToken::from("$0"), SymbolToken::unknown());
+    assert_eq!(SymbolToken::unknown(), SymbolToken::unknown().with_sid(3));
+    assert_eq!(imported("cars", 1), imported("cars", 1).with_sid(10));
+    assert_ne!(imported("cars", 1), imported("cars", 2));
+    assert_ne!(imported("cars", 1), SymbolToken::unknown());
+}
+
+#[test]
+fn symbol_zero_is_unknown() {
+    let bytes = [
+        0xE0, 0x01, 0x00, 0xEA, 0x70, 0x71, 0x00, 0xE4, 0x81, 0x80, 0x21, 0x01,
+    ];
+
+    let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Symbol(SymbolToken::unknown()),
+            IonValue::Symbol(SymbolToken::unknown()),
+            IonValue::Annotation(vec![SymbolToken::unknown()], Box::new(IonValue::Integer(1))),
+        ]
+    );
+
+    let values = IonTextParser::new("$0 '$0' $0::1".as_bytes())
+        .consume_all()
+        .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Symbol(SymbolToken::unknown()),
+            IonValue::Symbol("$0".into()),
+            IonValue::Annotation(vec![SymbolToken::unknown()], Box::new(IonValue::Integer(1))),
+        ]
+    );
+
+    let bytes = encode(values.clone());
+
+    assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), values);
+
+    let mut encoder = IonTextEncoder::new();
+
+    for value in values {
+        encoder.add(value);
+    }
+
+    assert_eq!(encoder.encode(), "$0 '$0' $0::1");
+}
+
+#[test]
+fn symbols_from_missing_shared_table() {
+    let values = vec![
+        IonValue::Symbol(imported("cars", 2)),
+        IonValue::Annotation(
+            vec![imported("cars", 1), "local".into()],
+            Box::new(IonValue::Symbol("sedan".into())),
+        ),
+    ];
+
+    let bytes = encode(values.clone());
+
+    let parsed = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert_eq!(parsed, values);
+
+    match &parsed[0] {
+        IonValue::Symbol(symbol) => {
+            assert_eq!(symbol.text(), None);
+            assert_eq!(symbol.sid(), Some(11));
+            assert_eq!(
+                symbol.import_location(),
+                Some(&ImportLocation::new("cars", 2))
+            );
+        }
+        value => panic!("Expected a symbol, got {:?}", value),
+    }
+
+    let bytes_again = encode(parsed);
+
+    assert_eq!(
+        IonParser::new(&bytes_again[..]).consume_all().unwrap(),
+        values
+    );
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    parser
+        .with_shared_table(
+            "cars".to_string(),
+            1,
+            &["brand".to_string(), "model".to_string()],
+        )
+        .unwrap();
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol("model".into()),
+            IonValue::Annotation(
+                vec!["brand".into(), "local".into()],
+                Box::new(IonValue::Symbol("sedan".into())),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn symbols_from_missing_shared_table_in_text() {
+    let text = r#"
+        $ion_symbol_table::{ imports: [{ name: "cars", version: 1, max_id: 2 }], symbols: ["a"] }
+        $10 $11 $12
+    "#;
+
+    let values = IonTextParser::new(text.as_bytes()).consume_all().unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Symbol(imported("cars", 1)),
+            IonValue::Symbol(imported("cars", 2)),
+            IonValue::Symbol("a".into()),
+        ]
+    );
+
+    assert_eq!(
+        IonTextParser::new("$13".as_bytes()).consume_all(),
+        Err(IonParserError::SymbolIdNotDefined)
+    );
+}
+
+#[test]
+fn symbols_from_missing_shared_table_keep_their_import() {
+    let text = r#"
+        $ion_symbol_table::{ imports: [{ name: "cars", version: 2, max_id: 5 }], symbols: ["a"] }
+        $10 $12 $15
+    "#;
+
+    let values = IonTextParser::new(text.as_bytes()).consume_all().unwrap();
+    let bytes = encode(values.clone());
+    let parsed = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert_eq!(parsed, values);
+
+    for value in &parsed[..2] {
+        let location = match value {
+            IonValue::Symbol(symbol) => symbol.import_location().unwrap(),
+            value => panic!("Expected a symbol, got {:?}", value),
+        };
+
+        assert_eq!(location.import_version(), Some(2));
+        assert_eq!(location.import_max_id(), Some(5));
+    }
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    for (version, symbols) in [(1, ["v1"; 3].as_slice()), (2, ["v2"; 5].as_slice())] {
+        let symbols: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+
+        parser
+            .with_shared_table("cars".to_string(), version, &symbols)
+            .unwrap();
+    }
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol("v2".into()),
+            IonValue::Symbol("v2".into()),
+            IonValue::Symbol("a".into()),
+        ]
+    );
+}
+
+#[test]
+fn field_names_from_missing_shared_table() {
+    let value = IonValue::OrderedStruct(vec![
+        (imported("cars", 1), IonValue::Integer(1)),
+        ("local".into(), IonValue::Integer(2)),
+    ]);
+
+    let bytes = encode(vec![value.clone()]);
+
+    // Without ordered structs the parser also returns them, as the name
+    // without text can't be a key of a Struct
+    let parsed = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert_eq!(parsed, vec![value.clone()]);
+
+    match &parsed[0] {
+        IonValue::OrderedStruct(fields) => {
+            assert_eq!(fields[0].0.text(), None);
+            assert_eq!(fields[0].0.sid(), Some(10));
+            assert_eq!(
+                fields[0].0.import_location(),
+                Some(&ImportLocation::new("cars", 1))
+            );
+        }
+        value => panic!("Expected an ordered struct, got {:?}", value),
+    }
+
+    let bytes_again = encode(parsed.clone());
+
+    assert_eq!(
+        IonParser::new(&bytes_again[..]).consume_all().unwrap(),
+        vec![value.clone()]
+    );
+
+    assert_eq!(
+        IonHash::digest::<Sha256>(&parsed[0]),
+        IonHash::digest::<Sha256>(&IonValue::OrderedStruct(vec![
+            (SymbolToken::unknown(), IonValue::Integer(1)),
+            ("local".into(), IonValue::Integer(2)),
+        ]))
+    );
+
+    let mut reader = IonReader::new(&bytes[..]);
+    reader.next().unwrap();
+    reader.step_in().unwrap();
+    reader.next().unwrap();
+
+    assert_eq!(reader.field_name(), None);
+    assert_eq!(reader.field_name_token(), Some(&imported("cars", 1)));
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_target_version(IonVersion::Ion1_1);
+    encoder.add(value.clone());
+    let bytes_1_1 = encoder.encode();
+
+    assert_eq!(
+        IonParser::new(&bytes_1_1[..]).consume_all().unwrap(),
+        vec![value.clone()]
+    );
+
+    assert_eq!(parsed[0].to_string(), "{$0:1,local:2}");
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    parser
+        .with_shared_table("cars".to_string(), 1, &["brand".to_string()])
+        .unwrap();
+
+    let values = parser.consume_all().unwrap();
+
+    assert!(matches!(values[0], IonValue::Struct(_)));
+    assert_eq!(
+        values,
+        vec![IonValue::OrderedStruct(vec![
+            ("brand".into(), IonValue::Integer(1)),
+            ("local".into(), IonValue::Integer(2)),
+        ])]
+    );
+
+    let text = r#"
+        $ion_symbol_table::{ imports: [{ name: "cars", version: 1, max_id: 1 }] }
+        { $10: 1, local: 2 }
+    "#;
+
+    assert_eq!(
+        IonTextParser::new(text.as_bytes()).consume_all().unwrap(),
+        vec![value]
+    );
+}
+
+#[test]
+fn unknown_symbols_hash() {
+    let expected = Sha256::digest([0x0B, 0x71, 0x0E]).to_vec();
+
+    assert_eq!(
+        IonHash::digest::<Sha256>(&IonValue::Symbol(SymbolToken::unknown())),
+        expected
+    );
+    assert_eq!(
+        IonHash::digest::<Sha256>(&IonValue::Symbol(imported("cars", 2))),
+        expected
+    );
+    assert_ne!(
+        IonHash::digest::<Sha256>(&IonValue::Symbol("$0".into())),
+        expected
+    );
+
+    let annotated = |annotation: SymbolToken| {
+        IonHash::digest::<Sha256>(&IonValue::Annotation(
+            vec![annotation],
+            Box::new(IonValue::Integer(1)),
+        ))
+    };
+
+    assert_eq!(
+        annotated(SymbolToken::unknown()),
+        annotated(imported("cars", 1))
+    );
+    assert_ne!(annotated(SymbolToken::unknown()), annotated("$0".into()));
+}
+
+#[test]
+fn writer_imports_shared_tables_when_needed() {
+    let values = vec![
+        IonValue::Symbol("first".into()),
+        IonValue::Symbol(imported("cars", 1)),
+        IonValue::Symbol("second".into()),
+        IonValue::Symbol(imported("cars", 3)),
+        IonValue::Symbol(imported("trucks", 1)),
+        IonValue::Symbol("first".into()),
+    ];
+
+    let mut writer = IonWriter::with_flush_threshold(Vec::new(), 0);
+
+    for value in &values {
+        writer.write(value).unwrap();
+    }
+
+    let bytes = writer.finish().unwrap();
+
+    assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), values);
+}
@@ -25,3 +25,41 @@ macro_rules! hashmap(
         }
      };
 );
+
+/// Returns all the Ion text files (`.ion`) inside the given section of the
+/// test-suite, including the ones in nested folders.
+pub fn list_text_testsuite_files(section: &str) -> Vec<std::path::PathBuf> {
+    list_testsuite_files(section, "ion")
+}
+
+/// Returns all the Ion binary files (`.10n`) inside the given section of the
+/// test-suite, including the ones in nested folders.
+pub fn list_binary_testsuite_files(section: &str) -> Vec<std::path::PathBuf> {
+    list_testsuite_files(section, "10n")
+}
+
+fn list_testsuite_files(section: &str, file_extension: &str) -> Vec<std::path::PathBuf> {
+    let mut pending = vec![std::path::Path::new("src/tests/test-suite/iontestdata/").join(section)];
+    let mut files = Vec::new();
+
+    while let Some(folder) = pending.pop() {
+        let entries = std::fs::read_dir(&folder)
+            .unwrap_or_else(|error| panic!("Failed to open folder {:?}: {:?}", folder, error));
+
+        for entry in entries {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                pending.push(path);
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == file_extension)
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
@@ -0,0 +1,236 @@
+use crate::ion_timestamp::{IonTimestamp, TimestampPrecision};
+use crate::{NullIonValue, SymbolToken};
+use bigdecimal::BigDecimal;
+use num_bigint::Sign;
+use std::fmt::Write;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn encode_text_null(buffer: &mut String, value: &NullIonValue) {
+    let text = match value {
+        NullIonValue::Null | NullIonValue::Annotation => "null",
+        NullIonValue::Bool => "null.bool",
+        NullIonValue::Integer => "null.int",
+        NullIonValue::Float => "null.float",
+        NullIonValue::Decimal => "null.decimal",
+        NullIonValue::DateTime => "null.timestamp",
+        NullIonValue::String => "null.string",
+        NullIonValue::Symbol => "null.symbol",
+        NullIonValue::Clob => "null.clob",
+        NullIonValue::Blob => "null.blob",
+        NullIonValue::List => "null.list",
+        NullIonValue::SExpr => "null.sexp",
+        NullIonValue::Struct => "null.struct",
+    };
+
+    buffer.push_str(text);
+}
+
+// Floats always need an exponent in Ion text, otherwise they would be read as
+// decimals. The `{:e}` format gives the shortest text that reads back the same f64.
+pub fn encode_text_float(buffer: &mut String, value: f64) {
+    if value.is_nan() {
+        buffer.push_str("nan");
+    } else if value.is_infinite() {
+        buffer.push_str(if value > 0.0 { "+inf" } else { "-inf" });
+    } else {
+        let _ = write!(buffer, "{:e}", value);
+    }
+}
+
+// Decimals with a positive scale are written with a decimal point (`1.50`),
+// the rest use the `d` exponent (`15d1`) so no precision is lost.
+pub fn encode_text_decimal(buffer: &mut String, value: &BigDecimal) {
+    let (coefficient, scale) = value.as_bigint_and_exponent();
+
+    if coefficient.sign() == Sign::Minus {
+        buffer.push('-');
+    }
+
+    let digits = coefficient.magnitude().to_string();
+
+    if scale < 0 {
+        let _ = write!(buffer, "{}d{}", digits, -scale);
+        return;
+    }
+
+    let scale = scale as usize;
+
+    if scale == 0 {
+        buffer.push_str(&digits);
+        buffer.push('.');
+        return;
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+    buffer.push_str(integer);
+    buffer.push('.');
+    buffer.push_str(fraction);
+}
+
+pub fn encode_text_datetime(buffer: &mut String, value: &IonTimestamp) {
+    let precision = value.precision();
+
+    let _ = write!(buffer, "{:04}", value.year());
+
+    if precision == TimestampPrecision::Year {
+        buffer.push('T');
+        return;
+    }
+
+    let _ = write!(buffer, "-{:02}", value.month());
+
+    if precision == TimestampPrecision::Month {
+        buffer.push('T');
+        return;
+    }
+
+    let _ = write!(buffer, "-{:02}", value.day());
+
+    if precision == TimestampPrecision::Day {
+        return;
+    }
+
+    let _ = write!(buffer, "T{:02}:{:02}", value.hour(), value.minute());
+
+    if precision == TimestampPrecision::Second {
+        let _ = write!(buffer, ":{:02}", value.second());
+
+        // Fractions with a zero or positive exponent have no digits to
+        // write after the dot.
+        if let Some(fraction) = value.fraction() {
+            let (coefficient, scale) = fraction.as_bigint_and_exponent();
+
+            if scale > 0 {
+                let digits = coefficient.to_string();
+                let _ = write!(buffer, ".{:0>width$}", digits, width = scale as usize);
+            }
+        }
+    }
+
+    match value.offset() {
+        None => buffer.push_str("-00:00"),
+        Some(0) => buffer.push('Z'),
+        Some(offset_minutes) => {
+            let sign = if offset_minutes < 0 { '-' } else { '+' };
+            let offset_minutes = offset_minutes.abs();
+            let _ = write!(
+                buffer,
+                "{}{:02}:{:02}",
+                sign,
+                offset_minutes / 60,
+                offset_minutes % 60
+            );
+        }
+    }
+}
+
+pub fn encode_text_string(buffer: &mut String, value: &str) {
+    buffer.push('"');
+    encode_escaped_text(buffer, value, '"');
+    buffer.push('"');
+}
+
+// Symbols that look like identifiers are written as they are, the rest need
+// quotes. Keywords and `$<number>` need quotes too, as otherwise they would
+// be read as other values or as symbol ids.
+pub fn encode_text_symbol(buffer: &mut String, value: &str) {
+    if is_identifier_symbol(value) {
+        buffer.push_str(value);
+    } else {
+        buffer.push('\'');
+        encode_escaped_text(buffer, value, '\'');
+        buffer.push('\'');
+    }
+}
+
+// Text doesn't keep the import location of symbols without text, as no
+// symbol table is written, so all of them are written as `$0`.
+pub fn encode_text_symbol_token(buffer: &mut String, value: &SymbolToken) {
+    match value.text() {
+        Some(text) => encode_text_symbol(buffer, text),
+        None => buffer.push_str("$0"),
+    }
+}
+
+pub fn encode_text_blob(buffer: &mut String, value: &[u8]) {
+    buffer.push_str("{{");
+    encode_base64(buffer, value);
+    buffer.push_str("}}");
+}
+
+pub fn encode_text_clob(buffer: &mut String, value: &[u8]) {
+    buffer.push_str("{{\"");
+
+    for byte in value {
+        match byte {
+            b'"' => buffer.push_str("\\\""),
+            b'\\' => buffer.push_str("\\\\"),
+            0x20..=0x7E => buffer.push(*byte as char),
+            _ => {
+                let _ = write!(buffer, "\\x{:02x}", byte);
+            }
+        }
+    }
+
+    buffer.push_str("\"}}");
+}
+
+fn encode_escaped_text(buffer: &mut String, value: &str, quote: char) {
+    for character in value.chars() {
+        match character {
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            '\0' => buffer.push_str("\\0"),
+            character if character == quote => {
+                buffer.push('\\');
+                buffer.push(character);
+            }
+            character if character < ' ' || character == '\u{7f}' => {
+                let _ = write!(buffer, "\\x{:02x}", character as u32);
+            }
+            character => buffer.push(character),
+        }
+    }
+}
+
+fn is_identifier_symbol(value: &str) -> bool {
+    let bytes = value.as_bytes();
+
+    let is_identifier = match bytes.first() {
+        Some(first) if first.is_ascii_alphabetic() || *first == b'_' || *first == b'$' => bytes
+            .iter()
+            .all(|byte| byte.is_ascii_alphanumeric() || *byte == b'_' || *byte == b'$'),
+        _ => false,
+    };
+
+    let is_keyword = matches!(value, "null" | "true" | "false" | "nan");
+
+    let is_symbol_id =
+        bytes.len() > 1 && bytes[0] == b'$' && bytes[1..].iter().all(|byte| byte.is_ascii_digit());
+
+    is_identifier && !is_keyword && !is_symbol_id
+}
+
+pub fn encode_base64(buffer: &mut String, value: &[u8]) {
+    for chunk in value.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+
+        let group = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - index * 6)) & 0b11_1111;
+                buffer.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                buffer.push('=');
+            }
+        }
+    }
+}
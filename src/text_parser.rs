@@ -0,0 +1,528 @@
+use crate::binary_parser_types::ParsingError;
+use crate::ion_parser_types::IonParserError;
+use crate::text_parser_types::TextParsingError;
+use std::io::Read;
+
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// What kind of content a quoted string is going to be converted into.
+/// Clobs only accept ASCII characters and byte escapes.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub(crate) enum StringMode {
+    Text,
+    Clob,
+}
+
+/// Low level reader for the Ion text format. It knows how to read each one of
+/// the text tokens (strings, symbols, numbers, etc) but it doesn't know how they
+/// are combined together, that is done by the IonTextParser.
+#[derive(Debug)]
+pub struct IonTextLexer<T: Read> {
+    reader: T,
+    buffer: Vec<u8>,
+    index: usize,
+    position: usize,
+    is_reader_finished: bool,
+}
+
+impl<T: Read> IonTextLexer<T> {
+    #[inline]
+    pub fn new(reader: T) -> IonTextLexer<T> {
+        IonTextLexer {
+            reader,
+            buffer: Vec::with_capacity(READ_CHUNK_SIZE),
+            index: 0,
+            position: 0,
+            is_reader_finished: false,
+        }
+    }
+
+    /// Amount of bytes consumed from the underlying reader since the
+    /// lexer was created.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    pub fn peek(&mut self) -> Result<Option<u8>, IonParserError> {
+        self.peek_at(0)
+    }
+
+    /// Returns the byte `offset` positions after the current one without consuming it.
+    #[inline]
+    pub fn peek_at(&mut self, offset: usize) -> Result<Option<u8>, IonParserError> {
+        while self.index + offset >= self.buffer.len() {
+            if !self.fill_buffer()? {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(self.buffer[self.index + offset]))
+    }
+
+    #[inline]
+    pub fn starts_with(&mut self, expected: &[u8]) -> Result<bool, IonParserError> {
+        for (offset, expected_byte) in expected.iter().enumerate() {
+            if self.peek_at(offset)? != Some(*expected_byte) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    #[inline]
+    pub fn next_byte(&mut self) -> Result<Option<u8>, IonParserError> {
+        let byte = self.peek()?;
+
+        if byte.is_some() {
+            self.index += 1;
+            self.position += 1;
+        }
+
+        Ok(byte)
+    }
+
+    #[inline]
+    pub fn skip(&mut self, len: usize) -> Result<(), IonParserError> {
+        for _ in 0..len {
+            self.consume_byte()?;
+        }
+
+        Ok(())
+    }
+
+    /// Like next_byte but considering the end of the stream an error.
+    #[inline]
+    pub fn consume_byte(&mut self) -> Result<u8, IonParserError> {
+        self.next_byte()?.ok_or(IonParserError::TextError(
+            TextParsingError::UnexpectedEndOfStream,
+        ))
+    }
+
+    #[inline]
+    pub fn expect(&mut self, expected: u8) -> Result<(), IonParserError> {
+        match self.consume_byte()? {
+            byte if byte == expected => Ok(()),
+            byte => Err(unexpected_character(byte)),
+        }
+    }
+
+    /// Skips white spaces and, if `skip_comments` is true, line and block comments.
+    /// Returns true if anything was skipped.
+    pub fn skip_whitespace(&mut self, skip_comments: bool) -> Result<bool, IonParserError> {
+        let mut skipped = false;
+
+        loop {
+            match self.peek()? {
+                Some(byte) if is_whitespace(byte) => {
+                    self.skip(1)?;
+                }
+                Some(b'/') if skip_comments && self.peek_at(1)? == Some(b'/') => {
+                    self.skip(2)?;
+
+                    while let Some(byte) = self.next_byte()? {
+                        if byte == b'\n' || byte == b'\r' {
+                            break;
+                        }
+                    }
+                }
+                Some(b'/') if skip_comments && self.peek_at(1)? == Some(b'*') => {
+                    self.skip(2)?;
+
+                    loop {
+                        match self.next_byte()? {
+                            Some(b'*') if self.peek()? == Some(b'/') => {
+                                self.skip(1)?;
+                                break;
+                            }
+                            Some(_) => {}
+                            None => {
+                                return Err(IonParserError::TextError(
+                                    TextParsingError::UnterminatedComment,
+                                ))
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(skipped),
+            }
+
+            skipped = true;
+        }
+    }
+
+    /// Consumes an identifier symbol (or keyword). The first byte needs to
+    /// be a valid identifier start.
+    pub fn consume_identifier(&mut self) -> Result<String, IonParserError> {
+        let mut identifier = String::new();
+
+        while let Some(byte) = self.peek()? {
+            if !is_identifier_part(byte) {
+                break;
+            }
+
+            identifier.push(byte as char);
+            self.skip(1)?;
+        }
+
+        Ok(identifier)
+    }
+
+    /// Consumes an s-expression operator symbol like `+`, `<=` or `!==`.
+    pub fn consume_operator(&mut self) -> Result<String, IonParserError> {
+        let mut operator = String::new();
+
+        while let Some(byte) = self.peek()? {
+            let is_comment_start =
+                byte == b'/' && matches!(self.peek_at(1)?, Some(b'/') | Some(b'*'));
+
+            if !is_operator_part(byte) || (is_comment_start && !operator.is_empty()) {
+                break;
+            }
+
+            operator.push(byte as char);
+            self.skip(1)?;
+        }
+
+        Ok(operator)
+    }
+
+    /// Consumes all the characters that can be part of a number or a timestamp.
+    /// The validation of the token is done by the IonTextParser.
+    pub fn consume_numeric_token(&mut self) -> Result<String, IonParserError> {
+        let mut token = String::new();
+
+        while let Some(byte) = self.peek()? {
+            if !is_numeric_part(byte) {
+                break;
+            }
+
+            token.push(byte as char);
+            self.skip(1)?;
+        }
+
+        match self.peek()? {
+            None => Ok(token),
+            Some(byte) if is_value_stop_character(byte) => Ok(token),
+            Some(b'/') if matches!(self.peek_at(1)?, Some(b'/') | Some(b'*')) => Ok(token),
+            Some(byte) => Err(unexpected_character(byte)),
+        }
+    }
+
+    /// Consumes a short string (`"text"`) or a quoted symbol (`'text'`),
+    /// including the delimiters, returning its unescaped bytes.
+    pub fn consume_quoted(
+        &mut self,
+        delimiter: u8,
+        mode: StringMode,
+    ) -> Result<Vec<u8>, IonParserError> {
+        self.expect(delimiter)?;
+
+        let mut buffer = Vec::new();
+
+        loop {
+            match self.consume_byte()? {
+                byte if byte == delimiter => return Ok(buffer),
+                b'\\' => self.consume_escape(&mut buffer, mode)?,
+                byte if byte == b'\n' || byte == b'\r' => {
+                    return Err(IonParserError::TextError(
+                        TextParsingError::InvalidStringCharacter(byte as char),
+                    ))
+                }
+                byte => push_raw_byte(&mut buffer, byte, mode)?,
+            }
+        }
+    }
+
+    /// Consumes one long string segment (`'''text'''`), including the delimiters,
+    /// returning its unescaped bytes. Concatenation of adjacent segments is done
+    /// by the IonTextParser as comments can be in between.
+    pub fn consume_long_string(&mut self, mode: StringMode) -> Result<Vec<u8>, IonParserError> {
+        self.expect(b'\'')?;
+        self.expect(b'\'')?;
+        self.expect(b'\'')?;
+
+        let mut buffer = Vec::new();
+
+        loop {
+            match self.consume_byte()? {
+                b'\'' if self.starts_with(b"''")? => {
+                    self.skip(2)?;
+                    return Ok(buffer);
+                }
+                b'\\' => self.consume_escape(&mut buffer, mode)?,
+                b'\r' => {
+                    if self.peek()? == Some(b'\n') {
+                        self.skip(1)?;
+                    }
+                    buffer.push(b'\n');
+                }
+                b'\n' => buffer.push(b'\n'),
+                byte => push_raw_byte(&mut buffer, byte, mode)?,
+            }
+        }
+    }
+
+    /// Consumes the base64 content of a blob, stopping before the closing `}}`.
+    pub fn consume_base64(&mut self) -> Result<Vec<u8>, IonParserError> {
+        let mut content = Vec::new();
+
+        loop {
+            match self.peek()? {
+                Some(byte) if is_whitespace(byte) => self.skip(1)?,
+                Some(b'}') => break,
+                Some(byte) if byte.is_ascii_alphanumeric() || byte == b'+' || byte == b'/' => {
+                    content.push(byte);
+                    self.skip(1)?;
+                }
+                Some(b'=') => {
+                    content.push(b'=');
+                    self.skip(1)?;
+                }
+                Some(byte) => return Err(unexpected_character(byte)),
+                None => {
+                    return Err(IonParserError::TextError(
+                        TextParsingError::UnexpectedEndOfStream,
+                    ))
+                }
+            }
+        }
+
+        decode_base64(&content)
+    }
+
+    fn consume_escape(
+        &mut self,
+        buffer: &mut Vec<u8>,
+        mode: StringMode,
+    ) -> Result<(), IonParserError> {
+        let escaped = match self.consume_byte()? {
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b't' => b'\t',
+            b'n' => b'\n',
+            b'f' => 0x0C,
+            b'r' => b'\r',
+            b'v' => 0x0B,
+            b'?' => b'?',
+            b'0' => 0x00,
+            b'\'' => b'\'',
+            b'"' => b'"',
+            b'/' => b'/',
+            b'\\' => b'\\',
+            // Escaped new lines are just line continuations
+            b'\n' => return Ok(()),
+            b'\r' => {
+                if self.peek()? == Some(b'\n') {
+                    self.skip(1)?;
+                }
+                return Ok(());
+            }
+            b'x' => {
+                let value = self.consume_hex_digits(2)?;
+
+                match mode {
+                    StringMode::Clob => buffer.push(value as u8),
+                    StringMode::Text => push_code_point(buffer, value)?,
+                }
+
+                return Ok(());
+            }
+            b'u' if mode == StringMode::Text => {
+                let value = self.consume_hex_digits(4)?;
+                let value = self.combine_surrogates(value)?;
+                return push_code_point(buffer, value);
+            }
+            b'U' if mode == StringMode::Text => {
+                let value = self.consume_hex_digits(8)?;
+                return push_code_point(buffer, value);
+            }
+            _ => return Err(invalid_escape()),
+        };
+
+        buffer.push(escaped);
+
+        Ok(())
+    }
+
+    fn combine_surrogates(&mut self, value: u32) -> Result<u32, IonParserError> {
+        if !(0xD800..0xDC00).contains(&value) {
+            return Ok(value);
+        }
+
+        if !self.starts_with(b"\\u")? {
+            return Err(invalid_escape());
+        }
+
+        self.skip(2)?;
+
+        let low = self.consume_hex_digits(4)?;
+
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(invalid_escape());
+        }
+
+        Ok(0x10000 + ((value - 0xD800) << 10) + (low - 0xDC00))
+    }
+
+    fn consume_hex_digits(&mut self, len: usize) -> Result<u32, IonParserError> {
+        let mut value: u32 = 0;
+
+        for _ in 0..len {
+            let digit = (self.consume_byte()? as char)
+                .to_digit(16)
+                .ok_or_else(invalid_escape)?;
+
+            value = (value << 4) | digit;
+        }
+
+        Ok(value)
+    }
+
+    fn fill_buffer(&mut self) -> Result<bool, IonParserError> {
+        if self.is_reader_finished {
+            return Ok(false);
+        }
+
+        self.buffer.drain(..self.index);
+        self.index = 0;
+
+        let previous_len = self.buffer.len();
+        self.buffer.resize(previous_len + READ_CHUNK_SIZE, 0);
+
+        let read_bytes = loop {
+            match self.reader.read(&mut self.buffer[previous_len..]) {
+                Ok(read_bytes) => break read_bytes,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.buffer.truncate(previous_len);
+                    return Err(IonParserError::BinaryError(ParsingError::ErrorReadingData(
+                        error,
+                    )));
+                }
+            }
+        };
+
+        self.buffer.truncate(previous_len + read_bytes);
+
+        if read_bytes == 0 {
+            self.is_reader_finished = true;
+        }
+
+        Ok(read_bytes > 0)
+    }
+}
+
+#[inline]
+pub(crate) fn unexpected_character(byte: u8) -> IonParserError {
+    IonParserError::TextError(TextParsingError::UnexpectedCharacter(byte as char))
+}
+
+#[inline]
+fn invalid_escape() -> IonParserError {
+    IonParserError::TextError(TextParsingError::InvalidEscapeSequence)
+}
+
+#[inline]
+fn push_raw_byte(buffer: &mut Vec<u8>, byte: u8, mode: StringMode) -> Result<(), IonParserError> {
+    let is_allowed_whitespace = byte == b'\t' || byte == 0x0B || byte == 0x0C;
+
+    if byte < 0x20 && !is_allowed_whitespace {
+        return Err(IonParserError::TextError(
+            TextParsingError::InvalidStringCharacter(byte as char),
+        ));
+    }
+
+    if mode == StringMode::Clob && byte > 0x7F {
+        return Err(IonParserError::TextError(
+            TextParsingError::InvalidClobCharacter(byte as char),
+        ));
+    }
+
+    buffer.push(byte);
+
+    Ok(())
+}
+
+#[inline]
+fn push_code_point(buffer: &mut Vec<u8>, value: u32) -> Result<(), IonParserError> {
+    let character = char::from_u32(value).ok_or_else(invalid_escape)?;
+    let mut encoded = [0u8; 4];
+    buffer.extend_from_slice(character.encode_utf8(&mut encoded).as_bytes());
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | 0x0B | 0x0C)
+}
+
+#[inline]
+pub(crate) fn is_identifier_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'$'
+}
+
+#[inline]
+pub(crate) fn is_identifier_part(byte: u8) -> bool {
+    is_identifier_start(byte) || byte.is_ascii_digit()
+}
+
+#[inline]
+pub(crate) fn is_operator_part(byte: u8) -> bool {
+    b"!#%&*+-./;<=>?@^`|~".contains(&byte)
+}
+
+#[inline]
+fn is_numeric_part(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'+' | b'-' | b':')
+}
+
+#[inline]
+pub(crate) fn is_value_stop_character(byte: u8) -> bool {
+    is_whitespace(byte) || b"{}[](),\"'".contains(&byte)
+}
+
+fn decode_base64(content: &[u8]) -> Result<Vec<u8>, IonParserError> {
+    let invalid_base64 = || IonParserError::TextError(TextParsingError::InvalidBase64);
+
+    if !content.len().is_multiple_of(4) {
+        return Err(invalid_base64());
+    }
+
+    let padding = content
+        .iter()
+        .rev()
+        .take_while(|byte| **byte == b'=')
+        .count();
+
+    if padding > 2 || content[..content.len() - padding].contains(&b'=') {
+        return Err(invalid_base64());
+    }
+
+    let mut result = Vec::with_capacity(content.len() / 4 * 3);
+
+    for chunk in content.chunks(4) {
+        let mut group: u32 = 0;
+
+        for byte in chunk {
+            let value = match byte {
+                b'A'..=b'Z' => byte - b'A',
+                b'a'..=b'z' => byte - b'a' + 26,
+                b'0'..=b'9' => byte - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                _ => 0,
+            };
+
+            group = (group << 6) | u32::from(value);
+        }
+
+        result.extend_from_slice(&group.to_be_bytes()[1..]);
+    }
+
+    result.truncate(result.len() - padding);
+
+    Ok(result)
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+/// This errors indicate a problem in the Ion text syntax. It comes always
+/// wrapped by the "TextError" of the error type "IonParserError".
+#[derive(Eq, PartialEq, Debug, Error)]
+pub enum TextParsingError {
+    #[error("Unexpected character {0:?}")]
+    UnexpectedCharacter(char),
+    #[error("The ion text ended in the middle of a value")]
+    UnexpectedEndOfStream,
+    #[error("Block comment is not closed")]
+    UnterminatedComment,
+    #[error("Invalid escape sequence in string, symbol or clob")]
+    InvalidEscapeSequence,
+    #[error("Strings and symbols cannot contain control characters or new lines")]
+    InvalidStringCharacter(char),
+    #[error("Clobs can only contain ASCII characters")]
+    InvalidClobCharacter(char),
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+    #[error("Invalid timestamp: {0}")]
+    InvalidTimestamp(String),
+    #[error("Invalid base64 content in blob")]
+    InvalidBase64,
+    #[error("Invalid null type: null.{0}")]
+    InvalidNullType(String),
+    #[error("Keywords cannot be used as annotations: {0}")]
+    KeywordAsAnnotation(String),
+    #[error("Only Ion 1.0 is supported, found version marker {0}")]
+    UnsupportedVersion(String),
+}